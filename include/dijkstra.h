@@ -0,0 +1,131 @@
+#ifndef DIJKSTRA_H
+#define DIJKSTRA_H
+
+/* Generated by cbindgen from src/ffi.rs. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Outcome of a call that can fail.
+ */
+typedef enum DijkstraStatus {
+  DIJKSTRA_STATUS_OK = 0,
+  /**
+   * A pointer argument was null.
+   */
+  DIJKSTRA_STATUS_NULL_POINTER,
+  /**
+   * A vertex id does not exist.
+   */
+  DIJKSTRA_STATUS_UNKNOWN_VERTEX,
+  /**
+   * A cost is NaN.
+   */
+  DIJKSTRA_STATUS_INVALID_COST,
+  /**
+   * The target cannot be reached from the source.
+   */
+  DIJKSTRA_STATUS_NO_PATH,
+  /**
+   * The output buffer cannot hold the path, whose length was written to
+   * `out_len`.
+   */
+  DIJKSTRA_STATUS_BUFFER_TOO_SMALL,
+} DijkstraStatus;
+
+/**
+ * A graph whose vertices are plain ids and whose edges cost a `double`,
+ * opaque to C.
+ *
+ * The functions taking it are declared in `include/dijkstra.h`, which is
+ * regenerated with `cbindgen --config cbindgen.toml --output
+ * include/dijkstra.h` after changing this module. Link against the output
+ * of `cargo rustc --lib --release --features ffi --crate-type staticlib`.
+ */
+typedef struct DijkstraGraph DijkstraGraph;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Creates an empty graph, to be released with [`dijkstra_graph_free`].
+ */
+struct DijkstraGraph *dijkstra_graph_new(void);
+
+/**
+ * Releases a graph. Does nothing if `graph` is null.
+ *
+ * # Safety
+ *
+ * `graph` must be null or come from [`dijkstra_graph_new`] and not have been
+ * released already.
+ */
+void dijkstra_graph_free(struct DijkstraGraph *graph);
+
+/**
+ * Adds a vertex and returns its id.
+ *
+ * # Safety
+ *
+ * `graph` must be a live graph from [`dijkstra_graph_new`].
+ */
+size_t dijkstra_graph_add_vertex(struct DijkstraGraph *graph);
+
+/**
+ * Adds a directed edge from `from` to `to`.
+ *
+ * # Safety
+ *
+ * `graph` must be null or a live graph from [`dijkstra_graph_new`].
+ */
+enum DijkstraStatus dijkstra_graph_add_edge(struct DijkstraGraph *graph,
+                                            size_t from,
+                                            size_t to,
+                                            double cost);
+
+/**
+ * Adds an edge between `a` and `b` that can be traversed in both
+ * directions.
+ *
+ * # Safety
+ *
+ * `graph` must be null or a live graph from [`dijkstra_graph_new`].
+ */
+enum DijkstraStatus dijkstra_graph_add_edge_undirected(struct DijkstraGraph *graph,
+                                                       size_t a,
+                                                       size_t b,
+                                                       double cost);
+
+/**
+ * Finds the cheapest path from `from` to `to`, writes the ids along it to
+ * `out_ids`, which has room for `capacity` ids, their number to `out_len`
+ * and its cost to `out_cost`.
+ *
+ * If the path is longer than `capacity`, writes only its length and returns
+ * [`DijkstraStatus::BufferTooSmall`], so the call can be repeated with a
+ * larger buffer. `out_ids` may be null when `capacity` is 0.
+ *
+ * # Safety
+ *
+ * `graph` must be null or a live graph from [`dijkstra_graph_new`],
+ * `out_ids` must be valid for writing `capacity` ids, and `out_len` and
+ * `out_cost` must be null or valid for writing.
+ */
+enum DijkstraStatus dijkstra_graph_shortest_path(const struct DijkstraGraph *graph,
+                                                 size_t from,
+                                                 size_t to,
+                                                 size_t *out_ids,
+                                                 size_t capacity,
+                                                 size_t *out_len,
+                                                 double *out_cost);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* DIJKSTRA_H */
@@ -0,0 +1,156 @@
+use std::ops::ControlFlow;
+
+use crate::progress::Progress;
+use crate::{AdjacencyMatrix, Graph, GraphError, GraphStorage};
+
+/// Shortest path distances between every pair of vertices, with the
+/// predecessor matrix needed to reconstruct the paths themselves.
+#[derive(Debug, Clone)]
+pub struct AllPairsShortestPaths {
+    /// `distances[from][to]`, or infinity if `to` is unreachable from `from`.
+    pub distances: Vec<Vec<f64>>,
+    /// `predecessors[from][to]` is the vertex before `to` on the path from `from`.
+    pub predecessors: Vec<Vec<Option<usize>>>,
+}
+
+impl AllPairsShortestPaths {
+    /// Returns the cost of the shortest path from `from` to `to`.
+    pub fn distance(&self, from: usize, to: usize) -> f64 {
+        self.distances[from][to]
+    }
+
+    /// Returns the vertex ids along the shortest path from `from` to `to`, or
+    /// `None` if there is no such path.
+    pub fn path(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        if self.distances[from][to] == f64::INFINITY {
+            return None;
+        }
+
+        let mut path = vec![to];
+        let mut at = to;
+        while at != from {
+            at = self.predecessors[from][at]?;
+            path.push(at);
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Clone,
+{
+    /// Computes shortest paths between all pairs of vertices with
+    /// Floyd-Warshall. Negative edge costs are supported.
+    ///
+    /// Fails with [`GraphError::NegativeCycle`] if the graph has a negative cycle.
+    pub fn all_pairs_shortest_paths(&self) -> Result<AllPairsShortestPaths, GraphError> {
+        self.all_pairs_shortest_paths_with_progress(|_| ControlFlow::Continue(()))
+    }
+
+    /// Like [`Graph::all_pairs_shortest_paths`], but calls `progress` with
+    /// the percentage done as the computation goes on, and stops when it
+    /// returns [`ControlFlow::Break`].
+    ///
+    /// Fails with [`GraphError::Cancelled`] if stopped.
+    pub fn all_pairs_shortest_paths_with_progress<P>(
+        &self,
+        progress: P,
+    ) -> Result<AllPairsShortestPaths, GraphError>
+    where
+        P: FnMut(f64) -> ControlFlow<()>,
+    {
+        span!(
+            INFO,
+            "Graph::all_pairs_shortest_paths",
+            vertices = self.vertices.len()
+        );
+        floyd_warshall(self, |id| self.entry_cost(id), progress)
+    }
+}
+
+impl AdjacencyMatrix {
+    /// Computes shortest paths between all pairs of vertices with
+    /// Floyd-Warshall, as [`Graph::all_pairs_shortest_paths`] does.
+    ///
+    /// Fails with [`GraphError::NegativeCycle`] if the graph has a negative cycle.
+    pub fn all_pairs_shortest_paths(&self) -> Result<AllPairsShortestPaths, GraphError> {
+        self.all_pairs_shortest_paths_with_progress(|_| ControlFlow::Continue(()))
+    }
+
+    /// Like [`AdjacencyMatrix::all_pairs_shortest_paths`], but calls
+    /// `progress` with the percentage done as the computation goes on, and
+    /// stops when it returns [`ControlFlow::Break`].
+    ///
+    /// Fails with [`GraphError::Cancelled`] if stopped.
+    pub fn all_pairs_shortest_paths_with_progress<P>(
+        &self,
+        progress: P,
+    ) -> Result<AllPairsShortestPaths, GraphError>
+    where
+        P: FnMut(f64) -> ControlFlow<()>,
+    {
+        span!(
+            INFO,
+            "AdjacencyMatrix::all_pairs_shortest_paths",
+            vertices = self.vertex_count()
+        );
+        floyd_warshall(self, |_| 0.0, progress)
+    }
+}
+
+/// Runs Floyd-Warshall over the edges of `storage`, each costing `entry` of
+/// the vertex it leads to on top.
+fn floyd_warshall<S, N, P>(
+    storage: &S,
+    entry: N,
+    progress: P,
+) -> Result<AllPairsShortestPaths, GraphError>
+where
+    S: GraphStorage,
+    N: Fn(usize) -> f64,
+    P: FnMut(f64) -> ControlFlow<()>,
+{
+    let mut progress = Progress::start(progress)?;
+    let n = storage.id_bound();
+    let mut distances = vec![vec![f64::INFINITY; n]; n];
+    let mut predecessors = vec![vec![None; n]; n];
+
+    for from in (0..n).filter(|id| storage.contains_vertex(*id)) {
+        distances[from][from] = 0.0;
+        for (to, cost) in storage.edges_from(from) {
+            let cost = cost + entry(to);
+            if cost < distances[from][to] {
+                distances[from][to] = cost;
+                predecessors[from][to] = Some(from);
+            }
+        }
+    }
+
+    for k in 0..n {
+        progress.steps(k, n)?;
+        for i in 0..n {
+            if distances[i][k] == f64::INFINITY {
+                continue;
+            }
+            for j in 0..n {
+                let new_dist = distances[i][k] + distances[k][j];
+                if new_dist < distances[i][j] {
+                    distances[i][j] = new_dist;
+                    predecessors[i][j] = predecessors[k][j];
+                }
+            }
+        }
+    }
+
+    if (0..n).any(|i| distances[i][i] < 0.0) {
+        return Err(GraphError::NegativeCycle);
+    }
+    progress.finish();
+
+    Ok(AllPairsShortestPaths {
+        distances,
+        predecessors,
+    })
+}
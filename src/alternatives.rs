@@ -0,0 +1,92 @@
+use std::collections::HashSet;
+
+use crate::{Graph, GraphError, Path};
+
+impl<T> Graph<T>
+where
+    T: Clone,
+{
+    /// Returns up to `limit` meaningfully different paths from `from` to `to`,
+    /// starting with the shortest, using the via-vertex method: each
+    /// alternative is the shortest path through some vertex, preferring the
+    /// cheapest such paths.
+    ///
+    /// An alternative costs at most `max_stretch` times the shortest path,
+    /// e.g. `1.25` for 25% more, visits no vertex twice, and shares at most
+    /// `max_overlap` of its cost, as a fraction between 0 and 1, with each
+    /// path returned before it. Unlike [`Graph::get_k_shortest_paths`], this
+    /// skips the many paths that only differ by a small detour.
+    ///
+    /// Fails if either vertex is unknown or `to` is not reachable from `from`.
+    pub fn get_alternative_paths(
+        &self,
+        from: usize,
+        to: usize,
+        limit: usize,
+        max_stretch: f64,
+        max_overlap: f64,
+    ) -> Result<Vec<Path<T>>, GraphError> {
+        let forward = self.shortest_paths_from(from)?;
+        let backward = self.shortest_paths_to(to)?;
+        let Some(optimal) = forward.distance_to(to) else {
+            return Err(GraphError::NoPathFound { from, to });
+        };
+
+        // Candidates by the cost of the cheapest path through them.
+        let mut via: Vec<(f64, usize)> = self
+            .vertices
+            .iter()
+            .flatten()
+            .filter_map(|vertex| {
+                let cost = forward.distance_to(vertex.id)? + backward.distance_from(vertex.id)?;
+                Some((cost, vertex.id))
+            })
+            .filter(|(cost, _)| *cost <= optimal * max_stretch)
+            .collect();
+        via.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+
+        let mut paths: Vec<Path<T>> = Vec::new();
+        // Steps of each path returned so far.
+        let mut chosen: Vec<HashSet<(usize, usize)>> = Vec::new();
+        // Vertices on those paths, which mostly lead to the same paths again.
+        let mut on_chosen = vec![false; self.vertices.len()];
+        for (cost, id) in via {
+            if paths.len() >= limit {
+                break;
+            }
+            if on_chosen[id] {
+                continue;
+            }
+            let mut ids = forward.path_to(id).unwrap();
+            ids.extend(&backward.path_from(id).unwrap()[1..]);
+
+            let mut seen = HashSet::new();
+            if !ids.iter().all(|id| seen.insert(*id)) {
+                continue;
+            }
+            let path = self.to_path(
+                ids.iter().map(|id| self.vertex(*id).clone()).collect(),
+                cost,
+            );
+            let too_similar = chosen.iter().any(|steps| {
+                let shared: f64 = ids
+                    .windows(2)
+                    .zip(path.costs())
+                    .filter(|(step, _)| steps.contains(&(step[0], step[1])))
+                    .map(|(_, cost)| cost)
+                    .sum();
+                shared > max_overlap * cost
+            });
+            if too_similar {
+                continue;
+            }
+
+            for id in &ids {
+                on_chosen[*id] = true;
+            }
+            chosen.push(ids.windows(2).map(|step| (step[0], step[1])).collect());
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+}
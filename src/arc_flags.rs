@@ -0,0 +1,191 @@
+use crate::{Graph, GraphError, Path, Vertex};
+
+/// Per-edge flags over a partition of the graph into regions, marking the
+/// edges that lie on a shortest path into each region, so that queries only
+/// follow edges flagged for the target's region.
+///
+/// Built with [`Graph::build_arc_flags`] or
+/// [`Graph::build_arc_flags_with_regions`] and queried with
+/// [`Graph::get_shortest_path_arc_flags`]. Flags go stale when the graph
+/// changes.
+pub struct ArcFlags {
+    /// Region of each vertex, indexed by id.
+    regions: Vec<usize>,
+    region_count: usize,
+    /// Targets of the flagged edges out of each vertex, sorted by id, with
+    /// parallel edges sharing one entry.
+    targets: Vec<Vec<usize>>,
+    /// Region bits of each entry in `targets`, `words` words per entry.
+    bits: Vec<Vec<u64>>,
+    words: usize,
+}
+
+impl ArcFlags {
+    pub fn region_count(&self) -> usize {
+        self.region_count
+    }
+
+    /// Returns the region of the vertex with the given id.
+    pub fn region_of(&self, id: usize) -> Option<usize> {
+        self.regions.get(id).copied()
+    }
+
+    /// Whether an edge from `from` to `to` lies on a shortest path into `region`.
+    fn allows(&self, from: usize, to: usize, region: usize) -> bool {
+        let Ok(index) = self.targets[from].binary_search(&to) else {
+            return false;
+        };
+        let word = self.bits[from][index * self.words + region / 64];
+        word & (1 << (region % 64)) != 0
+    }
+
+    fn flag(&mut self, from: usize, to: usize, region: usize) {
+        let index = self.targets[from].binary_search(&to).unwrap();
+        self.bits[from][index * self.words + region / 64] |= 1 << (region % 64);
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Clone,
+{
+    /// Partitions the graph into up to `region_count` regions around seed
+    /// vertices spread far apart, and precomputes arc flags for them.
+    ///
+    /// Fails if an edge has a negative cost.
+    pub fn build_arc_flags(&self, region_count: usize) -> Result<ArcFlags, GraphError> {
+        let mut seeds: Vec<usize> = self
+            .vertices
+            .iter()
+            .flatten()
+            .take(1)
+            .map(|vertex| vertex.id)
+            .collect();
+        while !seeds.is_empty() && seeds.len() < region_count {
+            let forest = self.multi_source_shortest_paths(&seeds)?;
+            let farthest = self
+                .vertices
+                .iter()
+                .flatten()
+                .map(|vertex| (vertex.id, forest.distance_to(vertex.id)))
+                .filter(|(_, distance)| *distance != Some(0.0))
+                .max_by(|a, b| {
+                    let a = a.1.unwrap_or(f64::INFINITY);
+                    a.total_cmp(&b.1.unwrap_or(f64::INFINITY))
+                });
+            let Some((farthest, _)) = farthest else {
+                break;
+            };
+            seeds.push(farthest);
+        }
+
+        let forest = self.multi_source_shortest_paths(&seeds)?;
+        let region_of_seed = |seed| seeds.iter().position(|other| *other == seed);
+        self.build_arc_flags_with_regions(|vertex| {
+            forest
+                .source_of(vertex.id)
+                .and_then(region_of_seed)
+                .unwrap_or(0)
+        })
+    }
+
+    /// Precomputes arc flags for the partition given by `region`, which maps
+    /// each vertex to a region numbered from zero.
+    ///
+    /// Fails if an edge has a negative cost.
+    pub fn build_arc_flags_with_regions<R>(&self, region: R) -> Result<ArcFlags, GraphError>
+    where
+        R: Fn(&Vertex<T>) -> usize,
+    {
+        span!(INFO, "Graph::build_arc_flags");
+        if let Some(edge) = self.adjacency.iter().flatten().find(|edge| edge.cost < 0.0) {
+            return Err(GraphError::InvalidCost(edge.cost));
+        }
+
+        let slots = self.vertices.len();
+        let mut regions = vec![0; slots];
+        for vertex in self.vertices.iter().flatten() {
+            regions[vertex.id] = region(vertex);
+        }
+        let region_count = regions.iter().max().map_or(0, |max| max + 1);
+        let words = region_count.div_ceil(64);
+
+        let mut targets: Vec<Vec<usize>> = (0..slots)
+            .map(|id| match self.vertices[id] {
+                Some(_) => self.outgoing(id).map(|edge| edge.to).collect(),
+                None => Vec::new(),
+            })
+            .collect();
+        for targets in &mut targets {
+            targets.sort_unstable();
+            targets.dedup();
+        }
+        let bits = targets
+            .iter()
+            .map(|targets| vec![0; targets.len() * words])
+            .collect();
+        let mut flags = ArcFlags {
+            regions,
+            region_count,
+            targets,
+            bits,
+            words,
+        };
+
+        for vertex in self.vertices.iter().flatten() {
+            let inside = flags.regions[vertex.id];
+            let mut boundary = false;
+            for edge in self.incoming(vertex.id) {
+                if flags.regions[edge.to] == inside {
+                    flags.flag(edge.to, vertex.id, inside);
+                } else {
+                    boundary = true;
+                }
+            }
+            if !boundary {
+                continue;
+            }
+
+            // Every edge on a shortest path to a boundary vertex may start a
+            // shortest path into its region.
+            let dist = self.distances_to(vertex.id);
+            for from in self.vertices.iter().flatten() {
+                for edge in self.outgoing(from.id) {
+                    let tight =
+                        dist[from.id] == dist[edge.to] + edge.cost + self.entry_cost(edge.to);
+                    if tight && dist[from.id].is_finite() {
+                        flags.flag(from.id, edge.to, inside);
+                    }
+                }
+            }
+        }
+
+        Ok(flags)
+    }
+
+    /// Like [`Graph::get_shortest_path`], but only follows edges flagged for
+    /// the region of `to`.
+    pub fn get_shortest_path_arc_flags(
+        &self,
+        from: usize,
+        to: usize,
+        flags: &ArcFlags,
+    ) -> Result<Path<T>, GraphError> {
+        span!(DEBUG, "Graph::get_shortest_path_arc_flags", from, to);
+        self.get_vertex(from)?;
+        self.get_vertex(to)?;
+        let region = flags.region_of(to).ok_or(GraphError::UnknownVertex(to))?;
+
+        let (dist, prev) = self.search_filtered(
+            from,
+            Some(to),
+            |_| 0.0,
+            |source, edge| flags.allows(source.id, edge.to, region),
+        );
+        if dist[to] == f64::INFINITY {
+            return Err(GraphError::NoPathFound { from, to });
+        }
+
+        Ok(self.to_path(self.reconstruct_path(&prev, from, to), dist[to]))
+    }
+}
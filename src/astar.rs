@@ -0,0 +1,161 @@
+use alloc::vec;
+
+use crate::graph::CompactId;
+use crate::indexed_heap::IndexedHeap;
+use crate::{Graph, GraphError, Path, Vertex};
+
+/// Estimates the remaining cost from one vertex value to another for A*.
+///
+/// For A* to return an optimal path, the heuristic must be consistent: the
+/// estimate from a vertex may never exceed the cost of an edge out of it
+/// plus the estimate from the vertex the edge leads to, which also keeps it
+/// from overestimating the true cost. Vertices are not reopened, so a
+/// heuristic that only never overestimates is not enough. Any
+/// `Fn(&T, &T) -> f64` closure is a heuristic.
+pub trait Heuristic<T> {
+    fn estimate(&self, from: &T, to: &T) -> f64;
+
+    /// Estimates the remaining cost between two vertices. Defaults to
+    /// [`Heuristic::estimate`] on their values; heuristics that work on ids,
+    /// such as [`Landmarks`](crate::Landmarks), override it.
+    fn estimate_vertices(&self, from: &Vertex<T>, to: &Vertex<T>) -> f64
+    where
+        T: Clone,
+    {
+        self.estimate(&from.value, &to.value)
+    }
+}
+
+impl<T, F> Heuristic<T> for F
+where
+    F: Fn(&T, &T) -> f64,
+{
+    fn estimate(&self, from: &T, to: &T) -> f64 {
+        self(from, to)
+    }
+}
+
+impl<T, E> Graph<T, f64, E>
+where
+    T: Clone,
+    E: Clone,
+{
+    /// Like [`Graph::get_shortest_path`], but guides the search towards `to`
+    /// with a consistent `heuristic` to settle fewer vertices.
+    pub fn get_shortest_path_astar<H>(
+        &self,
+        from: usize,
+        to: usize,
+        heuristic: H,
+    ) -> Result<Path<T, f64, E>, GraphError>
+    where
+        H: Heuristic<T>,
+    {
+        span!(DEBUG, "Graph::get_shortest_path_astar", from, to);
+        self.get_vertex(from)?;
+        let end = self.get_vertex(to)?;
+
+        let (dist, prev) = self.search(from, Some(to), |vertex| {
+            heuristic.estimate_vertices(vertex, end)
+        });
+        if dist[to] == f64::INFINITY {
+            return Err(GraphError::NoPathFound { from, to });
+        }
+
+        Ok(self.to_path(self.reconstruct_path(&prev, from, to), dist[to]))
+    }
+
+    /// Like [`Graph::get_shortest_path_astar`], but weighs the heuristic by
+    /// `epsilon`, which settles fewer vertices the larger it is and returns
+    /// a path costing at most `epsilon` times the cheapest one if the
+    /// heuristic is consistent. An `epsilon` of one is plain A*.
+    ///
+    /// Fails if either vertex is unknown, `to` is not reachable from `from`,
+    /// or `epsilon` is less than one or NaN.
+    pub fn get_shortest_path_weighted_astar<H>(
+        &self,
+        from: usize,
+        to: usize,
+        heuristic: H,
+        epsilon: f64,
+    ) -> Result<Path<T, f64, E>, GraphError>
+    where
+        H: Heuristic<T>,
+    {
+        span!(
+            DEBUG,
+            "Graph::get_shortest_path_weighted_astar",
+            from,
+            to,
+            epsilon
+        );
+        self.get_vertex(from)?;
+        let end = self.get_vertex(to)?;
+        if epsilon.is_nan() || epsilon < 1.0 {
+            return Err(GraphError::InvalidCost(epsilon));
+        }
+
+        let (dist, prev) = self.search(from, Some(to), |vertex| {
+            epsilon * heuristic.estimate_vertices(vertex, end)
+        });
+        if dist[to] == f64::INFINITY {
+            return Err(GraphError::NoPathFound { from, to });
+        }
+
+        Ok(self.to_path(self.reconstruct_path(&prev, from, to), dist[to]))
+    }
+
+    /// Finds some path from `from` to `to` by greedy best-first search,
+    /// always moving on from the vertex the heuristic deems closest to `to`
+    /// and ignoring the cost so far. Usually much faster than A*, but the
+    /// path may cost arbitrarily more than the cheapest one.
+    ///
+    /// Fails if either vertex is unknown or `to` is not reachable from `from`.
+    pub fn get_path_greedy<H>(
+        &self,
+        from: usize,
+        to: usize,
+        heuristic: H,
+    ) -> Result<Path<T, f64, E>, GraphError>
+    where
+        H: Heuristic<T>,
+    {
+        span!(DEBUG, "Graph::get_path_greedy", from, to);
+        self.get_vertex(from)?;
+        let end = self.get_vertex(to)?;
+
+        let slots = self.vertices.len();
+        // Cost along the search tree, which is fixed once a vertex is
+        // reached except for taking the cheapest of parallel edges.
+        let mut dist = vec![f64::INFINITY; slots];
+        let mut prev = vec![None; slots];
+        let mut queue = IndexedHeap::new(slots);
+        dist[from] = 0.0;
+        queue.push_or_decrease(from, 0.0);
+
+        while let Some((_, current)) = queue.pop() {
+            if current == to {
+                return Ok(self.to_path(self.reconstruct_path(&prev, from, to), dist[to]));
+            }
+            for edge in self.outgoing(current) {
+                let new_dist = dist[current] + edge.cost + self.entry_cost(edge.to);
+                let reached = dist[edge.to] < f64::INFINITY;
+                if new_dist == f64::INFINITY
+                    || (reached && prev[edge.to] != Some(current as CompactId))
+                {
+                    continue;
+                }
+                if !reached {
+                    let estimate = heuristic.estimate_vertices(self.vertex(edge.to), end);
+                    queue.push_or_decrease(edge.to, estimate);
+                }
+                if new_dist < dist[edge.to] {
+                    dist[edge.to] = new_dist;
+                    prev[edge.to] = Some(current as CompactId);
+                }
+            }
+        }
+
+        Err(GraphError::NoPathFound { from, to })
+    }
+}
@@ -0,0 +1,116 @@
+use crate::graph::{CompactId, Predecessors};
+use crate::{Graph, GraphError, Path};
+
+impl<T> Graph<T>
+where
+    T: Clone,
+{
+    /// Like [`Graph::get_shortest_path`], but supports negative edge costs.
+    ///
+    /// Fails with [`GraphError::NegativeCycle`] if a negative cycle is
+    /// reachable from `from`. [`Graph::find_negative_cycle`] returns one.
+    pub fn get_shortest_path_bellman_ford(
+        &self,
+        from: usize,
+        to: usize,
+    ) -> Result<Path<T>, GraphError> {
+        span!(DEBUG, "Graph::get_shortest_path_bellman_ford", from, to);
+        self.get_vertex(from)?;
+        self.get_vertex(to)?;
+
+        let mut dist = vec![f64::INFINITY; self.vertices.len()];
+        dist[from] = 0.0;
+
+        let (dist, prev) = self.bellman_ford(dist)?;
+        if dist[to] == f64::INFINITY {
+            return Err(GraphError::NoPathFound { from, to });
+        }
+
+        Ok(self.to_path(self.reconstruct_path(&prev, from, to), dist[to]))
+    }
+
+    /// Returns a cycle of negative total cost anywhere in the graph, as the
+    /// ids along it, each with an edge to the next and the last with an edge
+    /// back to the first, together with its total cost. Returns `None` if
+    /// there is no such cycle, i.e. if shortest paths are well defined
+    /// between every pair of vertices.
+    pub fn find_negative_cycle(&self) -> Option<(Vec<usize>, f64)> {
+        // Starting every vertex at zero finds cycles wherever they are, as
+        // if from an extra source linked to all vertices.
+        let slots = self.vertices.len();
+        let mut dist = vec![0.0; slots];
+        let mut prev: Predecessors = vec![None; slots];
+        // Cost of the edge each vertex was last lowered by, with its entry
+        // cost.
+        let mut step = vec![0.0; slots];
+
+        // Anything still lowered after as many rounds as there are vertices
+        // hangs off a negative cycle in the predecessors.
+        let mut lowered = None;
+        for _ in 0..=self.vertices.iter().flatten().count() {
+            lowered = None;
+            for vertex in self.vertices.iter().flatten() {
+                for edge in self.outgoing(vertex.id) {
+                    let cost = edge.cost + self.entry_cost(edge.to);
+                    let new_dist = dist[vertex.id] + cost;
+                    if new_dist < dist[edge.to] {
+                        dist[edge.to] = new_dist;
+                        prev[edge.to] = Some(vertex.id as CompactId);
+                        step[edge.to] = cost;
+                        lowered = Some(edge.to);
+                    }
+                }
+            }
+            lowered?;
+        }
+
+        // Following the predecessors long enough ends up on the cycle.
+        let mut start = lowered.unwrap();
+        for _ in 0..slots {
+            start = prev[start].unwrap() as usize;
+        }
+        let mut cycle = vec![start];
+        let mut cost = step[start];
+        let mut at = prev[start].unwrap() as usize;
+        while at != start {
+            cycle.push(at);
+            cost += step[at];
+            at = prev[at].unwrap() as usize;
+        }
+        cycle.reverse();
+        Some((cycle, cost))
+    }
+
+    /// Relaxes every edge, plus the entry cost of the vertex it leads to,
+    /// until `dist` converges, starting from the given initial distances.
+    pub(crate) fn bellman_ford(
+        &self,
+        mut dist: Vec<f64>,
+    ) -> Result<(Vec<f64>, Predecessors), GraphError> {
+        let mut prev: Predecessors = vec![None; self.vertices.len()];
+
+        for _ in 0..self.vertices.len() {
+            let mut changed = false;
+            for vertex in self.vertices.iter().flatten() {
+                if dist[vertex.id] == f64::INFINITY {
+                    continue;
+                }
+
+                for edge in self.outgoing(vertex.id) {
+                    let new_dist = dist[vertex.id] + edge.cost + self.entry_cost(edge.to);
+                    if new_dist < dist[edge.to] {
+                        prev[edge.to] = Some(vertex.id as CompactId);
+                        dist[edge.to] = new_dist;
+                        changed = true;
+                    }
+                }
+            }
+
+            if !changed {
+                return Ok((dist, prev));
+            }
+        }
+
+        Err(GraphError::NegativeCycle)
+    }
+}
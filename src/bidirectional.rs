@@ -0,0 +1,159 @@
+use std::collections::BinaryHeap;
+
+use crate::graph::{CompactId, Predecessors};
+use crate::min_non_nan::MinNonNan;
+use crate::{Edge, Graph, GraphError, Heuristic, Path};
+
+impl<T, E> Graph<T, f64, E>
+where
+    T: Clone,
+    E: Clone,
+{
+    /// Like [`Graph::get_shortest_path`], but searches forward from `from` and
+    /// backward from `to` at the same time until the two searches meet.
+    pub fn get_shortest_path_bidirectional(
+        &self,
+        from: usize,
+        to: usize,
+    ) -> Result<Path<T, f64, E>, GraphError> {
+        span!(DEBUG, "Graph::get_shortest_path_bidirectional", from, to);
+        self.get_vertex(from)?;
+        self.get_vertex(to)?;
+        self.search_bidirectional(from, to, |_| 0.0)
+    }
+
+    /// Like [`Graph::get_shortest_path_bidirectional`], but guides both
+    /// searches with `heuristic`, as [`Graph::get_shortest_path_astar`] does
+    /// one, to settle fewer vertices. The forward search estimates the cost
+    /// left to `to` and the backward one the cost from `from`, averaged into
+    /// potentials that keep both searches consistent.
+    ///
+    /// The heuristic must be consistent, never overestimating the cost of
+    /// an edge plus the estimate beyond it, and finite for the path to be the
+    /// cheapest one.
+    ///
+    /// Fails if either vertex is unknown or `to` is not reachable from `from`.
+    pub fn get_shortest_path_bidirectional_astar<H>(
+        &self,
+        from: usize,
+        to: usize,
+        heuristic: H,
+    ) -> Result<Path<T, f64, E>, GraphError>
+    where
+        H: Heuristic<T>,
+    {
+        span!(
+            DEBUG,
+            "Graph::get_shortest_path_bidirectional_astar",
+            from,
+            to
+        );
+        let start = self.get_vertex(from)?;
+        let end = self.get_vertex(to)?;
+        self.search_bidirectional(from, to, |id| {
+            let vertex = self.vertex(id);
+            let to_end = heuristic.estimate_vertices(vertex, end);
+            let from_start = heuristic.estimate_vertices(start, vertex);
+            (to_end - from_start) / 2.0
+        })
+    }
+
+    /// Searches forward from `from` and backward from `to` until the two
+    /// searches meet, ordering the forward frontier by distance plus
+    /// `potential` and the backward one by distance minus it. A zero
+    /// potential is bidirectional Dijkstra.
+    fn search_bidirectional<P>(
+        &self,
+        from: usize,
+        to: usize,
+        potential: P,
+    ) -> Result<Path<T, f64, E>, GraphError>
+    where
+        P: Fn(usize) -> f64,
+    {
+        let n = self.vertices.len();
+        let mut dist = [vec![f64::INFINITY; n], vec![f64::INFINITY; n]];
+        let mut visited = [vec![false; n], vec![false; n]];
+        // Forward: predecessor towards `start`. Backward: successor towards `end`.
+        let mut prev: [Predecessors; 2] = [vec![None; n], vec![None; n]];
+        let mut queues = [BinaryHeap::new(), BinaryHeap::new()];
+
+        dist[0][from] = 0.0;
+        dist[1][to] = 0.0;
+        queues[0].push((MinNonNan(potential(from)), from));
+        queues[1].push((MinNonNan(-potential(to)), to));
+
+        let mut best = if from == to { 0.0 } else { f64::INFINITY };
+        let mut meeting = from;
+
+        loop {
+            let top = |queue: &BinaryHeap<(MinNonNan, usize)>| {
+                queue.peek().map_or(f64::INFINITY, |(cost, _)| cost.0)
+            };
+            let (forward_top, backward_top) = (top(&queues[0]), top(&queues[1]));
+            if forward_top + backward_top >= best {
+                break;
+            }
+
+            let side = if forward_top <= backward_top { 0 } else { 1 };
+            let (_, current) = queues[side].pop().unwrap();
+            if visited[side][current] {
+                continue;
+            }
+            visited[side][current] = true;
+
+            // Backward steps lead into `current`, forward ones out of it.
+            let entry = self.entry_cost(current);
+            let edges: Box<dyn Iterator<Item = &Edge<f64, E>>> = if side == 0 {
+                Box::new(self.outgoing(current))
+            } else {
+                Box::new(self.incoming(current))
+            };
+            for edge in edges {
+                if visited[side][edge.to] {
+                    continue;
+                }
+
+                let entered = if side == 0 {
+                    self.entry_cost(edge.to)
+                } else {
+                    entry
+                };
+                let new_dist = dist[side][current] + edge.cost + entered;
+                if new_dist < dist[side][edge.to] {
+                    prev[side][edge.to] = Some(current as CompactId);
+                    dist[side][edge.to] = new_dist;
+                    let sign = if side == 0 { 1.0 } else { -1.0 };
+                    let key = new_dist + sign * potential(edge.to);
+                    queues[side].push((MinNonNan(key), edge.to));
+                }
+
+                let through = dist[0][edge.to] + dist[1][edge.to];
+                if through < best {
+                    best = through;
+                    meeting = edge.to;
+                }
+            }
+        }
+
+        event!(
+            DEBUG,
+            cost = best,
+            settled = visited.iter().flatten().filter(|settled| **settled).count(),
+            "bidirectional search finished"
+        );
+        if best == f64::INFINITY {
+            return Err(GraphError::NoPathFound { from, to });
+        }
+
+        let mut path = self.reconstruct_path(&prev[0], from, meeting);
+        let mut at = meeting;
+        while let Some(next) = prev[1][at] {
+            let next = next as usize;
+            path.push(self.vertex(next).clone());
+            at = next;
+        }
+
+        Ok(self.to_path(path, best))
+    }
+}
@@ -0,0 +1,349 @@
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use crate::{Graph, GraphError, Modes, TravelTimeProfile};
+
+const MAGIC: &[u8; 4] = b"DJKG";
+/// Version of the snapshots written, listing what each version added.
+/// Snapshots of every version up to it are read.
+///
+/// 1. Vertex values and edge costs.
+/// 2. Travel time profiles.
+/// 3. Edge criteria.
+/// 4. Edge capacities.
+/// 5. Travel modes.
+/// 6. Edge ids.
+/// 7. Entry costs.
+const VERSION: u32 = 7;
+
+/// Vertex values that can be stored in a binary snapshot.
+pub trait BinaryValue: Sized {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()>;
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Self>;
+}
+
+macro_rules! impl_binary_value {
+    ($($ty:ty),*) => {
+        $(
+            impl BinaryValue for $ty {
+                fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+                    writer.write_all(&self.to_le_bytes())
+                }
+
+                fn read_from<R: Read>(reader: &mut R) -> io::Result<Self> {
+                    let mut bytes = [0; std::mem::size_of::<$ty>()];
+                    reader.read_exact(&mut bytes)?;
+                    Ok(<$ty>::from_le_bytes(bytes))
+                }
+            }
+        )*
+    };
+}
+
+impl_binary_value!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f64);
+
+impl BinaryValue for usize {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        (*self as u64).write_to(writer)
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Self> {
+        usize::try_from(u64::read_from(reader)?).map_err(invalid_data)
+    }
+}
+
+impl BinaryValue for isize {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        (*self as i64).write_to(writer)
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Self> {
+        isize::try_from(i64::read_from(reader)?).map_err(invalid_data)
+    }
+}
+
+impl BinaryValue for bool {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        (*self as u8).write_to(writer)
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Self> {
+        Ok(u8::read_from(reader)? != 0)
+    }
+}
+
+impl BinaryValue for char {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        (*self as u32).write_to(writer)
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Self> {
+        char::from_u32(u32::read_from(reader)?).ok_or_else(|| invalid_data("invalid char"))
+    }
+}
+
+impl<A: BinaryValue, B: BinaryValue> BinaryValue for (A, B) {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.0.write_to(writer)?;
+        self.1.write_to(writer)
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Self> {
+        Ok((A::read_from(reader)?, B::read_from(reader)?))
+    }
+}
+
+impl<A: BinaryValue, B: BinaryValue, C: BinaryValue> BinaryValue for (A, B, C) {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.0.write_to(writer)?;
+        self.1.write_to(writer)?;
+        self.2.write_to(writer)
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Self> {
+        Ok((
+            A::read_from(reader)?,
+            B::read_from(reader)?,
+            C::read_from(reader)?,
+        ))
+    }
+}
+
+impl BinaryValue for String {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.len().write_to(writer)?;
+        writer.write_all(self.as_bytes())
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let len = usize::read_from(reader)?;
+        let mut bytes = Vec::new();
+        reader.take(len as u64).read_to_end(&mut bytes)?;
+        if bytes.len() != len {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        String::from_utf8(bytes).map_err(invalid_data)
+    }
+}
+
+impl<T: BinaryValue> BinaryValue for Vec<T> {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.len().write_to(writer)?;
+        for item in self {
+            item.write_to(writer)?;
+        }
+        Ok(())
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let len = usize::read_from(reader)?;
+        // A corrupt length must not reserve unbounded memory up front.
+        let mut items = Vec::with_capacity(len.min(1024));
+        for _ in 0..len {
+            items.push(T::read_from(reader)?);
+        }
+        Ok(items)
+    }
+}
+
+impl<T: BinaryValue> BinaryValue for Option<T> {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.is_some().write_to(writer)?;
+        match self {
+            Some(value) => value.write_to(writer),
+            None => Ok(()),
+        }
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Self> {
+        match bool::read_from(reader)? {
+            true => Ok(Some(T::read_from(reader)?)),
+            false => Ok(None),
+        }
+    }
+}
+
+impl BinaryValue for Modes {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.bits().write_to(writer)
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Self> {
+        Ok(Modes::from_bits(u8::read_from(reader)?))
+    }
+}
+
+impl BinaryValue for TravelTimeProfile {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.points.write_to(writer)
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Self> {
+        TravelTimeProfile::new(&Vec::read_from(reader)?).map_err(invalid_data)
+    }
+}
+
+fn invalid_data<E: Into<Box<dyn std::error::Error + Send + Sync>>>(error: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+fn io_error(error: io::Error) -> GraphError {
+    GraphError::Io(error.to_string())
+}
+
+impl<T: BinaryValue> Graph<T>
+where
+    T: Clone,
+{
+    /// Writes a compact binary snapshot of the graph to the file at `path`.
+    pub fn save_binary<P: AsRef<Path>>(&self, path: P) -> Result<(), GraphError> {
+        let mut writer = BufWriter::new(File::create(path).map_err(io_error)?);
+        self.write_binary(&mut writer)?;
+        writer.flush().map_err(io_error)
+    }
+
+    /// Reads a graph from a snapshot file written by [`Graph::save_binary`].
+    pub fn load_binary<P: AsRef<Path>>(path: P) -> Result<Self, GraphError> {
+        let mut reader = BufReader::new(File::open(path).map_err(io_error)?);
+        Self::read_binary(&mut reader)
+    }
+
+    /// Writes a compact binary snapshot of the graph to `writer`.
+    pub fn write_binary<W: Write>(&self, writer: &mut W) -> Result<(), GraphError> {
+        self.encode(writer).map_err(io_error)
+    }
+
+    /// Reads a graph from a snapshot written by [`Graph::write_binary`].
+    pub fn read_binary<R: Read>(reader: &mut R) -> Result<Self, GraphError> {
+        span!(INFO, "Graph::read_binary");
+        Self::decode(reader).map_err(io_error)
+    }
+
+    fn encode<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(MAGIC)?;
+        VERSION.write_to(writer)?;
+
+        self.vertices.len().write_to(writer)?;
+        for vertex in &self.vertices {
+            vertex.is_some().write_to(writer)?;
+            if let Some(vertex) = vertex {
+                vertex.value.write_to(writer)?;
+            }
+        }
+
+        let edge_count: usize = self.adjacency.iter().map(Vec::len).sum();
+        edge_count.write_to(writer)?;
+        for (from, edges) in self.adjacency.iter().enumerate() {
+            for edge in edges {
+                from.write_to(writer)?;
+                edge.to.write_to(writer)?;
+                edge.cost.write_to(writer)?;
+                edge.undirected.write_to(writer)?;
+                edge.profile.write_to(writer)?;
+                edge.criteria.write_to(writer)?;
+                edge.capacity.write_to(writer)?;
+                edge.modes.write_to(writer)?;
+                edge.id.write_to(writer)?;
+            }
+        }
+        self.next_edge_id.write_to(writer)?;
+        self.entry_costs.write_to(writer)?;
+
+        Ok(())
+    }
+
+    fn decode<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid_data("not a graph snapshot"));
+        }
+        let version = u32::read_from(reader)?;
+        if !(1..=VERSION).contains(&version) {
+            return Err(invalid_data(format!(
+                "unsupported snapshot version {}",
+                version
+            )));
+        }
+
+        let mut graph = Graph::new();
+        let slots = usize::read_from(reader)?;
+        for _ in 0..slots {
+            let value = match bool::read_from(reader)? {
+                true => Some(T::read_from(reader)?),
+                false => None,
+            };
+            graph.push_slot(value);
+        }
+
+        let edge_count = usize::read_from(reader)?;
+        for _ in 0..edge_count {
+            let from = usize::read_from(reader)?;
+            let to = usize::read_from(reader)?;
+            let cost = f64::read_from(reader)?;
+            let added = match bool::read_from(reader)? {
+                true => graph.add_edge_undirected(from, to, cost),
+                false => graph.add_edge(from, to, cost),
+            };
+            added.map_err(invalid_data)?;
+
+            // Attributes of later versions, in the order they were added.
+            let profile = match version >= 2 {
+                true => Option::read_from(reader)?,
+                false => None,
+            };
+            let criteria = match version >= 3 {
+                true => Vec::read_from(reader)?,
+                false => Vec::new(),
+            };
+            let capacity = match version >= 4 {
+                true => Option::read_from(reader)?,
+                false => None,
+            };
+            let modes = match version >= 5 {
+                true => Modes::read_from(reader)?,
+                false => Modes::ALL,
+            };
+            let id = match version >= 6 {
+                true => Some(usize::read_from(reader)?),
+                false => None,
+            };
+            let stored = graph.adjacency[from].last_mut().unwrap();
+            let mirrored = graph.reverse_adjacency[to].last_mut().unwrap();
+            for edge in [stored, mirrored] {
+                if let Some(id) = id {
+                    edge.id = id;
+                }
+                edge.modes = modes;
+                edge.capacity = capacity;
+                edge.criteria = criteria.clone();
+                edge.profile = profile.clone();
+            }
+        }
+
+        if version >= 6 {
+            let next_edge_id = usize::read_from(reader)?;
+            let mut ids: Vec<usize> = graph
+                .adjacency
+                .iter()
+                .flatten()
+                .map(|edge| edge.id)
+                .collect();
+            ids.sort_unstable();
+            if ids.windows(2).any(|pair| pair[0] == pair[1]) || ids.last() >= Some(&next_edge_id) {
+                return Err(invalid_data("invalid edge ids"));
+            }
+            graph.next_edge_id = next_edge_id;
+        }
+        if version >= 7 {
+            for (id, cost) in Vec::<f64>::read_from(reader)?.into_iter().enumerate() {
+                if cost != 0.0 && graph.get_vertex(id).is_ok() {
+                    graph.set_entry_cost(id, cost).map_err(invalid_data)?;
+                }
+            }
+        }
+
+        Ok(graph)
+    }
+}
@@ -0,0 +1,101 @@
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::{Graph, GraphError, Path, Vertex, Weight};
+
+/// How many vertices are settled between checks of the clock, which is
+/// slow to read compared to settling a vertex.
+const CLOCK_INTERVAL: usize = 256;
+
+/// Limits on how long a single search may run, for
+/// [`Graph::get_shortest_path_within_budget`], e.g. to keep a
+/// latency-sensitive server thread responsive.
+///
+/// Setters return the budget again, so that they can be chained inline.
+#[derive(Debug, Clone, Default)]
+pub struct Budget<'a> {
+    cancel: Option<&'a AtomicBool>,
+    max_settled: Option<usize>,
+    time_limit: Option<Duration>,
+}
+
+impl<'a> Budget<'a> {
+    /// Creates a budget without any limit.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stops the search once `cancel` is set, e.g. from another thread.
+    pub fn cancel_on(&mut self, cancel: &'a AtomicBool) -> &mut Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    /// Stops the search once it has settled `max_settled` vertices without
+    /// settling the target.
+    pub fn max_settled(&mut self, max_settled: usize) -> &mut Self {
+        self.max_settled = Some(max_settled);
+        self
+    }
+
+    /// Stops the search once it has run for `time_limit`, checked every few
+    /// hundred settled vertices.
+    pub fn time_limit(&mut self, time_limit: Duration) -> &mut Self {
+        self.time_limit = Some(time_limit);
+        self
+    }
+}
+
+impl<T, W> Graph<T, W>
+where
+    T: Clone,
+    W: Weight,
+{
+    /// Like [`Graph::get_shortest_path`], but gives up once `budget` is
+    /// exhausted.
+    ///
+    /// Fails if either vertex is unknown or `to` is not reachable from `from`,
+    /// and with [`GraphError::Cancelled`] if the budget ran out before the
+    /// target was settled.
+    pub fn get_shortest_path_within_budget(
+        &self,
+        from: usize,
+        to: usize,
+        budget: &Budget,
+    ) -> Result<Path<T, W>, GraphError> {
+        span!(DEBUG, "Graph::get_shortest_path_within_budget", from, to);
+        self.get_vertex(from)?;
+        self.get_vertex(to)?;
+
+        let deadline = budget.time_limit.map(|limit| Instant::now() + limit);
+        let settled = Cell::new(0);
+        let cancelled = Cell::new(false);
+        let is_end = |vertex: &Vertex<T>, _| {
+            if vertex.id == to {
+                return true;
+            }
+            let count = settled.get() + 1;
+            settled.set(count);
+            let out_of_budget = budget
+                .cancel
+                .is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+                || budget.max_settled.is_some_and(|max| count >= max)
+                || deadline.is_some_and(|deadline| {
+                    count.is_multiple_of(CLOCK_INTERVAL) && Instant::now() >= deadline
+                });
+            cancelled.set(out_of_budget);
+            out_of_budget
+        };
+        let (dist, prev, end) =
+            self.search_until(&[(from, W::ZERO)], is_end, |_| W::ZERO, |_, _| true);
+        if cancelled.get() {
+            return Err(GraphError::Cancelled);
+        }
+        if end != Some(to) {
+            return Err(GraphError::NoPathFound { from, to });
+        }
+
+        Ok(self.to_path(self.reconstruct_path(&prev, from, to), dist[to]))
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::{Graph, GraphError, Weight};
+
+/// Collects vertices by value and edges between them, and builds the
+/// [`Graph`] in one go, so that no ids need to be tracked by hand.
+///
+/// Setters return the builder again, so that it reads as a list:
+/// `GraphBuilder::new().edge("A", "B", 1.0).edge("B", "C", 2.0).build()`.
+/// The [`graph!`](crate::graph!) macro offers the same with less syntax.
+#[derive(Debug, Clone)]
+pub struct GraphBuilder<T, W = f64> {
+    /// Vertex values in the order they were first mentioned, which becomes
+    /// their id.
+    values: Vec<T>,
+    ids: HashMap<T, usize>,
+    /// Edges as `(from, to, cost, undirected)`, by vertex id.
+    edges: Vec<(usize, usize, W, bool)>,
+}
+
+impl<T, W> Default for GraphBuilder<T, W>
+where
+    T: Hash + Eq + Clone,
+    W: Weight,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, W> GraphBuilder<T, W>
+where
+    T: Hash + Eq + Clone,
+    W: Weight,
+{
+    /// Creates a builder without vertices or edges.
+    pub fn new() -> Self {
+        GraphBuilder {
+            values: Vec::new(),
+            ids: HashMap::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    /// Adds a vertex holding `value` unless one already does, e.g. to keep a
+    /// vertex without edges.
+    pub fn vertex(&mut self, value: T) -> &mut Self {
+        self.id(value);
+        self
+    }
+
+    /// Adds a directed edge from the vertex holding `from` to the one holding
+    /// `to`, creating either vertex if needed.
+    pub fn edge(&mut self, from: T, to: T, cost: W) -> &mut Self {
+        let (from, to) = (self.id(from), self.id(to));
+        self.edges.push((from, to, cost, false));
+        self
+    }
+
+    /// Like [`GraphBuilder::edge`], but for an edge that can be traversed in
+    /// both directions.
+    pub fn undirected_edge(&mut self, a: T, b: T, cost: W) -> &mut Self {
+        let (a, b) = (self.id(a), self.id(b));
+        self.edges.push((a, b, cost, true));
+        self
+    }
+
+    /// Builds the graph, with vertex ids in the order their values were first
+    /// mentioned and [values indexed](Graph::index_values), so that
+    /// [`Graph::find_vertex`] looks them up in constant time.
+    ///
+    /// Fails if an edge is rejected, e.g. because its cost is NaN.
+    pub fn build(&self) -> Result<Graph<T, W>, GraphError> {
+        span!(
+            INFO,
+            "GraphBuilder::build",
+            vertices = self.values.len(),
+            edges = self.edges.len()
+        );
+        let mut graph = Graph::new();
+        for value in &self.values {
+            graph.add_vertex(value.clone());
+        }
+        for (from, to, cost, undirected) in &self.edges {
+            if *undirected {
+                graph.add_edge_undirected(*from, *to, *cost)?;
+            } else {
+                graph.add_edge(*from, *to, *cost)?;
+            }
+        }
+        graph.index_values();
+        Ok(graph)
+    }
+
+    /// Returns the id the vertex holding `value` will get, adding it if it is
+    /// new.
+    fn id(&mut self, value: T) -> usize {
+        if let Some(id) = self.ids.get(&value) {
+            return *id;
+        }
+        let id = self.values.len();
+        self.ids.insert(value.clone(), id);
+        self.values.push(value);
+        id
+    }
+}
+
+/// Builds a [`Graph`] from a list of edges between vertex values, through a
+/// [`GraphBuilder`], and evaluates to the result of
+/// [`GraphBuilder::build`].
+///
+/// `a - cost -> b` adds a directed edge and `a - cost - b` an undirected
+/// one, e.g. `graph! { "A" - 1.0 -> "B", "B" - 2.0 - "C" }`. Values and
+/// costs must each be a single token, such as a literal or a variable, so
+/// wrap anything longer, like a negative cost, in parentheses.
+#[macro_export]
+macro_rules! graph {
+    (@edges $builder:ident;) => {};
+    (@edges $builder:ident; $from:tt - $cost:tt -> $to:tt $(, $($rest:tt)*)?) => {
+        $builder.edge($from, $to, $cost);
+        $crate::graph!(@edges $builder; $($($rest)*)?);
+    };
+    (@edges $builder:ident; $a:tt - $cost:tt - $b:tt $(, $($rest:tt)*)?) => {
+        $builder.undirected_edge($a, $b, $cost);
+        $crate::graph!(@edges $builder; $($($rest)*)?);
+    };
+    (@edges $builder:ident; $($unexpected:tt)*) => {
+        compile_error!("expected `a - cost -> b` or `a - cost - b`, with single-token values and costs")
+    };
+    ($($edges:tt)*) => {{
+        let mut builder = $crate::GraphBuilder::new();
+        $crate::graph!(@edges builder; $($edges)*);
+        builder.build()
+    }};
+}
@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+
+use crate::{Graph, GraphError, Path, ShortestPathTree};
+
+/// Shortest path trees of recently queried sources, so that repeated
+/// queries from the same source take time proportional to the path instead
+/// of a full search.
+///
+/// A cache serves one graph at a time: it empties itself whenever it is
+/// queried with a graph that has changed since, or with a different graph.
+/// When full, the tree used least recently makes room for the next one.
+#[derive(Debug, Clone)]
+pub struct PathCache {
+    capacity: usize,
+    /// Revision of the graph the trees were grown in.
+    revision: Option<u64>,
+    /// Trees by source, with the query count at their last use.
+    trees: HashMap<usize, (ShortestPathTree, u64)>,
+    queries: u64,
+}
+
+impl PathCache {
+    /// Creates an empty cache holding up to `capacity` trees, but always at
+    /// least the one last used.
+    pub fn new(capacity: usize) -> Self {
+        PathCache {
+            capacity,
+            revision: None,
+            trees: HashMap::new(),
+            queries: 0,
+        }
+    }
+
+    /// Returns the number of cached trees.
+    pub fn len(&self) -> usize {
+        self.trees.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.trees.is_empty()
+    }
+
+    /// Drops every cached tree.
+    pub fn clear(&mut self) {
+        self.trees.clear();
+    }
+
+    /// Returns the shortest path tree grown from `source` in `graph`, from the
+    /// cache if possible.
+    ///
+    /// Fails if `source` is unknown.
+    pub fn tree<T: Clone>(
+        &mut self,
+        graph: &Graph<T>,
+        source: usize,
+    ) -> Result<&ShortestPathTree, GraphError> {
+        if self.revision != Some(graph.revision()) {
+            self.trees.clear();
+            self.revision = Some(graph.revision());
+        }
+        self.queries += 1;
+
+        if !self.trees.contains_key(&source) {
+            let tree = graph.shortest_paths_from(source)?;
+            if self.trees.len() >= self.capacity {
+                let stale = self
+                    .trees
+                    .iter()
+                    .min_by_key(|(_, (_, used))| *used)
+                    .map(|(source, _)| *source);
+                if let Some(stale) = stale {
+                    self.trees.remove(&stale);
+                }
+            }
+            self.trees.insert(source, (tree, 0));
+        }
+
+        let (tree, used) = self.trees.get_mut(&source).unwrap();
+        *used = self.queries;
+        Ok(tree)
+    }
+
+    /// Returns the cheapest path from `from` to `to` in `graph`, as
+    /// [`Graph::get_shortest_path`] would, using the cached tree grown from
+    /// `from` if there is one.
+    ///
+    /// Fails if either vertex is unknown or `to` is not reachable from `from`.
+    pub fn get_shortest_path<T: Clone>(
+        &mut self,
+        graph: &Graph<T>,
+        from: usize,
+        to: usize,
+    ) -> Result<Path<T>, GraphError> {
+        graph.get_vertex(to)?;
+        let tree = self.tree(graph, from)?;
+        let Some(ids) = tree.iter_path_to(to) else {
+            return Err(GraphError::NoPathFound { from, to });
+        };
+        let cost = tree.dist[to];
+
+        let mut path: Vec<_> = ids.map(|id| graph.vertex(id).clone()).collect();
+        path.reverse();
+        Ok(graph.to_path(path, cost))
+    }
+}
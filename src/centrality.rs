@@ -0,0 +1,230 @@
+use std::collections::VecDeque;
+
+use crate::Graph;
+
+impl<T> Graph<T>
+where
+    T: Clone,
+{
+    /// Returns the betweenness centrality of every vertex, indexed by id, as
+    /// computed by Brandes' algorithm: the sum over all ordered pairs of
+    /// other vertices of the fraction of shortest paths between them that
+    /// pass through the vertex. Removed ids score zero.
+    ///
+    /// Pairs are ordered, so on an undirected graph every pair counts twice;
+    /// halve the scores for the usual undirected definition. Shortest paths
+    /// are counted per edge, so parallel edges of equal cost count as
+    /// different paths. Pairs joined by infinitely many shortest paths,
+    /// through cycles of zero cost, are left out.
+    pub fn betweenness_centrality(&self) -> Vec<f64> {
+        let slots = self.vertices.len();
+        let mut centrality = vec![0.0; slots];
+        let mut paths = vec![0.0; slots];
+        let mut dependency = vec![0.0; slots];
+        for source in self.vertices.iter().flatten() {
+            let (dist, _) = self.search(source.id, None, |_| 0.0);
+            let order = self.shortest_path_order(&dist);
+
+            paths.iter_mut().for_each(|count| *count = 0.0);
+            paths[source.id] = 1.0;
+            for current in &order {
+                for next in self.tight_edges(&dist, *current) {
+                    paths[next] += paths[*current];
+                }
+            }
+
+            dependency.iter_mut().for_each(|share| *share = 0.0);
+            for current in order.iter().rev() {
+                for next in self.tight_edges(&dist, *current) {
+                    dependency[*current] +=
+                        paths[*current] / paths[next] * (1.0 + dependency[next]);
+                }
+                if *current != source.id {
+                    centrality[*current] += dependency[*current];
+                }
+            }
+        }
+        centrality
+    }
+
+    /// Targets of the edges out of `id` that lie on a shortest path, i.e.
+    /// whose cost, with the entry cost of its target, is exactly the
+    /// difference between the distances at either end, once per such edge.
+    fn tight_edges<'a>(&'a self, dist: &'a [f64], id: usize) -> impl Iterator<Item = usize> + 'a {
+        self.outgoing(id)
+            .filter(move |edge| {
+                edge.to != id
+                    && edge.cost != f64::INFINITY
+                    && dist[id] + edge.cost + self.entry_cost(edge.to) == dist[edge.to]
+            })
+            .map(|edge| edge.to)
+    }
+
+    /// Orders the vertices with a finite distance in `dist` so that every
+    /// shortest path visits them in order, leaving out those on or behind a
+    /// cycle of zero cost.
+    fn shortest_path_order(&self, dist: &[f64]) -> Vec<usize> {
+        let mut in_degree = vec![0; self.vertices.len()];
+        for id in (0..dist.len()).filter(|id| dist[*id] != f64::INFINITY) {
+            for next in self.tight_edges(dist, id) {
+                in_degree[next] += 1;
+            }
+        }
+        let mut ready: VecDeque<usize> = (0..dist.len())
+            .filter(|id| dist[*id] != f64::INFINITY && in_degree[*id] == 0)
+            .collect();
+        let mut order = Vec::new();
+        while let Some(current) = ready.pop_front() {
+            order.push(current);
+            for next in self.tight_edges(dist, current) {
+                in_degree[next] -= 1;
+                if in_degree[next] == 0 {
+                    ready.push_back(next);
+                }
+            }
+        }
+        order
+    }
+
+    /// Returns the closeness centrality of every vertex, indexed by id: the
+    /// number of vertices it reaches divided by the total cost of the
+    /// shortest paths to them, scaled by the fraction of other vertices it
+    /// reaches so that scores stay comparable on disconnected graphs.
+    /// Vertices that reach nothing, or only at no cost, and removed ids score
+    /// zero.
+    ///
+    /// Runs one search per vertex; see
+    /// [`Graph::closeness_centrality_sampled`] for large graphs.
+    pub fn closeness_centrality(&self) -> Vec<f64> {
+        let others = self.vertices.iter().flatten().count().saturating_sub(1);
+        let mut closeness = vec![0.0; self.vertices.len()];
+        for vertex in self.vertices.iter().flatten() {
+            let (dist, _) = self.search(vertex.id, None, |_| 0.0);
+            let reached = dist.iter().filter(|distance| **distance != f64::INFINITY);
+            let (count, total) = reached.fold((0, 0.0), |(count, total), distance| {
+                (count + 1, total + distance)
+            });
+            closeness[vertex.id] = scaled_closeness(count - 1, total, others);
+        }
+        closeness
+    }
+
+    /// Like [`Graph::closeness_centrality`], but estimates the scores from the
+    /// distances to only `samples` vertices spread evenly over the ids, with
+    /// one backward search each.
+    pub fn closeness_centrality_sampled(&self, samples: usize) -> Vec<f64> {
+        let targets = self.sample_ids(samples);
+        let mut counts = vec![0; self.vertices.len()];
+        let mut totals = vec![0.0; self.vertices.len()];
+        for target in &targets {
+            let dist = self.distances_to(*target);
+            for vertex in self.vertices.iter().flatten() {
+                if vertex.id != *target && dist[vertex.id] != f64::INFINITY {
+                    counts[vertex.id] += 1;
+                    totals[vertex.id] += dist[vertex.id];
+                }
+            }
+        }
+
+        let mut closeness = vec![0.0; self.vertices.len()];
+        for vertex in self.vertices.iter().flatten() {
+            let sampled = targets.len() - usize::from(targets.contains(&vertex.id));
+            closeness[vertex.id] = scaled_closeness(counts[vertex.id], totals[vertex.id], sampled);
+        }
+        closeness
+    }
+
+    /// Returns the eccentricity of every vertex, indexed by id: the cost of
+    /// the most expensive of the shortest paths from it, or infinity if it
+    /// cannot reach every vertex. Removed ids hold infinity too.
+    pub fn eccentricities(&self) -> Vec<f64> {
+        let mut eccentricity = vec![f64::INFINITY; self.vertices.len()];
+        for vertex in self.vertices.iter().flatten() {
+            eccentricity[vertex.id] = self.eccentricity(vertex.id);
+        }
+        eccentricity
+    }
+
+    /// Returns the largest eccentricity, i.e. the cost of the most expensive
+    /// shortest path, or infinity if some vertex cannot reach another. An
+    /// empty graph has a diameter of zero.
+    pub fn diameter(&self) -> f64 {
+        self.vertices
+            .iter()
+            .flatten()
+            .map(|vertex| self.eccentricity(vertex.id))
+            .fold(0.0, f64::max)
+    }
+
+    /// Returns the smallest eccentricity, i.e. how far the most central
+    /// vertex is from the farthest vertex, or infinity if no vertex reaches
+    /// every other. An empty graph has a radius of zero.
+    pub fn radius(&self) -> f64 {
+        let radius = self
+            .vertices
+            .iter()
+            .flatten()
+            .map(|vertex| self.eccentricity(vertex.id))
+            .fold(f64::INFINITY, f64::min);
+        if radius == f64::INFINITY && self.vertices.iter().flatten().next().is_none() {
+            return 0.0;
+        }
+        radius
+    }
+
+    /// Like [`Graph::diameter`], but only searches forwards and backwards from
+    /// `samples` vertices spread evenly over the ids, which gives a lower
+    /// bound that is often exact in practice. Still returns infinity whenever
+    /// a sampled vertex cannot reach or be reached from another.
+    pub fn estimate_diameter(&self, samples: usize) -> f64 {
+        self.sample_ids(samples)
+            .into_iter()
+            .flat_map(|id| {
+                let (forward, _) = self.search(id, None, |_| 0.0);
+                let backward = self.distances_to(id);
+                [forward, backward]
+            })
+            .map(|dist| self.farthest(&dist))
+            .fold(0.0, f64::max)
+    }
+
+    /// Returns the eccentricity of the vertex `id`, known to be live.
+    fn eccentricity(&self, id: usize) -> f64 {
+        let (dist, _) = self.search(id, None, |_| 0.0);
+        self.farthest(&dist)
+    }
+
+    /// Returns the largest distance in `dist` to a live vertex.
+    fn farthest(&self, dist: &[f64]) -> f64 {
+        self.vertices
+            .iter()
+            .flatten()
+            .map(|vertex| dist[vertex.id])
+            .fold(0.0, f64::max)
+    }
+
+    /// Picks up to `samples` live ids spread evenly over all live ids.
+    fn sample_ids(&self, samples: usize) -> Vec<usize> {
+        let live: Vec<usize> = self
+            .vertices
+            .iter()
+            .flatten()
+            .map(|vertex| vertex.id)
+            .collect();
+        if samples >= live.len() {
+            return live;
+        }
+        (0..samples)
+            .map(|index| live[index * live.len() / samples])
+            .collect()
+    }
+}
+
+/// Closeness of a vertex that reaches `reached` of `others` other vertices
+/// at a total cost of `total`.
+fn scaled_closeness(reached: usize, total: f64, others: usize) -> f64 {
+    if reached == 0 || total == 0.0 {
+        return 0.0;
+    }
+    reached as f64 / total * (reached as f64 / others as f64)
+}
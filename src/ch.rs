@@ -0,0 +1,407 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::ops::ControlFlow;
+
+use crate::min_non_nan::MinNonNan;
+use crate::progress::Progress;
+use crate::{Graph, GraphError, Path, Vertex};
+
+/// Most vertices a witness search settles before giving up and keeping the
+/// shortcut it was looking for a witness against.
+const WITNESS_SETTLE_LIMIT: usize = 50;
+
+/// A graph preprocessed into a contraction hierarchy: every vertex has a
+/// rank, and shortcut edges let queries search only towards higher ranks.
+///
+/// Built with [`Graph::contract`]. Later changes to the source graph are not
+/// reflected.
+pub struct ContractedGraph<T> {
+    vertices: Vec<Option<Vertex<T>>>,
+    /// Edges to higher-ranked vertices, for the forward search.
+    upward: Vec<Vec<(usize, f64)>>,
+    /// Edges from higher-ranked vertices, reversed, for the backward search.
+    downward: Vec<Vec<(usize, f64)>>,
+    /// Vertex each shortcut bypasses, keyed by its endpoints.
+    middles: HashMap<(usize, usize), usize>,
+}
+
+/// Preprocessing state: the remaining graph with its shortcuts added, and
+/// the hierarchy built so far.
+struct Contraction {
+    outgoing: Vec<Vec<(usize, f64)>>,
+    incoming: Vec<Vec<(usize, f64)>>,
+    contracted_neighbours: Vec<usize>,
+    /// Depth in the hierarchy, one more than the deepest contracted neighbour.
+    levels: Vec<usize>,
+    middles: HashMap<(usize, usize), usize>,
+    upward: Vec<Vec<(usize, f64)>>,
+    downward: Vec<Vec<(usize, f64)>>,
+    /// Witness search distances, reset after each search through `touched`.
+    distances: Vec<f64>,
+    touched: Vec<usize>,
+}
+
+impl Contraction {
+    fn new(slots: usize) -> Self {
+        Contraction {
+            outgoing: vec![Vec::new(); slots],
+            incoming: vec![Vec::new(); slots],
+            contracted_neighbours: vec![0; slots],
+            levels: vec![0; slots],
+            middles: HashMap::new(),
+            upward: vec![Vec::new(); slots],
+            downward: vec![Vec::new(); slots],
+            distances: vec![f64::INFINITY; slots],
+            touched: Vec::new(),
+        }
+    }
+
+    /// Adds an edge, or lowers the cost of an existing one between the same
+    /// vertices. Returns whether anything changed.
+    fn insert(&mut self, from: usize, to: usize, cost: f64) -> bool {
+        match self.outgoing[from]
+            .iter()
+            .position(|(target, _)| *target == to)
+        {
+            Some(index) if self.outgoing[from][index].1 <= cost => return false,
+            Some(index) => {
+                self.outgoing[from][index].1 = cost;
+                let back = self.incoming[to]
+                    .iter_mut()
+                    .find(|(source, _)| *source == from);
+                back.unwrap().1 = cost;
+            }
+            None => {
+                self.outgoing[from].push((to, cost));
+                self.incoming[to].push((from, cost));
+            }
+        }
+        true
+    }
+
+    /// Shortcuts needed to contract `vertex`, as `(from, to, cost)`.
+    fn shortcuts(&mut self, vertex: usize) -> Vec<(usize, usize, f64)> {
+        let mut shortcuts = Vec::new();
+        let sources = self.incoming[vertex].clone();
+        for (from, cost_in) in sources {
+            let targets: Vec<(usize, f64)> = self.outgoing[vertex]
+                .iter()
+                .filter(|(to, _)| *to != from)
+                .map(|(to, cost_out)| (*to, cost_in + cost_out))
+                .collect();
+            if targets.is_empty() {
+                continue;
+            }
+
+            self.witness_search(from, vertex, &targets);
+            for (to, cost) in targets {
+                if self.distances[to] > cost {
+                    shortcuts.push((from, to, cost));
+                }
+            }
+            for id in self.touched.drain(..) {
+                self.distances[id] = f64::INFINITY;
+            }
+        }
+        shortcuts
+    }
+
+    /// Fills `distances` from `source` in the remaining graph without
+    /// `skipped`, until every target is settled, the costs of the paths
+    /// through `skipped` are exceeded or too many vertices are settled.
+    fn witness_search(&mut self, source: usize, skipped: usize, targets: &[(usize, f64)]) {
+        let limit = targets.iter().map(|(_, cost)| *cost).fold(0.0, f64::max);
+        let mut unsettled = targets.len();
+        self.distances[source] = 0.0;
+        self.touched.push(source);
+        let mut queue = BinaryHeap::from([(MinNonNan(0.0), source)]);
+        let mut settled = 0;
+
+        while let Some((MinNonNan(cost), current)) = queue.pop() {
+            if cost > self.distances[current] {
+                continue;
+            }
+            settled += 1;
+            if cost > limit || settled > WITNESS_SETTLE_LIMIT {
+                break;
+            }
+            if targets.iter().any(|(target, _)| *target == current) {
+                unsettled -= 1;
+                if unsettled == 0 {
+                    break;
+                }
+            }
+
+            for &(to, edge_cost) in &self.outgoing[current] {
+                let new_dist = cost + edge_cost;
+                if to != skipped && new_dist < self.distances[to] {
+                    if self.distances[to] == f64::INFINITY {
+                        self.touched.push(to);
+                    }
+                    self.distances[to] = new_dist;
+                    queue.push((MinNonNan(new_dist), to));
+                }
+            }
+        }
+    }
+
+    /// Importance of contracting `vertex` next; lower goes first.
+    fn priority(&mut self, vertex: usize) -> i64 {
+        let degree = self.outgoing[vertex].len() + self.incoming[vertex].len();
+        let edge_difference = self.shortcuts(vertex).len() as i64 - degree as i64;
+        2 * edge_difference + self.contracted_neighbours[vertex] as i64 + self.levels[vertex] as i64
+    }
+
+    /// Contracts `vertex`, moving its edges into the hierarchy, and returns
+    /// its remaining neighbours.
+    fn contract(&mut self, vertex: usize) -> Vec<usize> {
+        for (from, to, cost) in self.shortcuts(vertex) {
+            if self.insert(from, to, cost) {
+                self.middles.insert((from, to), vertex);
+            }
+        }
+
+        let outgoing = std::mem::take(&mut self.outgoing[vertex]);
+        let incoming = std::mem::take(&mut self.incoming[vertex]);
+        self.upward[vertex].clone_from(&outgoing);
+        self.downward[vertex].clone_from(&incoming);
+        for &(to, _) in &outgoing {
+            self.incoming[to].retain(|(source, _)| *source != vertex);
+        }
+        for &(from, _) in &incoming {
+            self.outgoing[from].retain(|(target, _)| *target != vertex);
+        }
+
+        let mut neighbours: Vec<usize> = outgoing
+            .iter()
+            .chain(&incoming)
+            .map(|(neighbour, _)| *neighbour)
+            .collect();
+        neighbours.sort_unstable();
+        neighbours.dedup();
+        for neighbour in &neighbours {
+            self.contracted_neighbours[*neighbour] += 1;
+            self.levels[*neighbour] = self.levels[*neighbour].max(self.levels[vertex] + 1);
+        }
+        neighbours
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Clone,
+{
+    /// Preprocesses the graph into a [`ContractedGraph`] for fast repeated
+    /// point-to-point queries.
+    ///
+    /// Fails if an edge has a negative cost.
+    pub fn contract(&self) -> Result<ContractedGraph<T>, GraphError> {
+        self.contract_with_progress(|_| ControlFlow::Continue(()))
+    }
+
+    /// Like [`Graph::contract`], but calls `progress` with the percentage of
+    /// vertices contracted so far, and stops when it returns
+    /// [`ControlFlow::Break`].
+    ///
+    /// Fails with [`GraphError::Cancelled`] if stopped.
+    pub fn contract_with_progress<P>(&self, progress: P) -> Result<ContractedGraph<T>, GraphError>
+    where
+        P: FnMut(f64) -> ControlFlow<()>,
+    {
+        span!(INFO, "Graph::contract", vertices = self.vertex_count());
+        let mut progress = Progress::start(progress)?;
+        let slots = self.vertices.len();
+        let mut contraction = Contraction::new(slots);
+        for vertex in self.vertices.iter().flatten() {
+            for edge in self.outgoing(vertex.id) {
+                if edge.cost < 0.0 {
+                    return Err(GraphError::InvalidCost(edge.cost));
+                }
+                if edge.to != vertex.id && edge.cost.is_finite() {
+                    contraction.insert(vertex.id, edge.to, edge.cost);
+                }
+            }
+        }
+
+        // Priority each vertex is queued under, so that stale heap entries
+        // can be told apart from live ones.
+        let mut queued: Vec<Option<i64>> = vec![None; slots];
+        let mut queue = BinaryHeap::new();
+        let (mut contracted, total) = (0, self.vertex_count());
+        for vertex in self.vertices.iter().flatten() {
+            let priority = contraction.priority(vertex.id);
+            queued[vertex.id] = Some(priority);
+            queue.push((Reverse(priority), vertex.id));
+        }
+
+        while let Some((Reverse(priority), vertex)) = queue.pop() {
+            if queued[vertex] != Some(priority) {
+                continue;
+            }
+            // Priorities go stale as the graph shrinks, so recompute lazily
+            // and contract only if the vertex is still the least important.
+            let priority = contraction.priority(vertex);
+            if queue
+                .peek()
+                .is_some_and(|(Reverse(next), _)| priority > *next)
+            {
+                queued[vertex] = Some(priority);
+                queue.push((Reverse(priority), vertex));
+                continue;
+            }
+
+            queued[vertex] = None;
+            contracted += 1;
+            progress.steps(contracted, total)?;
+            for neighbour in contraction.contract(vertex) {
+                let priority = contraction.priority(neighbour);
+                queued[neighbour] = Some(priority);
+                queue.push((Reverse(priority), neighbour));
+            }
+        }
+
+        event!(
+            INFO,
+            shortcuts = contraction.middles.len(),
+            "contracted graph"
+        );
+        progress.finish();
+        Ok(ContractedGraph {
+            vertices: self.vertices.clone(),
+            upward: contraction.upward,
+            downward: contraction.downward,
+            middles: contraction.middles,
+        })
+    }
+}
+
+impl<T> ContractedGraph<T>
+where
+    T: Clone,
+{
+    /// Number of shortcut edges added during preprocessing.
+    pub fn shortcut_count(&self) -> usize {
+        self.middles.len()
+    }
+
+    /// Returns the cheapest path from `from` to `to`, as
+    /// [`Graph::get_shortest_path`] would.
+    ///
+    /// Fails if either vertex is unknown or `to` is not reachable from `from`.
+    pub fn get_shortest_path(&self, from: usize, to: usize) -> Result<Path<T>, GraphError> {
+        span!(DEBUG, "ContractedGraph::get_shortest_path", from, to);
+        for id in [to, from] {
+            self.vertices
+                .get(id)
+                .and_then(Option::as_ref)
+                .ok_or(GraphError::UnknownVertex(id))?;
+        }
+
+        // Distance and predecessor of each vertex reached by the forward and
+        // backward searches, which stay small enough for maps to beat
+        // allocating per vertex.
+        let mut labels: [HashMap<usize, (f64, Option<usize>)>; 2] = [
+            HashMap::from([(from, (0.0, None))]),
+            HashMap::from([(to, (0.0, None))]),
+        ];
+        let dist = |labels: &HashMap<usize, (f64, Option<usize>)>, id| {
+            labels.get(&id).map_or(f64::INFINITY, |(dist, _)| *dist)
+        };
+        let mut queues = [BinaryHeap::new(), BinaryHeap::new()];
+        queues[0].push((MinNonNan(0.0), from));
+        queues[1].push((MinNonNan(0.0), to));
+
+        let mut best = f64::INFINITY;
+        let mut meeting = None;
+        for side in [0, 1].into_iter().cycle() {
+            let done = |queue: &BinaryHeap<(MinNonNan, usize)>| {
+                queue
+                    .peek()
+                    .is_none_or(|(MinNonNan(cost), _)| *cost >= best)
+            };
+            if done(&queues[0]) && done(&queues[1]) {
+                break;
+            }
+            let Some((MinNonNan(cost), current)) = queues[side].pop() else {
+                continue;
+            };
+            if cost > dist(&labels[side], current) || cost >= best {
+                continue;
+            }
+
+            let total = cost + dist(&labels[1 - side], current);
+            if total < best {
+                best = total;
+                meeting = Some(current);
+            }
+
+            let edges = if side == 0 {
+                &self.upward
+            } else {
+                &self.downward
+            };
+            for &(next, edge_cost) in &edges[current] {
+                let new_dist = cost + edge_cost;
+                if new_dist < dist(&labels[side], next) {
+                    labels[side].insert(next, (new_dist, Some(current)));
+                    queues[side].push((MinNonNan(new_dist), next));
+                }
+            }
+        }
+
+        event!(
+            DEBUG,
+            cost = best,
+            reached = labels[0].len() + labels[1].len(),
+            "hierarchy search finished"
+        );
+        let Some(meeting) = meeting else {
+            return Err(GraphError::NoPathFound { from, to });
+        };
+
+        let mut hops = vec![meeting];
+        while let Some(before) = labels[0][hops.last().unwrap()].1 {
+            hops.push(before);
+        }
+        hops.reverse();
+        while let Some(after) = labels[1][hops.last().unwrap()].1 {
+            hops.push(after);
+        }
+
+        let mut path = vec![self.vertices[from].clone().unwrap()];
+        let mut costs = Vec::new();
+        for pair in hops.windows(2) {
+            self.unpack(pair[0], pair[1], &mut path, &mut costs);
+        }
+        Ok(Path::new(path, costs, best))
+    }
+
+    /// Appends the original vertices after `from` up to `to`, expanding
+    /// shortcuts recursively, and the cost of each step into them.
+    fn unpack(&self, from: usize, to: usize, path: &mut Vec<Vertex<T>>, costs: &mut Vec<f64>) {
+        match self.middles.get(&(from, to)) {
+            Some(&middle) => {
+                self.unpack(from, middle, path, costs);
+                self.unpack(middle, to, path, costs);
+            }
+            None => {
+                path.push(self.vertices[to].clone().unwrap());
+                costs.push(self.hierarchy_cost(from, to));
+            }
+        }
+    }
+
+    /// Cost of the hierarchy edge from `from` to `to`, which is stored with
+    /// whichever of the two is ranked lower.
+    fn hierarchy_cost(&self, from: usize, to: usize) -> f64 {
+        let upward = self.upward[from]
+            .iter()
+            .filter(|(next, _)| *next == to)
+            .map(|(_, cost)| *cost);
+        let downward = self.downward[to]
+            .iter()
+            .filter(|(previous, _)| *previous == from)
+            .map(|(_, cost)| *cost);
+        upward.chain(downward).fold(f64::INFINITY, f64::min)
+    }
+}
@@ -0,0 +1,160 @@
+use std::cell::Cell;
+
+use crate::{Graph, GraphError, Path, Vertex, Weight};
+
+impl<T, W> Graph<T, W>
+where
+    T: Clone,
+    W: Weight,
+{
+    /// Returns every cheapest path from `from` to `to`, up to `limit` of
+    /// them, where [`Graph::get_shortest_path`] returns only one. Paths whose
+    /// costs are summed in a different order and differ by rounding are not
+    /// considered tied.
+    ///
+    /// Parallel edges of equal cost yield a single path, and a path never
+    /// visits a vertex twice, even around a cycle of zero-cost edges.
+    ///
+    /// Fails if either vertex is unknown or `to` is not reachable from `from`.
+    pub fn get_all_shortest_paths(
+        &self,
+        from: usize,
+        to: usize,
+        limit: usize,
+    ) -> Result<Vec<Path<T, W>>, GraphError> {
+        let (dist, preds) = self.optimal_predecessors(from, to)?;
+
+        // Depth-first walk back from `to`, with each frame holding a vertex
+        // and how many of its predecessors have been tried.
+        let mut paths = Vec::new();
+        let mut stack = vec![(to, 0)];
+        let mut on_path = vec![false; self.vertices.len()];
+        on_path[to] = true;
+        while let Some((current, tried)) = stack.last_mut() {
+            if paths.len() == limit {
+                break;
+            }
+            if *current == from {
+                let path: Vec<Vertex<T>> = stack
+                    .iter()
+                    .rev()
+                    .map(|(id, _)| self.vertex(*id).clone())
+                    .collect();
+                paths.push(self.to_path(path, dist[to]));
+                stack.pop();
+                on_path[from] = false;
+                continue;
+            }
+            match preds[*current].get(*tried).copied() {
+                Some(pred) => {
+                    *tried += 1;
+                    if !on_path[pred] {
+                        on_path[pred] = true;
+                        stack.push((pred, 0));
+                    }
+                }
+                None => {
+                    on_path[*current] = false;
+                    stack.pop();
+                }
+            }
+        }
+        Ok(paths)
+    }
+
+    /// Returns how many distinct cheapest paths lead from `from` to `to`,
+    /// with the same notion of ties as [`Graph::get_all_shortest_paths`] but
+    /// without enumerating them.
+    ///
+    /// The count saturates at `u64::MAX`, which is also returned when a
+    /// cycle of zero-cost edges lies on a cheapest path, as walking it any
+    /// number of times costs nothing.
+    ///
+    /// Fails if either vertex is unknown or `to` is not reachable from `from`.
+    pub fn count_shortest_paths(&self, from: usize, to: usize) -> Result<u64, GraphError> {
+        let (_, preds) = self.optimal_predecessors(from, to)?;
+
+        // Only vertices on some cheapest path to `to` matter, and all their
+        // predecessors are on one too.
+        let mut on_some_path = vec![false; self.vertices.len()];
+        on_some_path[to] = true;
+        let mut stack = vec![to];
+        let mut succs = vec![Vec::new(); self.vertices.len()];
+        while let Some(current) = stack.pop() {
+            for &pred in &preds[current] {
+                succs[pred].push(current);
+                if !on_some_path[pred] {
+                    on_some_path[pred] = true;
+                    stack.push(pred);
+                }
+            }
+        }
+
+        // Counts flow forward in topological order; a vertex left waiting on
+        // a predecessor is on a zero-cost cycle.
+        let mut waiting: Vec<usize> = preds.iter().map(Vec::len).collect();
+        let mut ways = vec![0u64; self.vertices.len()];
+        ways[from] = 1;
+        let mut ready = if waiting[from] == 0 {
+            vec![from]
+        } else {
+            Vec::new()
+        };
+        while let Some(current) = ready.pop() {
+            for &succ in &succs[current] {
+                ways[succ] = ways[succ].saturating_add(ways[current]);
+                waiting[succ] -= 1;
+                if waiting[succ] == 0 {
+                    ready.push(succ);
+                }
+            }
+        }
+        if waiting[to] > 0 {
+            return Ok(u64::MAX);
+        }
+        Ok(ways[to])
+    }
+
+    /// Searches from `from` until every vertex as close as `to` is settled
+    /// and returns the distances with, for each vertex, the distinct
+    /// vertices it is entered from on a cheapest path.
+    fn optimal_predecessors(
+        &self,
+        from: usize,
+        to: usize,
+    ) -> Result<(Vec<W>, Vec<Vec<usize>>), GraphError> {
+        self.get_vertex(from)?;
+        self.get_vertex(to)?;
+
+        let bound = Cell::new(W::INFINITY);
+        let is_past_target = |vertex: &Vertex<T>, distance: W| {
+            if vertex.id == to {
+                bound.set(distance);
+            }
+            distance > bound.get()
+        };
+        let (dist, _, _) =
+            self.search_until(&[(from, W::ZERO)], is_past_target, |_| W::ZERO, |_, _| true);
+        if dist[to] == W::INFINITY {
+            return Err(GraphError::NoPathFound { from, to });
+        }
+
+        let mut preds = vec![Vec::new(); self.vertices.len()];
+        for (id, preds) in preds.iter_mut().enumerate() {
+            if dist[id] > dist[to] {
+                continue;
+            }
+            for edge in self.incoming(id) {
+                let tight = dist[edge.to] != W::INFINITY
+                    && dist[edge.to]
+                        .saturating_add(edge.cost)
+                        .saturating_add(self.entry_cost(id))
+                        == dist[id];
+                if tight && edge.to != id && !preds.contains(&edge.to) {
+                    preds.push(edge.to);
+                }
+            }
+        }
+        Ok((dist, preds))
+    }
+}
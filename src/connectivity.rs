@@ -0,0 +1,74 @@
+use crate::{Graph, GraphError, Weight};
+
+impl<T, W> Graph<T, W>
+where
+    T: Clone,
+    W: Weight,
+{
+    /// Returns whether any path leads from `from` to `to`, whatever its cost.
+    /// Edges of infinite cost count as blocked, as they do for searches.
+    ///
+    /// Fails if either vertex is unknown.
+    pub fn is_reachable(&self, from: usize, to: usize) -> Result<bool, GraphError> {
+        self.get_vertex(to)?;
+        Ok(self.reachable(from)?[to])
+    }
+
+    /// Returns the ids of every vertex some path leads to from `id`,
+    /// including `id` itself, in ascending order.
+    ///
+    /// Fails if the vertex is unknown.
+    pub fn reachable_from(&self, id: usize) -> Result<Vec<usize>, GraphError> {
+        let reached = self.reachable(id)?;
+        Ok((0..reached.len()).filter(|id| reached[*id]).collect())
+    }
+
+    /// Splits the vertices into weakly connected components, i.e. groups
+    /// linked by edges in either direction. Each component lists its ids in
+    /// ascending order, and components are ordered by their smallest id.
+    ///
+    /// A graph with more than one component has pairs of vertices with no
+    /// path between them in either direction.
+    pub fn connected_components(&self) -> Vec<Vec<usize>> {
+        let mut component = vec![usize::MAX; self.vertices.len()];
+        let mut components: Vec<Vec<usize>> = Vec::new();
+        for vertex in self.vertices.iter().flatten() {
+            if component[vertex.id] != usize::MAX {
+                continue;
+            }
+            let index = components.len();
+            component[vertex.id] = index;
+            let mut members = Vec::new();
+            let mut stack = vec![vertex.id];
+            while let Some(current) = stack.pop() {
+                members.push(current);
+                for edge in self.outgoing(current).chain(self.incoming(current)) {
+                    if edge.cost != W::INFINITY && component[edge.to] == usize::MAX {
+                        component[edge.to] = index;
+                        stack.push(edge.to);
+                    }
+                }
+            }
+            members.sort_unstable();
+            components.push(members);
+        }
+        components
+    }
+
+    /// Marks every vertex reachable from `from`, indexed by id.
+    pub(crate) fn reachable(&self, from: usize) -> Result<Vec<bool>, GraphError> {
+        self.get_vertex(from)?;
+        let mut reached = vec![false; self.vertices.len()];
+        reached[from] = true;
+        let mut stack = vec![from];
+        while let Some(current) = stack.pop() {
+            for edge in self.outgoing(current) {
+                if edge.cost != W::INFINITY && !reached[edge.to] {
+                    reached[edge.to] = true;
+                    stack.push(edge.to);
+                }
+            }
+        }
+        Ok(reached)
+    }
+}
@@ -0,0 +1,148 @@
+use alloc::vec::Vec;
+
+use crate::indexed_heap::IndexedHeap;
+use crate::{Graph, GraphError, Path, Weight};
+
+/// Buffers for the distances, predecessors and frontier of a search, kept
+/// between queries by [`Graph::get_shortest_path_in`] so that repeated
+/// queries, e.g. every frame of a game loop, allocate nothing but the path
+/// they return.
+///
+/// The buffers grow to the largest graph searched and are not cleared
+/// between queries: each entry is stamped with the query that wrote it, and
+/// entries from earlier queries are ignored. One context can serve graphs
+/// of different sizes, but only one query at a time.
+pub struct SearchContext<W = f64> {
+    dist: Vec<W>,
+    prev: Vec<usize>,
+    /// Query in which each vertex was last reached, which makes its `dist`
+    /// and `prev` valid.
+    reached: Vec<u32>,
+    /// Query in which each vertex was last settled.
+    settled: Vec<u32>,
+    /// Stamp of the current query, never zero.
+    query: u32,
+    queue: IndexedHeap<W>,
+}
+
+impl<W: Weight> Default for SearchContext<W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<W: Weight> SearchContext<W> {
+    /// Creates a context without buffers, which grow on the first query.
+    pub fn new() -> Self {
+        SearchContext {
+            dist: Vec::new(),
+            prev: Vec::new(),
+            reached: Vec::new(),
+            settled: Vec::new(),
+            query: 0,
+            queue: IndexedHeap::new(0),
+        }
+    }
+
+    /// Creates a context with buffers for graphs of up to `vertices` vertex
+    /// ids, so that not even the first query allocates them.
+    pub fn with_capacity(vertices: usize) -> Self {
+        let mut context = Self::new();
+        context.start(vertices);
+        context
+    }
+
+    /// Starts a new query over `slots` vertex ids, growing the buffers if
+    /// needed and invalidating every entry of earlier queries.
+    fn start(&mut self, slots: usize) {
+        if self.dist.len() < slots {
+            self.dist.resize(slots, W::INFINITY);
+            self.prev.resize(slots, usize::MAX);
+            self.reached.resize(slots, 0);
+            self.settled.resize(slots, 0);
+        }
+        self.query = self.query.wrapping_add(1);
+        if self.query == 0 {
+            // Stamps have wrapped around, so old ones might match again.
+            self.reached.fill(0);
+            self.settled.fill(0);
+            self.query = 1;
+        }
+        self.queue.reset(slots);
+    }
+}
+
+impl<T, W, E> Graph<T, W, E>
+where
+    T: Clone,
+    W: Weight,
+    E: Clone,
+{
+    /// Like [`Graph::get_shortest_path`], but searches with the buffers of
+    /// `context` instead of allocating its own.
+    ///
+    /// Fails if either vertex is unknown or `to` is not reachable from `from`.
+    pub fn get_shortest_path_in(
+        &self,
+        context: &mut SearchContext<W>,
+        from: usize,
+        to: usize,
+    ) -> Result<Path<T, W, E>, GraphError> {
+        span!(DEBUG, "Graph::get_shortest_path_in", from, to);
+        self.get_vertex(from)?;
+        self.get_vertex(to)?;
+
+        context.start(self.vertices.len());
+        let SearchContext {
+            dist,
+            prev,
+            reached,
+            settled,
+            query,
+            queue,
+        } = context;
+        let query = *query;
+        dist[from] = W::ZERO;
+        prev[from] = usize::MAX;
+        reached[from] = query;
+        queue.push_or_decrease(from, W::ZERO);
+
+        while let Some((_, current)) = queue.pop() {
+            settled[current] = query;
+            if current == to {
+                break;
+            }
+            for edge in self.outgoing(current) {
+                if settled[edge.to] == query {
+                    continue;
+                }
+                let new_dist = dist[current]
+                    .saturating_add(edge.cost)
+                    .saturating_add(self.entry_cost(edge.to));
+                let known = if reached[edge.to] == query {
+                    dist[edge.to]
+                } else {
+                    W::INFINITY
+                };
+                if new_dist < known {
+                    dist[edge.to] = new_dist;
+                    prev[edge.to] = current;
+                    reached[edge.to] = query;
+                    queue.push_or_decrease(edge.to, new_dist);
+                }
+            }
+        }
+        if settled[to] != query {
+            return Err(GraphError::NoPathFound { from, to });
+        }
+
+        let mut vertices = Vec::new();
+        let mut at = to;
+        while at != usize::MAX {
+            vertices.push(self.vertex(at).clone());
+            at = prev[at];
+        }
+        vertices.reverse();
+        Ok(self.to_path(vertices, dist[to]))
+    }
+}
@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read};
+use std::str::FromStr;
+
+use crate::{Graph, GraphError};
+
+/// Positions of the recognised columns within a row.
+struct Columns {
+    from: usize,
+    to: usize,
+    cost: usize,
+    from_label: Option<usize>,
+    to_label: Option<usize>,
+}
+
+impl Columns {
+    fn positional(width: usize) -> Self {
+        let labelled = width >= 5;
+        Columns {
+            from: 0,
+            to: 1,
+            cost: 2,
+            from_label: labelled.then_some(3),
+            to_label: labelled.then_some(4),
+        }
+    }
+
+    fn from_header(header: &[String], line: usize) -> Result<Self, GraphError> {
+        let find = |names: &[&str]| {
+            header
+                .iter()
+                .position(|column| names.contains(&column.to_lowercase().as_str()))
+        };
+        let require = |names: &[&str]| {
+            find(names).ok_or_else(|| parse_error(line, &format!("missing '{}' column", names[0])))
+        };
+
+        Ok(Columns {
+            from: require(&["from", "source"])?,
+            to: require(&["to", "target"])?,
+            cost: require(&["cost", "weight"])?,
+            from_label: find(&["from_label", "source_label"]),
+            to_label: find(&["to_label", "target_label"]),
+        })
+    }
+}
+
+impl<T: FromStr> Graph<T>
+where
+    T: Clone,
+{
+    /// Reads a graph from CSV rows of `from,to,cost`, creating a vertex the
+    /// first time each `from` or `to` key appears.
+    ///
+    /// A header row is detected when its cost column is not a number; it may
+    /// name the columns `from`, `to` and `cost` in any order, plus optional
+    /// `from_label` and `to_label` columns. Without a header, a fourth and
+    /// fifth column are read as those labels. Vertex values are parsed from
+    /// the label if present and from the key otherwise. Blank lines and
+    /// lines starting with `#` are skipped.
+    pub fn from_edge_list_csv<R: Read>(reader: R) -> Result<Self, GraphError> {
+        span!(INFO, "Graph::from_edge_list_csv");
+        let mut graph = Graph::new();
+        let mut ids: HashMap<String, usize> = HashMap::new();
+        let mut columns: Option<Columns> = None;
+
+        for (index, line) in BufReader::new(reader).lines().enumerate() {
+            let number = index + 1;
+            let line = line.map_err(|error| GraphError::Io(error.to_string()))?;
+            if line.trim().is_empty() || line.trim_start().starts_with('#') {
+                continue;
+            }
+
+            let fields = split_row(&line, number)?;
+            let columns = match &columns {
+                Some(columns) => columns,
+                None => {
+                    let positional = Columns::positional(fields.len());
+                    let is_header = fields
+                        .get(positional.cost)
+                        .is_some_and(|cost| cost.parse::<f64>().is_err());
+                    if is_header {
+                        columns = Some(Columns::from_header(&fields, number)?);
+                        continue;
+                    }
+                    columns.insert(positional)
+                }
+            };
+
+            let field = |column: usize| {
+                fields
+                    .get(column)
+                    .map(String::as_str)
+                    .ok_or_else(|| parse_error(number, &format!("missing column {}", column + 1)))
+            };
+            let cost = field(columns.cost)?;
+            let cost = cost
+                .parse::<f64>()
+                .map_err(|_| parse_error(number, &format!("invalid edge cost '{}'", cost)))?;
+
+            let mut vertex = |key_column: usize, label_column: Option<usize>| {
+                let key = field(key_column)?;
+                if let Some(id) = ids.get(key) {
+                    return Ok(*id);
+                }
+
+                let label = match label_column {
+                    Some(column) => field(column)?,
+                    None => key,
+                };
+                let value = label.parse::<T>().map_err(|_| {
+                    parse_error(number, &format!("invalid vertex value '{}'", label))
+                })?;
+                let id = graph.add_vertex(value);
+                ids.insert(key.to_string(), id);
+                Ok(id)
+            };
+            let from = vertex(columns.from, columns.from_label)?;
+            let to = vertex(columns.to, columns.to_label)?;
+
+            graph
+                .add_edge(from, to, cost)
+                .map_err(|error| parse_error(number, &error.to_string()))?;
+        }
+
+        Ok(graph)
+    }
+}
+
+/// Splits a CSV row on commas, honouring double-quoted fields.
+pub(crate) fn split_row(line: &str, number: usize) -> Result<Vec<String>, GraphError> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field).trim().to_string()),
+            _ => field.push(c),
+        }
+    }
+    if quoted {
+        return Err(parse_error(number, "unterminated quoted field"));
+    }
+    fields.push(field.trim().to_string());
+
+    Ok(fields)
+}
+
+fn parse_error(line: usize, message: &str) -> GraphError {
+    GraphError::Parse {
+        line,
+        message: message.to_string(),
+    }
+}
@@ -0,0 +1,47 @@
+use crate::{Edge, Graph, GraphError, Path, Weight};
+
+impl<T, W, E> Graph<T, W, E>
+where
+    T: Clone,
+    W: Weight,
+    E: Clone,
+{
+    /// Like [`Graph::get_shortest_path`], but prices every edge with
+    /// `edge_cost` instead of its stored cost, so that one graph can be
+    /// routed by distance, time or energy, e.g. from figures kept in the
+    /// edges' [payloads](Edge::data). The path's costs are those given by
+    /// `edge_cost`, plus any [entry costs](Graph::set_entry_cost).
+    ///
+    /// Costs must not be negative. An infinite or NaN cost makes the edge
+    /// impassable.
+    ///
+    /// Fails if either vertex is unknown or `to` is not reachable from `from`.
+    pub fn get_shortest_path_by<C>(
+        &self,
+        from: usize,
+        to: usize,
+        edge_cost: C,
+    ) -> Result<Path<T, W, E>, GraphError>
+    where
+        C: Fn(&Edge<W, E>) -> W,
+    {
+        span!(DEBUG, "Graph::get_shortest_path_by", from, to);
+        self.get_vertex(from)?;
+        self.get_vertex(to)?;
+
+        let passable = |edge: &Edge<W, E>| edge_cost(edge) < W::INFINITY;
+        let (dist, prev, _) = self.search_costed(
+            &[(from, W::ZERO)],
+            |vertex, _| vertex.id == to,
+            |_| W::ZERO,
+            |_, edge| passable(edge),
+            |_, edge, _| edge_cost(edge),
+        );
+        if dist[to] == W::INFINITY {
+            return Err(GraphError::NoPathFound { from, to });
+        }
+
+        let path = self.reconstruct_path(&prev, from, to);
+        Ok(self.to_path_costed(path, dist[to], |_, edge| passable(edge), &edge_cost))
+    }
+}
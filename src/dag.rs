@@ -0,0 +1,154 @@
+use std::collections::VecDeque;
+
+use crate::graph::{CompactId, Predecessors};
+use crate::{Graph, GraphError, Path, Weight};
+
+impl<T, W> Graph<T, W>
+where
+    T: Clone,
+    W: Weight,
+{
+    /// Returns every vertex id in an order where each edge leads from an
+    /// earlier to a later vertex. Edges of infinite cost count as blocked, as
+    /// they do for searches.
+    ///
+    /// Fails with [`GraphError::Cyclic`] if the graph has a cycle, which
+    /// includes any undirected edge. [`Graph::find_cycle`] returns one.
+    pub fn topological_sort(&self) -> Result<Vec<usize>, GraphError> {
+        let mut in_degree = vec![0; self.vertices.len()];
+        for vertex in self.vertices.iter().flatten() {
+            for edge in self.outgoing(vertex.id) {
+                if edge.cost != W::INFINITY {
+                    in_degree[edge.to] += 1;
+                }
+            }
+        }
+
+        let mut ready: VecDeque<usize> = self
+            .vertices
+            .iter()
+            .flatten()
+            .map(|vertex| vertex.id)
+            .filter(|id| in_degree[*id] == 0)
+            .collect();
+        let mut order = Vec::new();
+        while let Some(current) = ready.pop_front() {
+            order.push(current);
+            for edge in self.outgoing(current) {
+                if edge.cost == W::INFINITY {
+                    continue;
+                }
+                in_degree[edge.to] -= 1;
+                if in_degree[edge.to] == 0 {
+                    ready.push_back(edge.to);
+                }
+            }
+        }
+
+        if order.len() < self.vertices.iter().flatten().count() {
+            return Err(GraphError::Cyclic);
+        }
+        Ok(order)
+    }
+
+    /// Returns the ids along some cycle, each with an edge to the next and
+    /// the last with an edge back to the first, or `None` if the graph is
+    /// acyclic. A self-loop is a cycle of one vertex and an undirected edge
+    /// one of two. Edges of infinite cost count as blocked.
+    pub fn find_cycle(&self) -> Option<Vec<usize>> {
+        // Vertices whose depth-first walk is done, and those on the current
+        // path, where reaching one again closes a cycle.
+        let mut finished = vec![false; self.vertices.len()];
+        let mut on_path = vec![false; self.vertices.len()];
+        for root in self.vertices.iter().flatten() {
+            if finished[root.id] {
+                continue;
+            }
+            on_path[root.id] = true;
+            let mut path = vec![(root.id, self.outgoing(root.id))];
+            while let Some((current, edges)) = path.last_mut() {
+                let current = *current;
+                let Some(edge) = edges.next() else {
+                    path.pop();
+                    on_path[current] = false;
+                    finished[current] = true;
+                    continue;
+                };
+                if edge.cost == W::INFINITY || finished[edge.to] {
+                    continue;
+                }
+                if on_path[edge.to] {
+                    let start = path.iter().position(|(id, _)| *id == edge.to).unwrap();
+                    return Some(path[start..].iter().map(|(id, _)| *id).collect());
+                }
+                on_path[edge.to] = true;
+                path.push((edge.to, self.outgoing(edge.to)));
+            }
+        }
+        None
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Clone,
+{
+    /// Like [`Graph::get_shortest_path`], but for acyclic graphs only: relaxes
+    /// each vertex once in topological order, which takes linear time and
+    /// supports negative edge costs.
+    ///
+    /// Fails with [`GraphError::Cyclic`] if the graph has a cycle, or if
+    /// either vertex is unknown or `to` is not reachable from `from`.
+    pub fn get_shortest_path_dag(&self, from: usize, to: usize) -> Result<Path<T>, GraphError> {
+        span!(DEBUG, "Graph::get_shortest_path_dag", from, to);
+        self.dag_path(from, to, 1.0)
+    }
+
+    /// Returns the most expensive path from `from` to `to` in an acyclic
+    /// graph, e.g. the critical path through a network of tasks whose edges
+    /// cost their durations.
+    ///
+    /// Fails with [`GraphError::Cyclic`] if the graph has a cycle, or if
+    /// either vertex is unknown or `to` is not reachable from `from`.
+    pub fn get_longest_path_dag(&self, from: usize, to: usize) -> Result<Path<T>, GraphError> {
+        self.dag_path(from, to, -1.0)
+    }
+
+    /// Finds the cheapest path after multiplying every edge cost by `sign`,
+    /// and reports it with the original costs.
+    fn dag_path(&self, from: usize, to: usize, sign: f64) -> Result<Path<T>, GraphError> {
+        self.get_vertex(from)?;
+        self.get_vertex(to)?;
+        let order = self.topological_sort()?;
+
+        let slots = self.vertices.len();
+        let mut dist = vec![f64::INFINITY; slots];
+        let mut prev: Predecessors = vec![None; slots];
+        // Cost of the edge each vertex was reached by.
+        let mut step = vec![0.0; slots];
+        dist[from] = 0.0;
+        for current in order {
+            if dist[current] == f64::INFINITY {
+                continue;
+            }
+            for edge in self.outgoing(current) {
+                if edge.cost == f64::INFINITY {
+                    continue;
+                }
+                let new_dist = dist[current] + sign * edge.cost;
+                if new_dist < dist[edge.to] {
+                    dist[edge.to] = new_dist;
+                    prev[edge.to] = Some(current as CompactId);
+                    step[edge.to] = edge.cost;
+                }
+            }
+        }
+        if dist[to] == f64::INFINITY {
+            return Err(GraphError::NoPathFound { from, to });
+        }
+
+        let path = self.reconstruct_path(&prev, from, to);
+        let costs = path[1..].iter().map(|vertex| step[vertex.id]).collect();
+        Ok(Path::new(path, costs, sign * dist[to]))
+    }
+}
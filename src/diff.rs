@@ -0,0 +1,277 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+
+use crate::{Graph, Weight};
+
+/// A difference between two graphs, found by [`Graph::diff`]. Vertices are
+/// matched by id, and edges by their endpoints and whether they are
+/// undirected, with undirected edges given from the lower id.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GraphChange<W = f64> {
+    /// Only the other graph has a vertex with this id.
+    AddedVertex { id: usize },
+    /// Only this graph has a vertex with this id.
+    RemovedVertex { id: usize },
+    /// Both graphs have the vertex, with different values.
+    ChangedValue { id: usize },
+    /// Both graphs have the vertex, with different entry costs.
+    ChangedEntryCost { id: usize, old: W, new: W },
+    /// Only the other graph has this edge, or more parallel copies of it.
+    AddedEdge {
+        from: usize,
+        to: usize,
+        undirected: bool,
+        cost: W,
+    },
+    /// Only this graph has this edge, or more parallel copies of it.
+    RemovedEdge {
+        from: usize,
+        to: usize,
+        undirected: bool,
+        cost: W,
+    },
+    /// Both graphs have the edge, with different costs.
+    ChangedCost {
+        from: usize,
+        to: usize,
+        undirected: bool,
+        old: W,
+        new: W,
+    },
+}
+
+impl<W: Weight> Display for GraphChange<W> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let arrow = |undirected: bool| if undirected { "<->" } else { "->" };
+        match self {
+            GraphChange::AddedVertex { id } => write!(f, "Added vertex {}", id),
+            GraphChange::RemovedVertex { id } => write!(f, "Removed vertex {}", id),
+            GraphChange::ChangedValue { id } => write!(f, "Changed the value of vertex {}", id),
+            GraphChange::ChangedEntryCost { id, old, new } => write!(
+                f,
+                "Changed the entry cost of vertex {} from {} to {}",
+                id,
+                old.to_f64(),
+                new.to_f64()
+            ),
+            GraphChange::AddedEdge {
+                from,
+                to,
+                undirected,
+                cost,
+            } => write!(
+                f,
+                "Added edge {} {} {} ({})",
+                from,
+                arrow(*undirected),
+                to,
+                cost.to_f64()
+            ),
+            GraphChange::RemovedEdge {
+                from,
+                to,
+                undirected,
+                cost,
+            } => write!(
+                f,
+                "Removed edge {} {} {} ({})",
+                from,
+                arrow(*undirected),
+                to,
+                cost.to_f64()
+            ),
+            GraphChange::ChangedCost {
+                from,
+                to,
+                undirected,
+                old,
+                new,
+            } => write!(
+                f,
+                "Changed the cost of edge {} {} {} from {} to {}",
+                from,
+                arrow(*undirected),
+                to,
+                old.to_f64(),
+                new.to_f64()
+            ),
+        }
+    }
+}
+
+/// The outcome of [`Graph::diff`]: every difference found, grouped by kind
+/// in the order of [`GraphChange`] and ordered by vertex ids within each
+/// kind.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphDiff<W = f64> {
+    changes: Vec<GraphChange<W>>,
+}
+
+impl<W> GraphDiff<W> {
+    /// Returns whether the graphs are equal.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn changes(&self) -> &[GraphChange<W>] {
+        &self.changes
+    }
+}
+
+/// Lists one difference per line.
+impl<W: Weight> Display for GraphDiff<W> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+/// An edge as compared by [`Graph::diff`]: `(from, to, undirected)`.
+type EdgeKey = (usize, usize, bool);
+
+impl<T, W, E> Graph<T, W, E>
+where
+    T: Clone + PartialEq,
+    W: Weight,
+    E: Clone,
+{
+    /// Compares the graph with `other`, e.g. one built by another import
+    /// pipeline, and lists what would have to change to turn this graph into
+    /// `other`: vertices, their values and entry costs, edges and their
+    /// costs.
+    ///
+    /// The order in which edges were added, their ids, payloads and other
+    /// attributes are ignored. Parallel edges are matched by cost, and only
+    /// the copies without a match are reported, as changed costs as far as
+    /// both graphs have some left.
+    pub fn diff(&self, other: &Graph<T, W, E>) -> GraphDiff<W> {
+        let mut vertex_changes = [Vec::new(), Vec::new(), Vec::new(), Vec::new()];
+        let slots = self.vertices.len().max(other.vertices.len());
+        for id in 0..slots {
+            let old = self.vertices.get(id).and_then(Option::as_ref);
+            let new = other.vertices.get(id).and_then(Option::as_ref);
+            match (old, new) {
+                (None, Some(_)) => vertex_changes[0].push(GraphChange::AddedVertex { id }),
+                (Some(_), None) => vertex_changes[1].push(GraphChange::RemovedVertex { id }),
+                (Some(old), Some(new)) => {
+                    if old.value != new.value {
+                        vertex_changes[2].push(GraphChange::ChangedValue { id });
+                    }
+                    let (old, new) = (self.entry_cost(id), other.entry_cost(id));
+                    if old != new {
+                        vertex_changes[3].push(GraphChange::ChangedEntryCost { id, old, new });
+                    }
+                }
+                (None, None) => {}
+            }
+        }
+
+        let (old_edges, new_edges) = (self.edge_costs(), other.edge_costs());
+        let mut keys: Vec<&EdgeKey> = old_edges.keys().chain(new_edges.keys()).collect();
+        keys.sort_unstable();
+        keys.dedup();
+        let mut edge_changes = [Vec::new(), Vec::new(), Vec::new()];
+        for &key @ (from, to, undirected) in keys {
+            let (mut removed, mut added) = unmatched(
+                old_edges.get(&key).map_or(&[], Vec::as_slice),
+                new_edges.get(&key).map_or(&[], Vec::as_slice),
+            );
+            let changed = removed.len().min(added.len());
+            for (old, new) in removed.drain(..changed).zip(added.drain(..changed)) {
+                edge_changes[2].push(GraphChange::ChangedCost {
+                    from,
+                    to,
+                    undirected,
+                    old,
+                    new,
+                });
+            }
+            for cost in added {
+                edge_changes[0].push(GraphChange::AddedEdge {
+                    from,
+                    to,
+                    undirected,
+                    cost,
+                });
+            }
+            for cost in removed {
+                edge_changes[1].push(GraphChange::RemovedEdge {
+                    from,
+                    to,
+                    undirected,
+                    cost,
+                });
+            }
+        }
+
+        GraphDiff {
+            changes: vertex_changes
+                .into_iter()
+                .chain(edge_changes)
+                .flatten()
+                .collect(),
+        }
+    }
+
+    /// Returns the sorted costs of the edges of live vertices by
+    /// [`EdgeKey`].
+    fn edge_costs(&self) -> BTreeMap<EdgeKey, Vec<W>> {
+        let mut costs: BTreeMap<EdgeKey, Vec<W>> = BTreeMap::new();
+        for (from, edges) in self.adjacency.iter().enumerate() {
+            if self.vertices[from].is_none() {
+                continue;
+            }
+            for edge in edges {
+                let key = if edge.undirected {
+                    (from.min(edge.to), from.max(edge.to), true)
+                } else {
+                    (from, edge.to, false)
+                };
+                costs.entry(key).or_default().push(edge.cost);
+            }
+        }
+        for list in costs.values_mut() {
+            // Costs are never NaN, so they are totally ordered.
+            list.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+        }
+        costs
+    }
+}
+
+/// Returns the costs of `old` and of `new` left once equal costs are
+/// paired up, both sorted as the inputs are.
+fn unmatched<W: Weight>(old: &[W], new: &[W]) -> (Vec<W>, Vec<W>) {
+    let (mut removed, mut added) = (Vec::new(), Vec::new());
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            i += 1;
+            j += 1;
+        } else if old[i] < new[j] {
+            removed.push(old[i]);
+            i += 1;
+        } else {
+            added.push(new[j]);
+            j += 1;
+        }
+    }
+    removed.extend_from_slice(&old[i..]);
+    added.extend_from_slice(&new[j..]);
+    (removed, added)
+}
+
+/// Graphs are equal if [`Graph::diff`] finds no difference, whatever order
+/// their edges were added in.
+impl<T, W, E> PartialEq for Graph<T, W, E>
+where
+    T: Clone + PartialEq,
+    W: Weight,
+    E: Clone,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.diff(other).is_empty()
+    }
+}
@@ -0,0 +1,233 @@
+use std::collections::BinaryHeap;
+
+use crate::min_non_nan::MinNonNan;
+use crate::{Graph, GraphError, Path};
+
+/// An edge of the residual network, paired with the one undoing it.
+struct Arc {
+    to: usize,
+    cost: f64,
+    residual: u8,
+    reverse: usize,
+}
+
+impl<T> Graph<T>
+where
+    T: Clone,
+{
+    /// Returns two paths from `from` to `to` that share no edge and whose
+    /// combined cost is as low as possible, using Suurballe's algorithm, the
+    /// cheaper path first. An undirected edge may be used by only one of the
+    /// paths, in one direction.
+    ///
+    /// Unlike taking the shortest path and searching again without its
+    /// edges, this finds a pair whenever one exists.
+    ///
+    /// Fails if either vertex is unknown, an edge cost is negative, or there
+    /// are no two such paths.
+    pub fn get_disjoint_paths(
+        &self,
+        from: usize,
+        to: usize,
+    ) -> Result<(Path<T>, Path<T>), GraphError> {
+        self.disjoint_paths(from, to, false)
+    }
+
+    /// Like [`Graph::get_disjoint_paths`], but the paths also share no
+    /// vertex other than `from` and `to`, so that no single vertex failing
+    /// can cut both.
+    pub fn get_vertex_disjoint_paths(
+        &self,
+        from: usize,
+        to: usize,
+    ) -> Result<(Path<T>, Path<T>), GraphError> {
+        self.disjoint_paths(from, to, true)
+    }
+
+    /// Sends two units of flow from `from` to `to` at the lowest cost, one
+    /// cheapest augmenting path at a time, and splits the flow into paths.
+    fn disjoint_paths(
+        &self,
+        from: usize,
+        to: usize,
+        split_vertices: bool,
+    ) -> Result<(Path<T>, Path<T>), GraphError> {
+        self.get_vertex(from)?;
+        self.get_vertex(to)?;
+        if let Some(edge) = self.adjacency.iter().flatten().find(|edge| edge.cost < 0.0) {
+            return Err(GraphError::InvalidCost(edge.cost));
+        }
+        if from == to {
+            let path = || self.to_path(vec![self.vertex(from).clone()], 0.0);
+            return Ok((path(), path()));
+        }
+
+        // With `split_vertices`, every vertex but the endpoints is entered at
+        // its own id and left at its id plus `slots`, across a single arc
+        // that only one path can take.
+        let slots = self.vertices.len();
+        let exit = |id: usize| {
+            if split_vertices && id != from && id != to {
+                id + slots
+            } else {
+                id
+            }
+        };
+        let nodes = if split_vertices { 2 * slots } else { slots };
+        let mut arcs: Vec<Arc> = Vec::new();
+        let mut arcs_of: Vec<Vec<usize>> = vec![Vec::new(); nodes];
+        let mut add_arc = |arcs: &mut Vec<Arc>, tail: usize, head: usize, cost: f64| {
+            let forward = arcs.len();
+            arcs.push(Arc {
+                to: head,
+                cost,
+                residual: 1,
+                reverse: forward + 1,
+            });
+            arcs.push(Arc {
+                to: tail,
+                cost: -cost,
+                residual: 0,
+                reverse: forward,
+            });
+            arcs_of[tail].push(forward);
+            arcs_of[head].push(forward + 1);
+            forward
+        };
+        // Pairs of arcs standing for the two directions of one undirected
+        // edge.
+        let mut undirected = Vec::new();
+        for vertex in self.vertices.iter().flatten() {
+            if split_vertices && exit(vertex.id) != vertex.id {
+                add_arc(&mut arcs, vertex.id, exit(vertex.id), 0.0);
+            }
+            for edge in &self.adjacency[vertex.id] {
+                if edge.cost == f64::INFINITY {
+                    continue;
+                }
+                // Arcs carry the entry cost of the vertex they lead to.
+                let cost = edge.cost + self.entry_cost(edge.to);
+                let forward = add_arc(&mut arcs, exit(vertex.id), edge.to, cost);
+                if edge.undirected {
+                    let cost = edge.cost + self.entry_cost(vertex.id);
+                    let backward = add_arc(&mut arcs, exit(edge.to), vertex.id, cost);
+                    undirected.push((forward, backward));
+                }
+            }
+        }
+
+        // Costs adjusted by the distances of the previous round stay
+        // non-negative along the residual arcs, so Dijkstra still applies.
+        let mut potential = vec![0.0; nodes];
+        for _ in 0..2 {
+            let (dist, prev_arc) = cheapest_augmenting_path(&arcs, &arcs_of, &potential, from);
+            if dist[to] == f64::INFINITY {
+                return Err(GraphError::NoDisjointPaths { from, to });
+            }
+            let mut at = to;
+            while let Some(arc) = prev_arc[at] {
+                arcs[arc].residual -= 1;
+                let reverse = arcs[arc].reverse;
+                arcs[reverse].residual += 1;
+                at = arcs[reverse].to;
+            }
+            for (node, distance) in potential.iter_mut().zip(dist) {
+                if distance != f64::INFINITY {
+                    *node += distance;
+                }
+            }
+        }
+
+        // Flow through both directions of an undirected edge cancels out.
+        for (forward, backward) in undirected {
+            if arcs[forward].residual == 0 && arcs[backward].residual == 0 {
+                for arc in [forward, backward] {
+                    arcs[arc].residual = 1;
+                    let reverse = arcs[arc].reverse;
+                    arcs[reverse].residual = 0;
+                }
+            }
+        }
+
+        let mut first = self.follow_flow(&mut arcs, &arcs_of, from, to, slots);
+        let mut second = self.follow_flow(&mut arcs, &arcs_of, from, to, slots);
+        if second.cost() < first.cost() {
+            std::mem::swap(&mut first, &mut second);
+        }
+        Ok((first, second))
+    }
+
+    /// Walks from `from` to `to` along arcs that carry flow, using each up,
+    /// and returns the walk with any loops cut out.
+    fn follow_flow(
+        &self,
+        arcs: &mut [Arc],
+        arcs_of: &[Vec<usize>],
+        from: usize,
+        to: usize,
+        slots: usize,
+    ) -> Path<T> {
+        let mut ids = vec![from];
+        let mut costs = Vec::new();
+        let mut at = from;
+        while at != to {
+            let arc = *arcs_of[at]
+                .iter()
+                .find(|arc| *arc % 2 == 0 && arcs[**arc].residual == 0)
+                .unwrap();
+            arcs[arc].residual = 1;
+            at = arcs[arc].to;
+            if at >= slots {
+                continue;
+            }
+            // Returning to a vertex closes a loop, which costs nothing less
+            // than skipping it.
+            if let Some(seen) = ids.iter().position(|id| *id == at) {
+                ids.truncate(seen + 1);
+                costs.truncate(seen);
+                continue;
+            }
+            ids.push(at);
+            costs.push(arcs[arc].cost);
+        }
+
+        let cost = costs.iter().sum();
+        let path = ids.iter().map(|id| self.vertex(*id).clone()).collect();
+        Path::new(path, costs, cost)
+    }
+}
+
+/// Dijkstra over the residual arcs with costs reduced by `potential`, which
+/// returns the reduced distances and the arc each node was reached by.
+fn cheapest_augmenting_path(
+    arcs: &[Arc],
+    arcs_of: &[Vec<usize>],
+    potential: &[f64],
+    from: usize,
+) -> (Vec<f64>, Vec<Option<usize>>) {
+    let mut dist = vec![f64::INFINITY; arcs_of.len()];
+    let mut prev_arc = vec![None; arcs_of.len()];
+    let mut queue = BinaryHeap::new();
+    dist[from] = 0.0;
+    queue.push((MinNonNan(0.0), from));
+    while let Some((MinNonNan(cost), current)) = queue.pop() {
+        if cost > dist[current] {
+            continue;
+        }
+        for index in &arcs_of[current] {
+            let arc = &arcs[*index];
+            if arc.residual == 0 {
+                continue;
+            }
+            // Rounding can leave reduced costs a hair below zero.
+            let reduced = (arc.cost + potential[current] - potential[arc.to]).max(0.0);
+            let new_dist = cost + reduced;
+            if new_dist < dist[arc.to] {
+                dist[arc.to] = new_dist;
+                prev_arc[arc.to] = Some(*index);
+                queue.push((MinNonNan(new_dist), arc.to));
+            }
+        }
+    }
+    (dist, prev_arc)
+}
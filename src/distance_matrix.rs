@@ -0,0 +1,53 @@
+use std::cell::Cell;
+
+use crate::{Graph, GraphError, Vertex};
+
+impl<T> Graph<T>
+where
+    T: Clone,
+{
+    /// Returns the costs from `source` to each of `targets`, searching only
+    /// until all of them are settled.
+    pub(crate) fn distance_row(&self, source: usize, targets: &[usize]) -> Vec<f64> {
+        let mut is_target = vec![false; self.vertices.len()];
+        for target in targets {
+            is_target[*target] = true;
+        }
+
+        let remaining = Cell::new(is_target.iter().filter(|t| **t).count());
+        let is_goal = |vertex: &Vertex<T>, _| {
+            if is_target[vertex.id] {
+                remaining.set(remaining.get() - 1);
+            }
+            remaining.get() == 0
+        };
+
+        let (dist, _, _) = self.search_until(&[(source, 0.0)], is_goal, |_| 0.0, |_, _| true);
+        targets.iter().map(|target| dist[*target]).collect()
+    }
+
+    /// Fails if any of `ids` is not a vertex.
+    pub(crate) fn check_all(&self, ids: &[usize]) -> Result<(), GraphError> {
+        ids.iter()
+            .try_for_each(|id| self.get_vertex(*id).map(|_| ()))
+    }
+
+    /// Computes the cost from every source to every target, one search per
+    /// source.
+    ///
+    /// `matrix[i][j]` is the cost from `sources[i]` to `targets[j]`, or
+    /// infinity if it is unreachable.
+    pub fn distance_matrix(
+        &self,
+        sources: &[usize],
+        targets: &[usize],
+    ) -> Result<Vec<Vec<f64>>, GraphError> {
+        self.check_all(sources)?;
+        self.check_all(targets)?;
+
+        Ok(sources
+            .iter()
+            .map(|source| self.distance_row(*source, targets))
+            .collect())
+    }
+}
@@ -0,0 +1,462 @@
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use crate::{Edge, Graph, GraphError, Path, ShortestPathTree, Vertex};
+
+/// Attributes that make highlighted vertices and edges stand out.
+const HIGHLIGHT: &str = ", color=red, penwidth=2";
+
+impl<T: Display> Graph<T>
+where
+    T: Clone,
+{
+    /// Renders the graph in Graphviz DOT format, labelling vertices with their
+    /// values and edges with their costs.
+    pub fn to_dot(&self) -> String {
+        self.to_dot_with_path(&[])
+    }
+
+    /// Like [`Graph::to_dot`], but highlights the vertices and edges of `path`,
+    /// given as consecutive vertex ids.
+    pub fn to_dot_with_path(&self, path: &[usize]) -> String {
+        let on_path = |from: usize, to: usize, undirected: bool| {
+            path.windows(2).any(|step| {
+                (step[0] == from && step[1] == to)
+                    || (undirected && step[0] == to && step[1] == from)
+            })
+        };
+        self.render_dot(
+            |vertex| {
+                let highlight = if path.contains(&vertex.id) {
+                    HIGHLIGHT
+                } else {
+                    ""
+                };
+                (vertex.value.to_string(), highlight)
+            },
+            |from, edge| on_path(from, edge.to, edge.undirected),
+        )
+    }
+
+    /// Like [`Graph::to_dot_with_path`], but highlights exactly the edges
+    /// `path` took, telling them apart from parallel edges between the same
+    /// vertices, and labels its vertices with the cost of the path so far.
+    pub fn to_dot_with_route(&self, path: &Path<T>) -> String {
+        let edges: HashSet<usize> = path.edge_ids().iter().copied().collect();
+        let mut cost_so_far = HashMap::new();
+        let mut cost = 0.0;
+        for (step, vertex) in path.vertices().iter().enumerate() {
+            if step > 0 {
+                cost += path.costs()[step - 1];
+            }
+            cost_so_far.entry(vertex.id).or_insert(cost);
+        }
+        self.render_dot(
+            |vertex| match cost_so_far.get(&vertex.id) {
+                Some(cost) => (format!("{}\n{}", vertex.value, cost), HIGHLIGHT),
+                None => (vertex.value.to_string(), ""),
+            },
+            |_, edge| edges.contains(&edge.id),
+        )
+    }
+
+    /// Renders the graph with the shortest path `tree` highlighted: its
+    /// source in bold, every vertex it reaches labelled with its distance,
+    /// the edges it was grown along in red, and unreachable vertices greyed
+    /// out. Of parallel edges, only the cheapest is part of the tree.
+    pub fn to_dot_with_tree(&self, tree: &ShortestPathTree) -> String {
+        let mut edges = HashSet::new();
+        for (id, prev) in tree.prev.iter().enumerate() {
+            let Some(prev) = *prev else {
+                continue;
+            };
+            let prev = prev as usize;
+            let cheapest = self
+                .outgoing(prev)
+                .filter(|edge| edge.to == id)
+                .min_by(|a, b| a.cost.total_cmp(&b.cost));
+            if let Some(edge) = cheapest {
+                edges.insert(edge.id);
+            }
+        }
+        self.render_dot(
+            |vertex| match tree.distance_to(vertex.id) {
+                Some(_) if vertex.id == tree.source() => (
+                    format!("{}\n0", vertex.value),
+                    ", color=red, penwidth=3, style=bold",
+                ),
+                Some(distance) => (format!("{}\n{}", vertex.value, distance), HIGHLIGHT),
+                None => (
+                    vertex.value.to_string(),
+                    ", color=gray, fontcolor=gray, style=dashed",
+                ),
+            },
+            |_, edge| edges.contains(&edge.id),
+        )
+    }
+
+    /// Writes every vertex with the label and extra attributes returned by
+    /// `vertex_style`, with line breaks in labels kept, and every edge,
+    /// highlighted where `highlight` holds for its source and the edge.
+    fn render_dot<V, H>(&self, vertex_style: V, highlight: H) -> String
+    where
+        V: Fn(&Vertex<T>) -> (String, &'static str),
+        H: Fn(usize, &Edge) -> bool,
+    {
+        let mut dot = String::from("digraph {\n");
+        for vertex in self.vertices.iter().flatten() {
+            let (label, attributes) = vertex_style(vertex);
+            dot += &format!(
+                "    {} [label=\"{}\"{}];\n",
+                vertex.id,
+                escape(&label).replace('\n', "\\n"),
+                attributes
+            );
+        }
+        for vertex in self.vertices.iter().flatten() {
+            for edge in &self.adjacency[vertex.id] {
+                let direction = if edge.undirected { ", dir=both" } else { "" };
+                let highlight = if highlight(vertex.id, edge) {
+                    HIGHLIGHT
+                } else {
+                    ""
+                };
+                dot += &format!(
+                    "    {} -> {} [label=\"{}\"{}{}];\n",
+                    vertex.id, edge.to, edge.cost, direction, highlight
+                );
+            }
+        }
+        dot += "}\n";
+        dot
+    }
+}
+
+/// Renders a graph in DOT format, such as the output of
+/// [`Graph::to_dot_with_tree`], to SVG by piping it through the Graphviz
+/// `dot` command, which has to be installed.
+///
+/// Fails with [`GraphError::Io`] if `dot` cannot be run or rejects the
+/// input.
+pub fn render_svg(dot: &str) -> Result<String, GraphError> {
+    let io = |error: std::io::Error| GraphError::Io(format!("running dot: {}", error));
+    let mut child = Command::new("dot")
+        .arg("-Tsvg")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(io)?;
+    // Written from another thread, as `dot` may fill its output pipe before
+    // reading all of a large graph.
+    let mut stdin = child.stdin.take().unwrap();
+    let input = dot.to_string();
+    let writer = std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let output = child.wait_with_output().map_err(io)?;
+    let written = writer.join().unwrap();
+    if !output.status.success() {
+        return Err(GraphError::Io(format!(
+            "dot failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    written.map_err(io)?;
+    String::from_utf8(output.stdout).map_err(|_| GraphError::Io("dot wrote invalid UTF-8".into()))
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token<'a> {
+    Id(Cow<'a, str>),
+    EdgeOp,
+    Symbol(char),
+}
+
+fn tokenize(input: &str) -> Result<Vec<(Token<'_>, usize)>, GraphError> {
+    let bytes = input.as_bytes();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let c = bytes[i];
+        let next = bytes.get(i + 1).copied();
+        match c {
+            b'\n' => {
+                line += 1;
+                i += 1;
+            }
+            _ if c.is_ascii_whitespace() => i += 1,
+            b'#' => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if next == Some(b'/') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if next == Some(b'*') => {
+                i += 2;
+                while i < bytes.len() && !(bytes[i] == b'*' && bytes.get(i + 1) == Some(&b'/')) {
+                    if bytes[i] == b'\n' {
+                        line += 1;
+                    }
+                    i += 1;
+                }
+                i += 2;
+            }
+            b'-' if next == Some(b'>') || next == Some(b'-') => {
+                tokens.push((Token::EdgeOp, line));
+                i += 2;
+            }
+            b'{' | b'}' | b'[' | b']' | b';' | b',' | b'=' => {
+                tokens.push((Token::Symbol(c as char), line));
+                i += 1;
+            }
+            b'"' => {
+                let start = i + 1;
+                let start_line = line;
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    if bytes[i] == b'\\' {
+                        i += 1;
+                    }
+                    if bytes.get(i) == Some(&b'\n') {
+                        line += 1;
+                    }
+                    i += 1;
+                }
+                if i >= bytes.len() {
+                    return Err(parse_error(start_line, "unterminated string"));
+                }
+                tokens.push((Token::Id(unescape(&input[start..i])), start_line));
+                i += 1;
+            }
+            _ if is_id_byte(c) || c == b'-' => {
+                let start = i;
+                i += 1;
+                while i < bytes.len() && is_id_byte(bytes[i]) {
+                    i += 1;
+                }
+                tokens.push((Token::Id(Cow::Borrowed(&input[start..i])), line));
+            }
+            _ => {
+                return Err(parse_error(
+                    line,
+                    &format!("unexpected character '{}'", c as char),
+                ))
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Undoes the escaping done by the exporter on the contents of a quoted id:
+/// `\"`, `\\` and the `\n` line breaks of labels. Escaped newlines continue
+/// the string on the next line and are dropped, and other escapes are kept
+/// as written.
+fn unescape(quoted: &str) -> Cow<'_, str> {
+    if !quoted.contains('\\') {
+        return Cow::Borrowed(quoted);
+    }
+    let mut text = String::with_capacity(quoted.len());
+    let mut chars = quoted.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some(c @ ('"' | '\\')) => text.push(c),
+            Some('n') => text.push('\n'),
+            Some('\n') => {}
+            Some(c) => {
+                text.push('\\');
+                text.push(c);
+            }
+            None => text.push('\\'),
+        }
+    }
+    Cow::Owned(text)
+}
+
+fn is_id_byte(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_' || c == b'.' || !c.is_ascii()
+}
+
+fn parse_error(line: usize, message: &str) -> GraphError {
+    GraphError::Parse {
+        line,
+        message: message.to_string(),
+    }
+}
+
+/// `key=value` pairs of an attribute list, in order.
+type Attributes<'a> = Vec<(Cow<'a, str>, Cow<'a, str>)>;
+
+struct Parser<'a> {
+    tokens: Vec<(Token<'a>, usize)>,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&Token<'a>> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .or(self.tokens.last())
+            .map_or(1, |(_, line)| *line)
+    }
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        let token = self.peek().cloned();
+        self.position += 1;
+        token
+    }
+
+    fn expect(&mut self, symbol: char) -> Result<(), GraphError> {
+        match self.next() {
+            Some(Token::Symbol(c)) if c == symbol => Ok(()),
+            _ => Err(parse_error(self.line(), &format!("expected '{}'", symbol))),
+        }
+    }
+
+    fn id(&mut self) -> Result<Cow<'a, str>, GraphError> {
+        match self.next() {
+            Some(Token::Id(id)) => Ok(id),
+            _ => Err(parse_error(self.line(), "expected an identifier")),
+        }
+    }
+
+    fn attributes(&mut self) -> Result<Attributes<'a>, GraphError> {
+        let mut attributes = Vec::new();
+        while self.peek() == Some(&Token::Symbol('[')) {
+            self.next();
+            while self.peek() != Some(&Token::Symbol(']')) {
+                let key = self.id()?;
+                self.expect('=')?;
+                attributes.push((key, self.id()?));
+                if let Some(Token::Symbol(';' | ',')) = self.peek() {
+                    self.next();
+                }
+            }
+            self.expect(']')?;
+        }
+        Ok(attributes)
+    }
+}
+
+impl Graph<String> {
+    /// Parses a graph from a subset of the Graphviz DOT language: node
+    /// statements and edge statements inside a `graph` or `digraph`.
+    ///
+    /// Vertex values are node labels, or node ids for nodes without a label.
+    /// Edge costs are taken from the `weight` or else the `label` attribute
+    /// and default to 1. Edges of a `graph`, or with `dir=both`, are
+    /// undirected.
+    pub fn from_dot(input: &str) -> Result<Self, GraphError> {
+        let mut parser = Parser {
+            tokens: tokenize(input)?,
+            position: 0,
+        };
+
+        if matches!(parser.peek(), Some(Token::Id(id)) if id == "strict") {
+            parser.next();
+        }
+        let undirected_graph = match parser.id()?.as_ref() {
+            "graph" => true,
+            "digraph" => false,
+            _ => return Err(parse_error(parser.line(), "expected 'graph' or 'digraph'")),
+        };
+        if let Some(Token::Id(_)) = parser.peek() {
+            parser.next();
+        }
+        parser.expect('{')?;
+
+        let mut graph = Graph::new();
+        let mut ids: HashMap<Cow<str>, usize> = HashMap::new();
+
+        loop {
+            let line = parser.line();
+            let name = match parser.next() {
+                Some(Token::Symbol('}')) => break,
+                Some(Token::Symbol(';')) => continue,
+                Some(Token::Id(name)) => name,
+                _ => return Err(parse_error(line, "expected a statement")),
+            };
+
+            if parser.peek() == Some(&Token::Symbol('=')) {
+                parser.next();
+                parser.id()?;
+                continue;
+            }
+            if matches!(name.as_ref(), "graph" | "node" | "edge") {
+                parser.attributes()?;
+                continue;
+            }
+            if name == "subgraph" {
+                return Err(parse_error(line, "subgraphs are not supported"));
+            }
+
+            let mut chain = vec![name.clone()];
+            while parser.peek() == Some(&Token::EdgeOp) {
+                parser.next();
+                chain.push(parser.id()?);
+            }
+            let attributes = parser.attributes()?;
+            let attribute = |key: &str| {
+                attributes
+                    .iter()
+                    .find(|(k, _)| *k == key)
+                    .map(|(_, value)| value.as_ref())
+            };
+
+            if chain.len() == 1 {
+                let label = attribute("label").unwrap_or(&name).to_string();
+                match ids.get(&name) {
+                    Some(id) => graph.vertices[*id].as_mut().unwrap().value = label,
+                    None => {
+                        ids.insert(name, graph.add_vertex(label));
+                    }
+                }
+                continue;
+            }
+
+            let cost = match attribute("weight").or(attribute("label")) {
+                Some(cost) => cost
+                    .parse::<f64>()
+                    .map_err(|_| parse_error(line, &format!("invalid edge cost '{}'", cost)))?,
+                None => 1.0,
+            };
+            let undirected = undirected_graph || attribute("dir") == Some("both");
+
+            for step in chain.windows(2) {
+                let from = *ids
+                    .entry(step[0].clone())
+                    .or_insert_with(|| graph.add_vertex(step[0].to_string()));
+                let to = *ids
+                    .entry(step[1].clone())
+                    .or_insert_with(|| graph.add_vertex(step[1].to_string()));
+                if undirected {
+                    graph.add_edge_undirected(from, to, cost)?;
+                } else {
+                    graph.add_edge(from, to, cost)?;
+                }
+            }
+        }
+
+        Ok(graph)
+    }
+}
@@ -0,0 +1,281 @@
+use std::collections::BinaryHeap;
+
+use crate::min_non_nan::MinNonNan;
+use crate::{Edge, Graph, GraphError, Heuristic, Path};
+
+/// Priority of a vertex in the D* Lite queue, compared lexicographically.
+type Key = (f64, f64);
+
+/// An incremental planner that keeps its search state between queries and
+/// repairs it when edge costs change or the start moves, instead of
+/// replanning from scratch.
+///
+/// The planner owns its graph, so cost changes must go through
+/// [`DStarLite::update_edge_cost`]. Edges cost the entry cost of the vertex
+/// they lead to on top of their own, as with [`Graph::set_entry_cost`], and
+/// the two together must be positive and the heuristic consistent for the
+/// repaired paths to stay optimal.
+pub struct DStarLite<T, H = fn(&T, &T) -> f64> {
+    graph: Graph<T>,
+    heuristic: H,
+    start: usize,
+    goal: usize,
+    /// Start at the time of the last cost change, for the key modifier.
+    last_start: usize,
+    key_modifier: f64,
+    g: Vec<f64>,
+    rhs: Vec<f64>,
+    /// Key each vertex is queued under, so that stale heap entries can be
+    /// told apart from live ones.
+    queued: Vec<Option<Key>>,
+    queue: BinaryHeap<(MinNonNan, MinNonNan, usize)>,
+}
+
+impl<T> DStarLite<T>
+where
+    T: Clone,
+{
+    /// Creates a planner for paths from `start` to `goal` without a heuristic.
+    ///
+    /// Fails if either vertex is unknown or an edge cost, with the entry cost
+    /// of the vertex it leads to, is not positive.
+    pub fn new(graph: Graph<T>, start: usize, goal: usize) -> Result<Self, GraphError> {
+        Self::with_heuristic(graph, start, goal, |_, _| 0.0)
+    }
+}
+
+impl<T, H> DStarLite<T, H>
+where
+    T: Clone,
+    H: Heuristic<T>,
+{
+    /// Creates a planner for paths from `start` to `goal`, guided by a
+    /// consistent `heuristic`.
+    ///
+    /// Fails if either vertex is unknown or an edge cost, with the entry cost
+    /// of the vertex it leads to, is not positive.
+    pub fn with_heuristic(
+        graph: Graph<T>,
+        start: usize,
+        goal: usize,
+        heuristic: H,
+    ) -> Result<Self, GraphError> {
+        graph.get_vertex(start)?;
+        graph.get_vertex(goal)?;
+        if let Some(cost) = graph
+            .vertices()
+            .flat_map(|vertex| graph.outgoing(vertex.id))
+            .map(|edge| edge.cost + graph.entry_cost(edge.to))
+            .find(|cost| *cost <= 0.0)
+        {
+            return Err(GraphError::InvalidCost(cost));
+        }
+
+        let slots = graph.vertices.len();
+        let mut planner = DStarLite {
+            graph,
+            heuristic,
+            start,
+            goal,
+            last_start: start,
+            key_modifier: 0.0,
+            g: vec![f64::INFINITY; slots],
+            rhs: vec![f64::INFINITY; slots],
+            queued: vec![None; slots],
+            queue: BinaryHeap::new(),
+        };
+        planner.rhs[goal] = 0.0;
+        planner.enqueue(goal);
+        Ok(planner)
+    }
+
+    pub fn graph(&self) -> &Graph<T> {
+        &self.graph
+    }
+
+    /// Consumes the planner and returns its graph.
+    pub fn into_graph(self) -> Graph<T> {
+        self.graph
+    }
+
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    pub fn goal(&self) -> usize {
+        self.goal
+    }
+
+    /// Moves the start, e.g. as the robot advances along its path.
+    ///
+    /// Fails if the vertex is unknown.
+    pub fn set_start(&mut self, start: usize) -> Result<(), GraphError> {
+        self.graph.get_vertex(start)?;
+        self.start = start;
+        Ok(())
+    }
+
+    /// Changes the cost of the edges from `from` to `to` as
+    /// [`Graph::update_edge_cost`] does, and marks the affected vertices for
+    /// repair on the next [`DStarLite::path`] call. An infinite cost blocks
+    /// the edge.
+    ///
+    /// Fails like [`Graph::update_edge_cost`], or if the cost, with the entry
+    /// cost of the vertex the edges lead to, is not positive.
+    pub fn update_edge_cost(
+        &mut self,
+        from: usize,
+        to: usize,
+        new_cost: f64,
+    ) -> Result<(), GraphError> {
+        self.graph.get_vertex(from)?;
+        self.graph.get_vertex(to)?;
+        let undirected = self
+            .graph
+            .outgoing(to)
+            .any(|edge| edge.undirected && edge.to == from);
+        let entry = match undirected {
+            true => self.graph.entry_cost(to).min(self.graph.entry_cost(from)),
+            false => self.graph.entry_cost(to),
+        };
+        if new_cost + entry <= 0.0 {
+            return Err(GraphError::InvalidCost(new_cost));
+        }
+        self.graph.update_edge_cost(from, to, new_cost)?;
+
+        self.key_modifier += self.estimate(self.last_start, self.start);
+        self.last_start = self.start;
+        self.update_vertex(from);
+        self.update_vertex(to);
+        Ok(())
+    }
+
+    /// Returns the cheapest path from the current start to the goal,
+    /// repairing the search state as far as needed.
+    ///
+    /// Fails if the goal is not reachable from the start.
+    pub fn path(&mut self) -> Result<Path<T>, GraphError> {
+        self.compute_shortest_path();
+        let no_path = GraphError::NoPathFound {
+            from: self.start,
+            to: self.goal,
+        };
+        if self.g[self.start] == f64::INFINITY {
+            return Err(no_path);
+        }
+
+        let mut visited = vec![false; self.g.len()];
+        let mut path = vec![self.graph.get_vertex(self.start)?.clone()];
+        let mut at = self.start;
+        visited[at] = true;
+        while at != self.goal {
+            let next = self
+                .graph
+                .outgoing(at)
+                .filter(|edge| !visited[edge.to])
+                .map(|edge| {
+                    (
+                        self.step_cost(edge) + self.g[edge.to],
+                        self.g[edge.to],
+                        edge.to,
+                    )
+                })
+                .filter(|(cost, _, _)| *cost < f64::INFINITY)
+                .min_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+            let Some((_, _, next)) = next else {
+                return Err(no_path);
+            };
+            visited[next] = true;
+            path.push(self.graph.vertex(next).clone());
+            at = next;
+        }
+
+        Ok(self.graph.to_path(path, self.g[self.start]))
+    }
+
+    /// Cost of stepping along `edge`, including entering the vertex it leads
+    /// to.
+    fn step_cost(&self, edge: &Edge) -> f64 {
+        edge.cost + self.graph.entry_cost(edge.to)
+    }
+
+    fn estimate(&self, from: usize, to: usize) -> f64 {
+        let from = self.graph.vertex(from);
+        let to = self.graph.vertex(to);
+        self.heuristic.estimate_vertices(from, to)
+    }
+
+    fn key(&self, id: usize) -> Key {
+        let best = self.g[id].min(self.rhs[id]);
+        (
+            best + self.estimate(self.start, id) + self.key_modifier,
+            best,
+        )
+    }
+
+    fn enqueue(&mut self, id: usize) {
+        let key = self.key(id);
+        self.queued[id] = Some(key);
+        self.queue.push((MinNonNan(key.0), MinNonNan(key.1), id));
+    }
+
+    /// Returns the smallest live entry of the queue, dropping stale ones.
+    fn top(&mut self) -> Option<(Key, usize)> {
+        while let Some((MinNonNan(first), MinNonNan(second), id)) = self.queue.peek() {
+            if self.queued[*id] == Some((*first, *second)) {
+                return Some(((*first, *second), *id));
+            }
+            self.queue.pop();
+        }
+        None
+    }
+
+    /// Recomputes the one-step lookahead cost of `id` and requeues it if it
+    /// has become inconsistent.
+    fn update_vertex(&mut self, id: usize) {
+        if id != self.goal {
+            self.rhs[id] = self
+                .graph
+                .outgoing(id)
+                .map(|edge| self.step_cost(edge) + self.g[edge.to])
+                .fold(f64::INFINITY, f64::min);
+        }
+
+        self.queued[id] = None;
+        if self.g[id] != self.rhs[id] {
+            self.enqueue(id);
+        }
+    }
+
+    fn compute_shortest_path(&mut self) {
+        while let Some((old_key, id)) = self.top() {
+            let start_key = self.key(self.start);
+            if !less(old_key, start_key) && self.rhs[self.start] == self.g[self.start] {
+                break;
+            }
+
+            let new_key = self.key(id);
+            if less(old_key, new_key) {
+                self.enqueue(id);
+            } else if self.g[id] > self.rhs[id] {
+                self.g[id] = self.rhs[id];
+                self.queued[id] = None;
+                let sources: Vec<usize> = self.graph.incoming(id).map(|edge| edge.to).collect();
+                for source in sources {
+                    self.update_vertex(source);
+                }
+            } else {
+                self.g[id] = f64::INFINITY;
+                let sources: Vec<usize> = self.graph.incoming(id).map(|edge| edge.to).collect();
+                for source in sources {
+                    self.update_vertex(source);
+                }
+                self.update_vertex(id);
+            }
+        }
+    }
+}
+
+fn less(a: Key, b: Key) -> bool {
+    a.0 < b.0 || (a.0 == b.0 && a.1 < b.1)
+}
@@ -0,0 +1,191 @@
+use std::collections::BinaryHeap;
+
+use crate::graph::CompactId;
+use crate::min_non_nan::MinNonNan;
+use crate::{Graph, GraphError, ShortestPathTree};
+
+/// Shortest paths from one source that are repaired, rather than recomputed,
+/// when edges change, in the style of Ramalingam and Reps: only vertices
+/// whose distance can change are searched again.
+///
+/// The structure owns its graph, so changes must go through its own
+/// [`DynamicShortestPaths::update_edge_cost`],
+/// [`DynamicShortestPaths::add_edge`] and
+/// [`DynamicShortestPaths::remove_edge`]. Edge costs must not be negative.
+pub struct DynamicShortestPaths<T> {
+    graph: Graph<T>,
+    tree: ShortestPathTree,
+}
+
+impl<T> DynamicShortestPaths<T>
+where
+    T: Clone,
+{
+    /// Computes the shortest paths from `source` in `graph`.
+    ///
+    /// Fails if the source is unknown or an edge cost is negative.
+    pub fn new(graph: Graph<T>, source: usize) -> Result<Self, GraphError> {
+        if let Some(edge) = graph
+            .adjacency
+            .iter()
+            .flatten()
+            .find(|edge| edge.cost < 0.0)
+        {
+            return Err(GraphError::InvalidCost(edge.cost));
+        }
+        let tree = graph.shortest_paths_from(source)?;
+        Ok(DynamicShortestPaths { graph, tree })
+    }
+
+    pub fn graph(&self) -> &Graph<T> {
+        &self.graph
+    }
+
+    /// Consumes the structure and returns its graph.
+    pub fn into_graph(self) -> Graph<T> {
+        self.graph
+    }
+
+    /// Returns the shortest paths as of the last change.
+    pub fn tree(&self) -> &ShortestPathTree {
+        &self.tree
+    }
+
+    /// Changes the cost of the edges from `from` to `to` as
+    /// [`Graph::update_edge_cost`] does, and repairs the shortest paths.
+    ///
+    /// Fails like [`Graph::update_edge_cost`], or if the cost is negative.
+    pub fn update_edge_cost(
+        &mut self,
+        from: usize,
+        to: usize,
+        new_cost: f64,
+    ) -> Result<(), GraphError> {
+        if new_cost < 0.0 {
+            return Err(GraphError::InvalidCost(new_cost));
+        }
+        let steps = self.steps_between(from, to);
+        self.graph.update_edge_cost(from, to, new_cost)?;
+        self.repair(&steps);
+        Ok(())
+    }
+
+    /// Adds a directed edge as [`Graph::add_edge`] does, and repairs the
+    /// shortest paths.
+    ///
+    /// Fails like [`Graph::add_edge`], or if the cost is negative.
+    pub fn add_edge(&mut self, from: usize, to: usize, cost: f64) -> Result<(), GraphError> {
+        if cost < 0.0 {
+            return Err(GraphError::InvalidCost(cost));
+        }
+        self.graph.add_edge(from, to, cost)?;
+        self.repair(&[(from, to)]);
+        Ok(())
+    }
+
+    /// Removes the edges from `from` to `to` as [`Graph::remove_edge`] does,
+    /// and repairs the shortest paths.
+    ///
+    /// Fails like [`Graph::remove_edge`].
+    pub fn remove_edge(&mut self, from: usize, to: usize) -> Result<(), GraphError> {
+        let steps = self.steps_between(from, to);
+        self.graph.remove_edge(from, to)?;
+        self.repair(&steps);
+        Ok(())
+    }
+
+    /// Directions in which edges change when those from `from` to `to` do,
+    /// which includes the reverse if any of them is undirected.
+    fn steps_between(&self, from: usize, to: usize) -> Vec<(usize, usize)> {
+        let mut steps = vec![(from, to)];
+        let undirected = self.graph.get_vertex(from).is_ok()
+            && self.graph.get_vertex(to).is_ok()
+            && self
+                .graph
+                .outgoing(from)
+                .any(|edge| edge.to == to && edge.undirected);
+        if undirected && from != to {
+            steps.push((to, from));
+        }
+        steps
+    }
+
+    /// Restores the shortest paths after the edges in the directions `steps`
+    /// changed.
+    fn repair(&mut self, steps: &[(usize, usize)]) {
+        let tree = &mut self.tree;
+
+        // Vertices whose tree path used a changed edge may get farther, and
+        // so may everything below them in the tree.
+        let mut affected = vec![false; tree.dist.len()];
+        let mut stack: Vec<usize> = steps
+            .iter()
+            .filter(|(from, to)| tree.prev[*to] == Some(*from as CompactId))
+            .map(|(_, to)| *to)
+            .collect();
+        let mut reset = Vec::new();
+        while let Some(id) = stack.pop() {
+            if affected[id] {
+                continue;
+            }
+            affected[id] = true;
+            reset.push(id);
+            for edge in self.graph.outgoing(id) {
+                if tree.prev[edge.to] == Some(id as CompactId) {
+                    stack.push(edge.to);
+                }
+            }
+        }
+        for id in &reset {
+            tree.dist[*id] = f64::INFINITY;
+            tree.prev[*id] = None;
+        }
+
+        // Seeds the search with the best way into each reset vertex from the
+        // rest of the tree, and with every changed edge that got cheaper.
+        let mut queue = BinaryHeap::new();
+        for id in &reset {
+            for edge in self.graph.incoming(*id) {
+                if !affected[edge.to] {
+                    let cost = edge.cost + self.graph.entry_cost(*id);
+                    relax(tree, &mut queue, edge.to, *id, cost);
+                }
+            }
+        }
+        for (from, to) in steps {
+            for edge in self.graph.outgoing(*from) {
+                if edge.to == *to {
+                    let cost = edge.cost + self.graph.entry_cost(*to);
+                    relax(tree, &mut queue, *from, *to, cost);
+                }
+            }
+        }
+
+        while let Some((MinNonNan(cost), current)) = queue.pop() {
+            if cost > tree.dist[current] {
+                continue;
+            }
+            for edge in self.graph.outgoing(current) {
+                let cost = edge.cost + self.graph.entry_cost(edge.to);
+                relax(tree, &mut queue, current, edge.to, cost);
+            }
+        }
+    }
+}
+
+/// Lowers the distance of `to` if stepping from `from` at `cost`, which
+/// includes the entry cost of `to`, beats it, and queues it.
+fn relax(
+    tree: &mut ShortestPathTree,
+    queue: &mut BinaryHeap<(MinNonNan, usize)>,
+    from: usize,
+    to: usize,
+    cost: f64,
+) {
+    let new_dist = tree.dist[from] + cost;
+    if new_dist < tree.dist[to] {
+        tree.dist[to] = new_dist;
+        tree.prev[to] = Some(from as CompactId);
+        queue.push((MinNonNan(new_dist), to));
+    }
+}
@@ -0,0 +1,74 @@
+use alloc::vec::Vec;
+
+use crate::{Modes, TravelTimeProfile};
+
+/// A weighted edge stored in the adjacency list of its source vertex.
+///
+/// An undirected edge is stored once but can be traversed in both directions.
+/// Beyond its cost, an edge carries a payload of type `E`, such as a street
+/// name or road class, which paths hand back for each step.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Edge<W = f64, E = ()> {
+    /// Id of the edge, assigned when it is added to a graph and never reused
+    /// there, which tells parallel edges between the same vertices apart.
+    pub id: usize,
+    /// Id of the vertex the edge leads to.
+    pub to: usize,
+    pub cost: W,
+    pub undirected: bool,
+    /// Travel time by departure time, used instead of `cost` by
+    /// time-dependent searches.
+    pub profile: Option<TravelTimeProfile>,
+    /// Costs under several criteria, such as time and toll, used by
+    /// multi-criteria searches. Empty unless set.
+    pub criteria: Vec<f64>,
+    /// Most flow the edge can carry, used by flow algorithms instead of
+    /// `cost`. `None` unless set.
+    pub capacity: Option<f64>,
+    /// Travel modes that may use the edge, checked by queries restricted to
+    /// some modes. [`Modes::ALL`] unless set.
+    pub modes: Modes,
+    /// The payload given when the edge was added, or `E::default()`.
+    pub data: E,
+}
+
+impl<W, E: Default> Edge<W, E> {
+    pub fn new(to: usize, cost: W) -> Self {
+        Self::with_data(to, cost, E::default())
+    }
+
+    pub fn new_undirected(to: usize, cost: W) -> Self {
+        Self::undirected_with_data(to, cost, E::default())
+    }
+}
+
+impl<W, E> Edge<W, E> {
+    pub fn with_data(to: usize, cost: W, data: E) -> Self {
+        Self {
+            id: 0,
+            to,
+            cost,
+            undirected: false,
+            profile: None,
+            criteria: Vec::new(),
+            capacity: None,
+            modes: Modes::ALL,
+            data,
+        }
+    }
+
+    pub fn undirected_with_data(to: usize, cost: W, data: E) -> Self {
+        Self {
+            id: 0,
+            to,
+            cost,
+            undirected: true,
+            profile: None,
+            criteria: Vec::new(),
+            capacity: None,
+            modes: Modes::ALL,
+            data,
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use crate::{Edge, Graph, GraphError, Weight};
+
+impl<T, W, E> Graph<T, W, E>
+where
+    T: Clone,
+    W: Weight,
+    E: Clone,
+{
+    /// Returns the edge with the given id, as returned by
+    /// [`Path::edge_ids`](crate::Path::edge_ids), together with the id of the
+    /// vertex it was added from. Takes time linear in the number of edges.
+    ///
+    /// Fails if there is no such edge, e.g. because it was removed.
+    pub fn edge(&self, id: usize) -> Result<(usize, &Edge<W, E>), GraphError> {
+        self.edges()
+            .find(|(_, edge)| edge.id == id)
+            .ok_or(GraphError::UnknownEdgeId(id))
+    }
+
+    /// Returns an iterator over every edge that can be traversed from `from`
+    /// to `to`, including parallel edges and undirected edges between the
+    /// two, e.g. to pick one by id or payload.
+    ///
+    /// Fails if either vertex is unknown.
+    pub fn edges_between(
+        &self,
+        from: usize,
+        to: usize,
+    ) -> Result<impl Iterator<Item = &Edge<W, E>> + '_, GraphError> {
+        self.get_vertex(from)?;
+        self.get_vertex(to)?;
+        Ok(self.outgoing(from).filter(move |edge| edge.to == to))
+    }
+
+    /// Removes the edge with the given id and returns it as stored at the
+    /// vertex it was added from, leaving parallel edges in place.
+    ///
+    /// Fails if there is no such edge.
+    pub fn remove_edge_by_id(&mut self, id: usize) -> Result<Edge<W, E>, GraphError> {
+        let (from, to) = self.edge(id).map(|(from, edge)| (from, edge.to))?;
+        let index = self.adjacency[from]
+            .iter()
+            .position(|edge| edge.id == id)
+            .unwrap();
+        let edge = self.adjacency[from].remove(index);
+        self.reverse_adjacency[to].retain(|edge| edge.id != id);
+        self.touch();
+        Ok(edge)
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{Graph, GraphError, Weight};
+
+impl<T, W, E> Graph<T, W, E>
+where
+    T: Clone,
+    W: Weight,
+    E: Clone,
+{
+    /// Removes every edge that can be traversed from `from` to `to`,
+    /// including parallel edges and undirected edges between the two.
+    ///
+    /// Fails if either vertex is unknown or there is no such edge.
+    pub fn remove_edge(&mut self, from: usize, to: usize) -> Result<(), GraphError> {
+        self.get_vertex(from)?;
+        self.get_vertex(to)?;
+
+        if !self.remove_edges_between(from, to) {
+            return Err(GraphError::UnknownEdge { from, to });
+        }
+        self.touch();
+        Ok(())
+    }
+
+    /// Removes every edge that can be traversed from `from` to `to`, both
+    /// known to exist, and returns whether there was any.
+    pub(crate) fn remove_edges_between(&mut self, from: usize, to: usize) -> bool {
+        let before = self.adjacency[from].len() + self.adjacency[to].len();
+        self.adjacency[from].retain(|edge| edge.to != to);
+        self.reverse_adjacency[to].retain(|edge| edge.to != from);
+        self.adjacency[to].retain(|edge| !(edge.undirected && edge.to == from));
+        self.reverse_adjacency[from].retain(|edge| !(edge.undirected && edge.to == to));
+        before != self.adjacency[from].len() + self.adjacency[to].len()
+    }
+
+    /// Sets the cost of every edge that can be traversed from `from` to
+    /// `to`, including parallel edges and undirected edges between the two.
+    ///
+    /// Fails if either vertex is unknown, there is no such edge or the cost
+    /// is NaN.
+    pub fn update_edge_cost(
+        &mut self,
+        from: usize,
+        to: usize,
+        new_cost: W,
+    ) -> Result<(), GraphError> {
+        Self::check_cost(new_cost)?;
+        self.get_vertex(from)?;
+        self.get_vertex(to)?;
+
+        let mut updated = false;
+        for edge in &mut self.adjacency[from] {
+            if edge.to == to {
+                edge.cost = new_cost;
+                updated = true;
+            }
+        }
+        for edge in &mut self.adjacency[to] {
+            if edge.undirected && edge.to == from {
+                edge.cost = new_cost;
+                updated = true;
+            }
+        }
+        for edge in &mut self.reverse_adjacency[to] {
+            if edge.to == from {
+                edge.cost = new_cost;
+            }
+        }
+        for edge in &mut self.reverse_adjacency[from] {
+            if edge.undirected && edge.to == to {
+                edge.cost = new_cost;
+            }
+        }
+
+        if !updated {
+            return Err(GraphError::UnknownEdge { from, to });
+        }
+        self.touch();
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use crate::{Graph, GraphError, Weight};
+
+impl<T, W, E> Graph<T, W, E>
+where
+    T: Clone,
+    W: Weight,
+    E: Clone,
+{
+    /// Makes entering the vertex with the given id cost `cost` on top of the
+    /// edge it is entered by, e.g. for a delay at an intersection or a
+    /// terrain penalty. The vertex a path starts at is not entered. Vertices
+    /// cost nothing to enter unless set.
+    ///
+    /// Honoured by [`Graph::get_shortest_path`] and the other Dijkstra-based
+    /// searches, including A*, bidirectional, hop-limited, Dial and 0-1 BFS
+    /// ones, by Bellman-Ford, both all-pairs searches, disjoint paths,
+    /// co-optimal paths, arc flags, betweenness centrality,
+    /// [`DynamicShortestPaths`](crate::DynamicShortestPaths) and
+    /// [`DStarLite`](crate::DStarLite), and by the step costs of their paths.
+    /// Structures with their own copy of the costs, like
+    /// [`ContractedGraph`](crate::ContractedGraph),
+    /// [`FrozenGraph`](crate::FrozenGraph) and
+    /// [`AdjacencyMatrix`](crate::AdjacencyMatrix), and searches with costs
+    /// of their own, such as DAG, turn-aware and multi-criteria searches,
+    /// ignore it.
+    ///
+    /// Fails if the vertex is unknown or the cost is NaN.
+    #[allow(clippy::eq_op)]
+    pub fn set_entry_cost(&mut self, id: usize, cost: W) -> Result<(), GraphError> {
+        self.get_vertex(id)?;
+        if cost != cost {
+            return Err(GraphError::InvalidCost(cost.to_f64()));
+        }
+        if self.entry_costs.len() <= id {
+            self.entry_costs.resize(self.vertices.len(), W::ZERO);
+        }
+        self.entry_costs[id] = cost;
+        self.touch();
+        Ok(())
+    }
+
+    /// Returns the cost of entering the vertex with the given id.
+    ///
+    /// Fails if the vertex is unknown.
+    pub fn get_entry_cost(&self, id: usize) -> Result<W, GraphError> {
+        self.get_vertex(id)?;
+        Ok(self.entry_cost(id))
+    }
+
+    /// Cost of entering the vertex with the given id, which need not exist.
+    pub(crate) fn entry_cost(&self, id: usize) -> W {
+        self.entry_costs.get(id).copied().unwrap_or(W::ZERO)
+    }
+
+    /// Gives each vertex `new` the entry cost of the vertex `old` of `other`
+    /// it is a copy of, for every `(new, old)` pair.
+    pub(crate) fn copy_entry_costs<I>(&mut self, other: &Graph<T, W, E>, copies: I)
+    where
+        I: IntoIterator<Item = (usize, usize)>,
+    {
+        if other.entry_costs.is_empty() {
+            return;
+        }
+        self.entry_costs.resize(self.vertices.len(), W::ZERO);
+        for (new, old) in copies {
+            self.entry_costs[new] = other.entry_cost(old);
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use alloc::string::String;
+use core::error::Error;
+use core::fmt::{Display, Formatter, Result};
+
+/// Errors returned by [`Graph`](crate::Graph) operations.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GraphError {
+    /// No vertex with the given id exists in the graph.
+    UnknownVertex(usize),
+    /// No vertex with the given key exists in a [`KeyedGraph`](crate::KeyedGraph).
+    UnknownKey,
+    /// No edge from `from` to `to` exists in the graph.
+    UnknownEdge { from: usize, to: usize },
+    /// No edge with the given id exists in the graph.
+    UnknownEdgeId(usize),
+    /// An edge from `from` to `to` already exists and the graph's insertion
+    /// policy rejects duplicates.
+    DuplicateEdge { from: usize, to: usize },
+    /// An edge from the vertex to itself was rejected by the graph's
+    /// insertion policy.
+    SelfLoop(usize),
+    /// The target is not reachable from the source.
+    NoPathFound { from: usize, to: usize },
+    /// The target cannot be reached from the source along two disjoint
+    /// paths.
+    NoDisjointPaths { from: usize, to: usize },
+    /// None of the requested targets is reachable from the source.
+    NoTargetReachable { from: usize },
+    /// The target cannot be reached from the source within the given cost.
+    NoPathWithin(f64),
+    /// An edge cost was rejected, e.g. because it is NaN.
+    InvalidCost(f64),
+    /// The search was cancelled or ran out of its budget before finishing.
+    Cancelled,
+    /// A cycle with negative total cost makes shortest paths undefined.
+    NegativeCycle,
+    /// The graph has a cycle where an acyclic graph is required.
+    Cyclic,
+    /// Input in a textual graph format could not be parsed.
+    Parse { line: usize, message: String },
+    /// Reading or writing graph data failed.
+    Io(String),
+}
+
+impl Display for GraphError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            GraphError::UnknownVertex(id) => write!(f, "There is no vertex with id {}", id),
+            GraphError::UnknownKey => write!(f, "There is no vertex with the given key"),
+            GraphError::UnknownEdge { from, to } => {
+                write!(f, "There is no edge from vertex {} to vertex {}", from, to)
+            }
+            GraphError::UnknownEdgeId(id) => write!(f, "There is no edge with id {}", id),
+            GraphError::DuplicateEdge { from, to } => write!(
+                f,
+                "There is already an edge from vertex {} to vertex {}",
+                from, to
+            ),
+            GraphError::SelfLoop(id) => write!(f, "Vertex {} cannot have an edge to itself", id),
+            GraphError::NoPathFound { from, to } => {
+                write!(f, "There is no path from vertex {} to vertex {}", from, to)
+            }
+            GraphError::NoDisjointPaths { from, to } => write!(
+                f,
+                "There are no two disjoint paths from vertex {} to vertex {}",
+                from, to
+            ),
+            GraphError::NoTargetReachable { from } => {
+                write!(f, "None of the targets is reachable from vertex {}", from)
+            }
+            GraphError::NoPathWithin(budget) => {
+                write!(f, "There is no path within a cost of {}", budget)
+            }
+            GraphError::InvalidCost(cost) => write!(f, "Invalid edge cost {}", cost),
+            GraphError::Cancelled => write!(f, "The search was cancelled"),
+            GraphError::NegativeCycle => write!(f, "The graph contains a negative cycle"),
+            GraphError::Cyclic => write!(f, "The graph contains a cycle"),
+            GraphError::Parse { line, message } => {
+                write!(f, "Parse error on line {}: {}", line, message)
+            }
+            GraphError::Io(message) => write!(f, "I/O error: {}", message),
+        }
+    }
+}
+
+impl Error for GraphError {}
@@ -0,0 +1,163 @@
+use std::ptr;
+
+use crate::{Graph, GraphError};
+
+/// A graph whose vertices are plain ids and whose edges cost a `double`,
+/// opaque to C.
+///
+/// The functions taking it are declared in `include/dijkstra.h`, which is
+/// regenerated with `cbindgen --config cbindgen.toml --output
+/// include/dijkstra.h` after changing this module. Link against the output
+/// of `cargo rustc --lib --release --features ffi --crate-type staticlib`.
+pub struct DijkstraGraph {
+    graph: Graph<()>,
+}
+
+/// Outcome of a call that can fail.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DijkstraStatus {
+    Ok = 0,
+    /// A pointer argument was null.
+    NullPointer,
+    /// A vertex id does not exist.
+    UnknownVertex,
+    /// A cost is NaN.
+    InvalidCost,
+    /// The target cannot be reached from the source.
+    NoPath,
+    /// The output buffer cannot hold the path, whose length was written to
+    /// `out_len`.
+    BufferTooSmall,
+}
+
+impl From<GraphError> for DijkstraStatus {
+    fn from(error: GraphError) -> Self {
+        match error {
+            GraphError::UnknownVertex(_) => DijkstraStatus::UnknownVertex,
+            GraphError::NoPathFound { .. } => DijkstraStatus::NoPath,
+            // No other error can come from the calls made here.
+            _ => DijkstraStatus::InvalidCost,
+        }
+    }
+}
+
+/// Creates an empty graph, to be released with [`dijkstra_graph_free`].
+#[no_mangle]
+pub extern "C" fn dijkstra_graph_new() -> *mut DijkstraGraph {
+    Box::into_raw(Box::new(DijkstraGraph {
+        graph: Graph::new(),
+    }))
+}
+
+/// Releases a graph. Does nothing if `graph` is null.
+///
+/// # Safety
+///
+/// `graph` must be null or come from [`dijkstra_graph_new`] and not have been
+/// released already.
+#[no_mangle]
+pub unsafe extern "C" fn dijkstra_graph_free(graph: *mut DijkstraGraph) {
+    if !graph.is_null() {
+        drop(Box::from_raw(graph));
+    }
+}
+
+/// Adds a vertex and returns its id.
+///
+/// # Safety
+///
+/// `graph` must be a live graph from [`dijkstra_graph_new`].
+#[no_mangle]
+pub unsafe extern "C" fn dijkstra_graph_add_vertex(graph: *mut DijkstraGraph) -> usize {
+    (*graph).graph.add_vertex(())
+}
+
+/// Adds a directed edge from `from` to `to`.
+///
+/// # Safety
+///
+/// `graph` must be null or a live graph from [`dijkstra_graph_new`].
+#[no_mangle]
+pub unsafe extern "C" fn dijkstra_graph_add_edge(
+    graph: *mut DijkstraGraph,
+    from: usize,
+    to: usize,
+    cost: f64,
+) -> DijkstraStatus {
+    let Some(graph) = graph.as_mut() else {
+        return DijkstraStatus::NullPointer;
+    };
+    status(graph.graph.add_edge(from, to, cost))
+}
+
+/// Adds an edge between `a` and `b` that can be traversed in both
+/// directions.
+///
+/// # Safety
+///
+/// `graph` must be null or a live graph from [`dijkstra_graph_new`].
+#[no_mangle]
+pub unsafe extern "C" fn dijkstra_graph_add_edge_undirected(
+    graph: *mut DijkstraGraph,
+    a: usize,
+    b: usize,
+    cost: f64,
+) -> DijkstraStatus {
+    let Some(graph) = graph.as_mut() else {
+        return DijkstraStatus::NullPointer;
+    };
+    status(graph.graph.add_edge_undirected(a, b, cost))
+}
+
+/// Finds the cheapest path from `from` to `to`, writes the ids along it to
+/// `out_ids`, which has room for `capacity` ids, their number to `out_len`
+/// and its cost to `out_cost`.
+///
+/// If the path is longer than `capacity`, writes only its length and returns
+/// [`DijkstraStatus::BufferTooSmall`], so the call can be repeated with a
+/// larger buffer. `out_ids` may be null when `capacity` is 0.
+///
+/// # Safety
+///
+/// `graph` must be null or a live graph from [`dijkstra_graph_new`],
+/// `out_ids` must be valid for writing `capacity` ids, and `out_len` and
+/// `out_cost` must be null or valid for writing.
+#[no_mangle]
+pub unsafe extern "C" fn dijkstra_graph_shortest_path(
+    graph: *const DijkstraGraph,
+    from: usize,
+    to: usize,
+    out_ids: *mut usize,
+    capacity: usize,
+    out_len: *mut usize,
+    out_cost: *mut f64,
+) -> DijkstraStatus {
+    let Some(graph) = graph.as_ref() else {
+        return DijkstraStatus::NullPointer;
+    };
+    if out_len.is_null() || out_cost.is_null() || (out_ids.is_null() && capacity > 0) {
+        return DijkstraStatus::NullPointer;
+    }
+    let path = match graph.graph.get_shortest_path(from, to) {
+        Ok(path) => path,
+        Err(error) => return error.into(),
+    };
+
+    *out_len = path.len();
+    *out_cost = path.cost();
+    if path.len() > capacity {
+        return DijkstraStatus::BufferTooSmall;
+    }
+    for (index, vertex) in path.iter().enumerate() {
+        ptr::write(out_ids.add(index), vertex.id);
+    }
+    DijkstraStatus::Ok
+}
+
+fn status(result: Result<(), GraphError>) -> DijkstraStatus {
+    match result {
+        Ok(()) => DijkstraStatus::Ok,
+        Err(error) => error.into(),
+    }
+}
@@ -0,0 +1,261 @@
+use std::collections::VecDeque;
+
+use crate::{Edge, Graph, GraphError};
+
+/// A maximum flow between two vertices together with a minimum cut, which
+/// the flow saturates.
+///
+/// Computed by [`Graph::max_flow`].
+#[derive(Debug, Clone)]
+pub struct MaxFlow {
+    value: f64,
+    flows: Vec<(usize, usize, f64)>,
+    source_side: Vec<usize>,
+    cut: Vec<(usize, usize)>,
+}
+
+impl MaxFlow {
+    /// Returns the total flow from the source to the sink.
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Returns the flow through each edge that carries any, as
+    /// `(from, to, flow)` in the direction of the flow. Parallel edges are
+    /// listed separately.
+    pub fn flows(&self) -> &[(usize, usize, f64)] {
+        &self.flows
+    }
+
+    /// Returns the ids on the source's side of the minimum cut, in ascending
+    /// order.
+    pub fn source_side(&self) -> &[usize] {
+        &self.source_side
+    }
+
+    /// Returns the edges of the minimum cut, i.e. those leading from the
+    /// source's side to the sink's, as `(from, to)`, with parallel edges
+    /// listed separately. Their capacities add up to the flow's value.
+    pub fn min_cut(&self) -> &[(usize, usize)] {
+        &self.cut
+    }
+}
+
+/// An edge of the residual network, paired with the one undoing it.
+struct Arc {
+    to: usize,
+    residual: f64,
+    reverse: usize,
+}
+
+impl<T> Graph<T>
+where
+    T: Clone,
+{
+    /// Gives every edge that can be traversed from `from` to `to` a
+    /// capacity, including parallel edges and undirected edges between the
+    /// two. Searches keep using the edges' costs.
+    ///
+    /// Fails if either vertex is unknown, there is no such edge, or the
+    /// capacity is NaN, infinite or negative.
+    pub fn set_edge_capacity(
+        &mut self,
+        from: usize,
+        to: usize,
+        capacity: f64,
+    ) -> Result<(), GraphError> {
+        self.get_vertex(from)?;
+        self.get_vertex(to)?;
+        if !capacity.is_finite() || capacity < 0.0 {
+            return Err(GraphError::InvalidCost(capacity));
+        }
+
+        let mut updated = false;
+        for edge in &mut self.adjacency[from] {
+            if edge.to == to {
+                edge.capacity = Some(capacity);
+                updated = true;
+            }
+        }
+        for edge in &mut self.adjacency[to] {
+            if edge.undirected && edge.to == from {
+                edge.capacity = Some(capacity);
+                updated = true;
+            }
+        }
+        for edge in &mut self.reverse_adjacency[to] {
+            if edge.to == from {
+                edge.capacity = Some(capacity);
+            }
+        }
+        for edge in &mut self.reverse_adjacency[from] {
+            if edge.undirected && edge.to == to {
+                edge.capacity = Some(capacity);
+            }
+        }
+
+        if !updated {
+            return Err(GraphError::UnknownEdge { from, to });
+        }
+        Ok(())
+    }
+
+    /// Returns a maximum flow from `source` to `sink` and a minimum cut
+    /// between them, using Dinic's algorithm. Undirected edges carry flow in
+    /// either direction, up to their capacity.
+    ///
+    /// Edges without a capacity use their cost as capacity, except that
+    /// edges of infinite cost count as blocked, as they do for searches.
+    ///
+    /// Fails if either vertex is unknown or a capacity taken from a cost is
+    /// negative.
+    pub fn max_flow(&self, source: usize, sink: usize) -> Result<MaxFlow, GraphError> {
+        self.get_vertex(source)?;
+        self.get_vertex(sink)?;
+
+        // Each edge becomes an arc with its capacity, paired with an arc back
+        // that has capacity only if the edge is undirected.
+        let slots = self.vertices.len();
+        let mut arcs: Vec<Arc> = Vec::new();
+        let mut arcs_of: Vec<Vec<usize>> = vec![Vec::new(); slots];
+        let mut edges: Vec<(usize, &Edge, f64)> = Vec::new();
+        for vertex in self.vertices.iter().flatten() {
+            for edge in &self.adjacency[vertex.id] {
+                let capacity = match edge.capacity {
+                    Some(capacity) => capacity,
+                    None if edge.cost == f64::INFINITY => 0.0,
+                    None if edge.cost < 0.0 => return Err(GraphError::InvalidCost(edge.cost)),
+                    None => edge.cost,
+                };
+                let back = if edge.undirected { capacity } else { 0.0 };
+                let forward = arcs.len();
+                arcs.push(Arc {
+                    to: edge.to,
+                    residual: capacity,
+                    reverse: forward + 1,
+                });
+                arcs.push(Arc {
+                    to: vertex.id,
+                    residual: back,
+                    reverse: forward,
+                });
+                arcs_of[vertex.id].push(forward);
+                arcs_of[edge.to].push(forward + 1);
+                edges.push((vertex.id, edge, capacity));
+            }
+        }
+
+        let mut value = 0.0;
+        if source != sink {
+            while let Some(mut level) = levels(&arcs, &arcs_of, source, sink) {
+                value += blocking_flow(&mut arcs, &arcs_of, &mut level, source, sink);
+            }
+        }
+
+        let mut on_source_side = vec![false; slots];
+        on_source_side[source] = true;
+        let mut stack = vec![source];
+        while let Some(current) = stack.pop() {
+            for arc in arcs_of[current].iter().map(|arc| &arcs[*arc]) {
+                if arc.residual > 0.0 && !on_source_side[arc.to] {
+                    on_source_side[arc.to] = true;
+                    stack.push(arc.to);
+                }
+            }
+        }
+
+        let mut flows = Vec::new();
+        let mut cut = Vec::new();
+        for (index, (from, edge, capacity)) in edges.into_iter().enumerate() {
+            let flow = capacity - arcs[2 * index].residual;
+            if flow > 0.0 {
+                flows.push((from, edge.to, flow));
+            } else if flow < 0.0 {
+                flows.push((edge.to, from, -flow));
+            }
+            if on_source_side[from] && !on_source_side[edge.to] {
+                cut.push((from, edge.to));
+            } else if edge.undirected && on_source_side[edge.to] && !on_source_side[from] {
+                cut.push((edge.to, from));
+            }
+        }
+
+        Ok(MaxFlow {
+            value,
+            flows,
+            source_side: (0..slots).filter(|id| on_source_side[*id]).collect(),
+            cut,
+        })
+    }
+}
+
+/// Returns the number of residual arcs on the fewest-arc path from `source`
+/// to each vertex, or `None` once `sink` cannot be reached.
+fn levels(arcs: &[Arc], arcs_of: &[Vec<usize>], source: usize, sink: usize) -> Option<Vec<usize>> {
+    let mut level = vec![usize::MAX; arcs_of.len()];
+    level[source] = 0;
+    let mut queue = VecDeque::from([source]);
+    while let Some(current) = queue.pop_front() {
+        for arc in arcs_of[current].iter().map(|arc| &arcs[*arc]) {
+            if arc.residual > 0.0 && level[arc.to] == usize::MAX {
+                level[arc.to] = level[current] + 1;
+                queue.push_back(arc.to);
+            }
+        }
+    }
+    (level[sink] != usize::MAX).then_some(level)
+}
+
+/// Pushes flow along paths that go one level further with each arc until
+/// every such path is saturated, and returns how much was pushed.
+fn blocking_flow(
+    arcs: &mut [Arc],
+    arcs_of: &[Vec<usize>],
+    level: &mut [usize],
+    source: usize,
+    sink: usize,
+) -> f64 {
+    // Arcs before `next_arc` of a vertex are known to lead nowhere.
+    let mut next_arc = vec![0; arcs_of.len()];
+    let mut path: Vec<usize> = Vec::new();
+    let mut pushed = 0.0;
+    let mut current = source;
+    loop {
+        if current == sink {
+            let bottleneck = path
+                .iter()
+                .map(|arc| arcs[*arc].residual)
+                .fold(f64::INFINITY, f64::min);
+            for arc in path.drain(..) {
+                arcs[arc].residual -= bottleneck;
+                let reverse = arcs[arc].reverse;
+                arcs[reverse].residual += bottleneck;
+            }
+            pushed += bottleneck;
+            current = source;
+            continue;
+        }
+
+        let own = &arcs_of[current];
+        while next_arc[current] < own.len() {
+            let arc = &arcs[own[next_arc[current]]];
+            if arc.residual > 0.0 && level[arc.to] == level[current] + 1 {
+                break;
+            }
+            next_arc[current] += 1;
+        }
+        if let Some(arc) = own.get(next_arc[current]) {
+            path.push(*arc);
+            current = arcs[*arc].to;
+            continue;
+        }
+
+        // A dead end, which no later path needs to visit again.
+        level[current] = usize::MAX;
+        let Some(arc) = path.pop() else {
+            return pushed;
+        };
+        current = arcs[arcs[arc].reverse].to;
+        next_arc[current] += 1;
+    }
+}
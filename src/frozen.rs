@@ -0,0 +1,178 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::graph::CompactId;
+use crate::indexed_heap::IndexedHeap;
+use crate::{Graph, GraphError, GraphStorage, Path, Vertex, Weight};
+
+/// Marks a vertex without predecessor.
+const NO_VERTEX: CompactId = CompactId::MAX;
+
+/// An immutable copy of a graph laid out for fast queries: the edges out of
+/// all vertices are stored back to back in flat arrays, in compressed sparse
+/// row form, instead of in one allocation per vertex.
+///
+/// Built with [`Graph::freeze`]. Later changes to the source graph are not
+/// reflected. Ids are stored as `u32` internally but `usize` in the API, as
+/// in [`Graph`].
+#[derive(Debug, Clone)]
+pub struct FrozenGraph<T, W = f64> {
+    vertices: Vec<Option<Vertex<T>>>,
+    /// Where the edges out of each vertex start in `targets` and `costs`,
+    /// with a final entry for the end of the last vertex's edges.
+    offsets: Vec<usize>,
+    targets: Vec<CompactId>,
+    costs: Vec<W>,
+}
+
+impl<T, W> FrozenGraph<T, W>
+where
+    T: Clone,
+    W: Weight,
+{
+    /// Returns the vertex with the given id.
+    ///
+    /// Fails if no such vertex exists.
+    pub fn get_vertex(&self, id: usize) -> Result<&Vertex<T>, GraphError> {
+        self.vertices
+            .get(id)
+            .and_then(Option::as_ref)
+            .ok_or(GraphError::UnknownVertex(id))
+    }
+
+    /// Returns the number of edges that can be traversed, counting undirected
+    /// edges once per direction.
+    pub fn edge_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Returns the cheapest path from `from` to `to`, as
+    /// [`Graph::get_shortest_path`] would.
+    ///
+    /// Fails if either vertex is unknown or `to` is not reachable from `from`.
+    pub fn get_shortest_path(&self, from: usize, to: usize) -> Result<Path<T, W>, GraphError> {
+        span!(DEBUG, "FrozenGraph::get_shortest_path", from, to);
+        self.get_vertex(from)?;
+        self.get_vertex(to)?;
+
+        let slots = self.vertices.len();
+        let mut dist = vec![W::INFINITY; slots];
+        let mut prev = vec![NO_VERTEX; slots];
+        // Cost of the edge each vertex was reached by.
+        let mut step = vec![W::ZERO; slots];
+        let mut settled = vec![false; slots];
+        let mut queue = IndexedHeap::new(slots);
+        dist[from] = W::ZERO;
+        queue.push_or_decrease(from, W::ZERO);
+
+        while let Some((cost, current)) = queue.pop() {
+            if current == to {
+                break;
+            }
+            settled[current] = true;
+            let edges = self.offsets[current]..self.offsets[current + 1];
+            for (&next, &edge_cost) in self.targets[edges.clone()].iter().zip(&self.costs[edges]) {
+                let next = next as usize;
+                if settled[next] {
+                    continue;
+                }
+                let new_dist = cost.saturating_add(edge_cost);
+                if new_dist < dist[next] {
+                    dist[next] = new_dist;
+                    prev[next] = current as CompactId;
+                    step[next] = edge_cost;
+                    queue.push_or_decrease(next, new_dist);
+                }
+            }
+        }
+        event!(
+            DEBUG,
+            cost = dist[to].to_f64(),
+            settled = settled.iter().filter(|settled| **settled).count(),
+            "search finished"
+        );
+        if dist[to] == W::INFINITY {
+            return Err(GraphError::NoPathFound { from, to });
+        }
+
+        let mut ids = vec![to];
+        while prev[*ids.last().unwrap()] != NO_VERTEX {
+            ids.push(prev[*ids.last().unwrap()] as usize);
+        }
+        ids.reverse();
+        let costs = ids[1..].iter().map(|id| step[*id]).collect();
+        let path = ids
+            .iter()
+            .map(|id| self.vertices[*id].clone().unwrap())
+            .collect();
+        Ok(Path::new(path, costs, dist[to]))
+    }
+}
+
+impl<T, W> GraphStorage<W> for FrozenGraph<T, W>
+where
+    T: Clone,
+    W: Weight,
+{
+    fn id_bound(&self) -> usize {
+        self.vertices.len()
+    }
+
+    fn contains_vertex(&self, id: usize) -> bool {
+        self.get_vertex(id).is_ok()
+    }
+
+    fn edges_from(&self, id: usize) -> impl Iterator<Item = (usize, W)> + '_ {
+        let edges = if self.contains_vertex(id) {
+            self.offsets[id]..self.offsets[id + 1]
+        } else {
+            0..0
+        };
+        self.targets[edges.clone()]
+            .iter()
+            .zip(&self.costs[edges])
+            .map(|(&next, &cost)| (next as usize, cost))
+    }
+}
+
+impl<T, W> Graph<T, W>
+where
+    T: Clone,
+    W: Weight,
+{
+    /// Copies the graph into a [`FrozenGraph`], which answers queries faster
+    /// on large graphs but cannot be changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the graph has `u32::MAX` or more vertex ids, counting
+    /// removed ones.
+    pub fn freeze(&self) -> FrozenGraph<T, W> {
+        span!(INFO, "Graph::freeze");
+        assert!(
+            self.vertices.len() < NO_VERTEX as usize,
+            "too many vertices to freeze"
+        );
+        let mut offsets = Vec::with_capacity(self.vertices.len() + 1);
+        let mut targets = Vec::new();
+        let mut costs = Vec::new();
+        for id in 0..self.vertices.len() {
+            offsets.push(targets.len());
+            if self.vertices[id].is_none() {
+                continue;
+            }
+            for edge in self.outgoing(id) {
+                targets.push(edge.to as CompactId);
+                costs.push(edge.cost);
+            }
+        }
+        offsets.push(targets.len());
+
+        FrozenGraph {
+            vertices: self.vertices.clone(),
+            offsets,
+            targets,
+            costs,
+        }
+    }
+}
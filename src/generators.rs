@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+
+use crate::{Connectivity, Graph, GraphError};
+
+/// A small, fast pseudo-random number generator (SplitMix64) for the graph
+/// generators, so that the same seed always builds the same graph.
+///
+/// Not suitable for cryptography.
+#[derive(Debug, Clone)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    /// Creates a generator whose output is fully determined by `seed`.
+    pub fn new(seed: u64) -> Self {
+        SeededRng { state: seed }
+    }
+
+    /// Returns the next 64 random bits.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number drawn uniformly from `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns an integer drawn uniformly from `0..bound`, which must not be
+    /// zero.
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next_f64() * bound as f64) as usize
+    }
+}
+
+impl Graph<usize> {
+    /// Builds an Erdős–Rényi random graph: `vertices` vertices valued by
+    /// their id, with a directed edge between each ordered pair of distinct
+    /// vertices drawn independently with the given `probability`. Each edge
+    /// costs whatever `weight` draws from `rng`.
+    ///
+    /// Takes time proportional to the number of edges rather than of pairs,
+    /// so large sparse graphs are cheap to build.
+    ///
+    /// Fails if `weight` returns NaN.
+    pub fn erdos_renyi<F>(
+        vertices: usize,
+        probability: f64,
+        rng: &mut SeededRng,
+        mut weight: F,
+    ) -> Result<Self, GraphError>
+    where
+        F: FnMut(&mut SeededRng) -> f64,
+    {
+        let mut graph = Graph::new();
+        for id in 0..vertices {
+            graph.add_vertex(id);
+        }
+        if probability <= 0.0 || vertices < 2 {
+            return Ok(graph);
+        }
+
+        // Jumps straight to the next pair that gets an edge, over a gap
+        // whose length is geometrically distributed.
+        let pairs = vertices * (vertices - 1);
+        let log_miss = (1.0 - probability.min(1.0)).ln();
+        let mut pair = 0;
+        loop {
+            if log_miss != f64::NEG_INFINITY {
+                let gap = ((1.0 - rng.next_f64()).ln() / log_miss).floor();
+                if gap >= (pairs - pair) as f64 {
+                    break;
+                }
+                pair += gap as usize;
+            }
+            if pair >= pairs {
+                break;
+            }
+            // Each vertex has `vertices - 1` pairs, skipping itself.
+            let from = pair / (vertices - 1);
+            let mut to = pair % (vertices - 1);
+            if to >= from {
+                to += 1;
+            }
+            graph.add_edge(from, to, weight(rng))?;
+            pair += 1;
+        }
+        Ok(graph)
+    }
+
+    /// Builds a Barabási–Albert scale-free graph: starting from
+    /// `edges_per_vertex` unconnected vertices, each new vertex is linked by
+    /// undirected edges to that many distinct earlier vertices, chosen with
+    /// probability proportional to how many edges they already have. Vertices
+    /// are valued by their id and each edge costs whatever `weight` draws from
+    /// `rng`.
+    ///
+    /// Fails if `weight` returns NaN.
+    pub fn barabasi_albert<F>(
+        vertices: usize,
+        edges_per_vertex: usize,
+        rng: &mut SeededRng,
+        mut weight: F,
+    ) -> Result<Self, GraphError>
+    where
+        F: FnMut(&mut SeededRng) -> f64,
+    {
+        let mut graph = Graph::new();
+        let start = edges_per_vertex.min(vertices);
+        for id in 0..start {
+            graph.add_vertex(id);
+        }
+
+        // Every endpoint of every edge so far, so that picking uniformly from
+        // it favours vertices by their degree.
+        let mut endpoints: Vec<usize> = Vec::new();
+        let mut targets: Vec<usize> = (0..start).collect();
+        for id in start..vertices {
+            graph.add_vertex(id);
+            for target in &targets {
+                graph.add_edge_undirected(id, *target, weight(rng))?;
+                endpoints.push(*target);
+                endpoints.push(id);
+            }
+            targets.clear();
+            while targets.len() < edges_per_vertex.min(id + 1) {
+                let target = endpoints[rng.below(endpoints.len())];
+                if !targets.contains(&target) {
+                    targets.push(target);
+                }
+            }
+        }
+        Ok(graph)
+    }
+}
+
+impl Graph<(f64, f64)> {
+    /// Builds a random geometric graph: `vertices` points drawn uniformly from
+    /// the unit square, valued by their `(x, y)` position, with an undirected
+    /// edge between every two points at most `radius` apart that costs their
+    /// distance.
+    ///
+    /// Buckets the points by cells of the radius, so only nearby points are
+    /// compared.
+    pub fn random_geometric(vertices: usize, radius: f64, rng: &mut SeededRng) -> Self {
+        let mut graph = Graph::new();
+        let cells = if radius > 0.0 {
+            ((1.0 / radius).floor() as usize).clamp(1, vertices.max(1))
+        } else {
+            1
+        };
+        let cell_of = |coordinate: f64| ((coordinate * cells as f64) as usize).min(cells - 1);
+        let mut buckets: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        for id in 0..vertices {
+            let point = (rng.next_f64(), rng.next_f64());
+            graph.add_vertex(point);
+            buckets
+                .entry((cell_of(point.0), cell_of(point.1)))
+                .or_default()
+                .push(id);
+        }
+
+        for id in 0..vertices {
+            let (x, y) = graph.vertex(id).value;
+            let (col, row) = (cell_of(x), cell_of(y));
+            for near_col in col.saturating_sub(1)..=(col + 1).min(cells - 1) {
+                for near_row in row.saturating_sub(1)..=(row + 1).min(cells - 1) {
+                    let Some(bucket) = buckets.get(&(near_col, near_row)) else {
+                        continue;
+                    };
+                    for other in bucket.iter().filter(|other| **other > id) {
+                        let (other_x, other_y) = graph.vertex(*other).value;
+                        let distance = (x - other_x).hypot(y - other_y);
+                        if distance <= radius {
+                            graph.add_edge_undirected(id, *other, distance).unwrap();
+                        }
+                    }
+                }
+            }
+        }
+        graph
+    }
+}
+
+impl Graph<(usize, usize)> {
+    /// Builds a `width` by `height` grid like [`Graph::from_grid`] with
+    /// [`Connectivity::FourWay`], where the cost of entering each cell is
+    /// whatever `weight` draws from `rng`.
+    ///
+    /// Fails if `weight` returns NaN or a negative cost.
+    pub fn random_grid<F>(
+        width: usize,
+        height: usize,
+        rng: &mut SeededRng,
+        mut weight: F,
+    ) -> Result<Self, GraphError>
+    where
+        F: FnMut(&mut SeededRng) -> f64,
+    {
+        let costs: Vec<Vec<f64>> = (0..height)
+            .map(|_| (0..width).map(|_| weight(rng)).collect())
+            .collect();
+        Graph::from_grid(&costs, Connectivity::FourWay)
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{Graph, GraphError, Heuristic};
+
+/// Mean radius of the Earth in metres.
+const EARTH_RADIUS: f64 = 6_371_008.8;
+
+/// A point on the Earth in degrees of latitude and longitude, for vertices
+/// of geographic graphs.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LatLon {
+    pub lat: f64,
+    pub lon: f64,
+}
+
+impl LatLon {
+    pub fn new(lat: f64, lon: f64) -> Self {
+        LatLon { lat, lon }
+    }
+
+    /// Returns the great-circle distance to `other` in metres, by the
+    /// haversine formula on a spherical Earth.
+    pub fn distance_to(&self, other: &LatLon) -> f64 {
+        let (lat, other_lat) = (self.lat.to_radians(), other.lat.to_radians());
+        let half_dlat = (other_lat - lat) / 2.0;
+        let half_dlon = (other.lon - self.lon).to_radians() / 2.0;
+        let a = half_dlat.sin().powi(2) + lat.cos() * other_lat.cos() * half_dlon.sin().powi(2);
+        2.0 * EARTH_RADIUS * a.sqrt().min(1.0).asin()
+    }
+
+    /// Returns the initial bearing of the great circle towards `other`, in
+    /// degrees clockwise from north in `0.0..360.0`.
+    pub fn bearing_to(&self, other: &LatLon) -> f64 {
+        let (lat, other_lat) = (self.lat.to_radians(), other.lat.to_radians());
+        let dlon = (other.lon - self.lon).to_radians();
+        let y = dlon.sin() * other_lat.cos();
+        let x = lat.cos() * other_lat.sin() - lat.sin() * other_lat.cos() * dlon.cos();
+        y.atan2(x).to_degrees().rem_euclid(360.0)
+    }
+}
+
+/// The great-circle distance to the target as an A* heuristic for graphs of
+/// [`LatLon`] vertices, in metres or as the time needed to cover it at a top
+/// speed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GreatCircle {
+    /// Metres covered per unit of cost.
+    speed: f64,
+}
+
+impl GreatCircle {
+    /// Estimates in metres, consistent when edges cost at least the distance
+    /// between their ends, as those added by [`Graph::add_geo_edge`] do.
+    pub fn distance() -> Self {
+        GreatCircle { speed: 1.0 }
+    }
+
+    /// Estimates the time needed at `max_speed` metres per unit of cost,
+    /// e.g. metres per second for edges costed in seconds. Consistent as long
+    /// as no edge is traversed faster than `max_speed`.
+    pub fn travel_time(max_speed: f64) -> Self {
+        GreatCircle { speed: max_speed }
+    }
+}
+
+impl Heuristic<LatLon> for GreatCircle {
+    fn estimate(&self, from: &LatLon, to: &LatLon) -> f64 {
+        from.distance_to(to) / self.speed
+    }
+}
+
+impl Graph<LatLon> {
+    /// Adds a directed edge from `from` to `to` that costs the great-circle
+    /// distance between them in metres.
+    ///
+    /// Fails if either vertex is unknown or the
+    /// [insertion policy](Graph::set_insertion_policy) rejects the edge.
+    pub fn add_geo_edge(&mut self, from: usize, to: usize) -> Result<(), GraphError> {
+        let cost = self
+            .get_vertex(from)?
+            .value
+            .distance_to(&self.get_vertex(to)?.value);
+        self.add_edge(from, to, cost)
+    }
+
+    /// Like [`Graph::add_geo_edge`], but for an edge that can be traversed in
+    /// both directions.
+    pub fn add_geo_edge_undirected(&mut self, a: usize, b: usize) -> Result<(), GraphError> {
+        let cost = self
+            .get_vertex(a)?
+            .value
+            .distance_to(&self.get_vertex(b)?.value);
+        self.add_edge_undirected(a, b, cost)
+    }
+}
@@ -0,0 +1,61 @@
+use crate::{EdgeGeometry, Graph, LatLon, Path};
+
+impl<E: EdgeGeometry> Path<LatLon, f64, E> {
+    /// Renders the path as a GeoJSON `Feature` with its
+    /// [geometry](Path::geometry) as a `LineString` and its total cost as the
+    /// `cost` property, e.g. for drawing it on a web map.
+    pub fn to_geojson(&self) -> String {
+        let coordinates: Vec<String> = self.geometry().iter().map(position).collect();
+        format!(
+            "{{\"type\":\"Feature\",\"geometry\":{{\"type\":\"LineString\",\"coordinates\":[{}]}},\"properties\":{{\"cost\":{}}}}}",
+            coordinates.join(","),
+            number(self.cost())
+        )
+    }
+}
+
+impl Graph<LatLon> {
+    /// Renders the graph as a GeoJSON `FeatureCollection`: a `Point` for each
+    /// vertex with its `id`, then a `LineString` for each edge with its
+    /// `from` and `to` ids, `cost` and whether it is `undirected`.
+    pub fn to_geojson(&self) -> String {
+        let mut features = Vec::new();
+        for vertex in self.vertices() {
+            features.push(format!(
+                "{{\"type\":\"Feature\",\"geometry\":{{\"type\":\"Point\",\"coordinates\":{}}},\"properties\":{{\"id\":{}}}}}",
+                position(&vertex.value),
+                vertex.id
+            ));
+        }
+        for (from, edge) in self.edges() {
+            features.push(format!(
+                "{{\"type\":\"Feature\",\"geometry\":{{\"type\":\"LineString\",\"coordinates\":[{},{}]}},\"properties\":{{\"from\":{},\"to\":{},\"cost\":{},\"undirected\":{}}}}}",
+                position(&self.vertex(from).value),
+                position(&self.vertex(edge.to).value),
+                from,
+                edge.to,
+                number(edge.cost),
+                edge.undirected
+            ));
+        }
+        format!(
+            "{{\"type\":\"FeatureCollection\",\"features\":[{}]}}",
+            features.join(",")
+        )
+    }
+}
+
+/// A GeoJSON position, which puts longitude first.
+fn position(point: &LatLon) -> String {
+    format!("[{},{}]", number(point.lon), number(point.lat))
+}
+
+/// Formats a number for JSON, which has no infinity or NaN, as `null` for
+/// those.
+fn number(value: f64) -> String {
+    if value.is_finite() {
+        value.to_string()
+    } else {
+        String::from("null")
+    }
+}
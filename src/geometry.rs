@@ -0,0 +1,124 @@
+use crate::{Graph, GraphError, LatLon, Path};
+
+/// Edge payloads that can carry the shape of a road or line between its two
+/// vertices, for [`Path::geometry`].
+///
+/// Implemented for `Vec<LatLon>` and `Option<Vec<LatLon>>` polylines, and
+/// for `()`, whose edges run straight. Payloads holding more than a shape
+/// can implement it to hand theirs out.
+pub trait EdgeGeometry {
+    /// Returns the points along the edge, including or leaving out its two
+    /// vertices and in either direction, or `None` if it runs straight.
+    fn polyline(&self) -> Option<&[LatLon]>;
+}
+
+impl EdgeGeometry for () {
+    fn polyline(&self) -> Option<&[LatLon]> {
+        None
+    }
+}
+
+impl EdgeGeometry for Vec<LatLon> {
+    fn polyline(&self) -> Option<&[LatLon]> {
+        Some(self)
+    }
+}
+
+impl EdgeGeometry for Option<Vec<LatLon>> {
+    fn polyline(&self) -> Option<&[LatLon]> {
+        self.as_deref()
+    }
+}
+
+impl<W, E: EdgeGeometry> Path<LatLon, W, E> {
+    /// Returns the shape of the path: its first vertex, then the polyline of
+    /// each step's edge, turned to run the way the path does, and the
+    /// vertex it leads to. Points repeated where segments meet appear once.
+    ///
+    /// Steps whose edges have no polyline, or paths without edge payloads,
+    /// run straight between their vertices.
+    pub fn geometry(&self) -> Vec<LatLon> {
+        let mut points: Vec<LatLon> = self.vertices().iter().take(1).map(|v| v.value).collect();
+        for step in 0..self.costs().len() {
+            for point in self.step_geometry(step) {
+                if points.last() != Some(&point) {
+                    points.push(point);
+                }
+            }
+        }
+        points
+    }
+
+    /// Returns the shape of the step into the vertex after `step`, from the
+    /// vertex it leaves to the one it enters.
+    pub(crate) fn step_geometry(&self, step: usize) -> Vec<LatLon> {
+        let from = self.vertices()[step].value;
+        let to = self.vertices()[step + 1].value;
+        let polyline = self
+            .data()
+            .get(step)
+            .and_then(EdgeGeometry::polyline)
+            .unwrap_or(&[]);
+        // A polyline drawn from the other end starts nearer `to`.
+        let turned = match (polyline.first(), polyline.last()) {
+            (Some(start), Some(end)) => {
+                start.distance_to(&to) + end.distance_to(&from)
+                    < start.distance_to(&from) + end.distance_to(&to)
+            }
+            _ => false,
+        };
+        let mut points = Vec::with_capacity(polyline.len() + 2);
+        points.push(from);
+        if turned {
+            points.extend(polyline.iter().rev());
+        } else {
+            points.extend(polyline);
+        }
+        points.push(to);
+        points.dedup();
+        points
+    }
+}
+
+impl<E: EdgeGeometry + Clone> Graph<LatLon, f64, E> {
+    /// Adds a directed edge from `from` to `to` carrying `data`, costing the
+    /// length in metres of its polyline from `from` to `to`, or the
+    /// great-circle distance between them if it has none.
+    ///
+    /// Fails if either vertex is unknown or the
+    /// [insertion policy](Graph::set_insertion_policy) rejects the edge.
+    pub fn add_geo_edge_with_data(
+        &mut self,
+        from: usize,
+        to: usize,
+        data: E,
+    ) -> Result<(), GraphError> {
+        let cost = self.polyline_length(from, to, &data)?;
+        self.add_edge_with_data(from, to, cost, data)
+    }
+
+    /// Like [`Graph::add_geo_edge_with_data`], but for an edge that can be
+    /// traversed in both directions.
+    pub fn add_geo_edge_undirected_with_data(
+        &mut self,
+        a: usize,
+        b: usize,
+        data: E,
+    ) -> Result<(), GraphError> {
+        let cost = self.polyline_length(a, b, &data)?;
+        self.add_edge_undirected_with_data(a, b, cost, data)
+    }
+
+    /// Length in metres from `from` through the points of `data`'s polyline
+    /// to `to`.
+    fn polyline_length(&self, from: usize, to: usize, data: &E) -> Result<f64, GraphError> {
+        let mut at = self.get_vertex(from)?.value;
+        let end = self.get_vertex(to)?.value;
+        let mut length = 0.0;
+        for point in data.polyline().unwrap_or(&[]).iter().chain([&end]) {
+            length += at.distance_to(point);
+            at = *point;
+        }
+        Ok(length)
+    }
+}
@@ -0,0 +1,653 @@
+use alloc::collections::BinaryHeap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+use core::ops::Range;
+#[cfg(target_has_atomic = "64")]
+use core::sync::atomic::AtomicU64 as AtomicRevision;
+#[cfg(not(target_has_atomic = "64"))]
+use core::sync::atomic::AtomicUsize as AtomicRevision;
+use core::sync::atomic::Ordering;
+
+use crate::indexed_heap::IndexedHeap;
+use crate::min_non_nan::MinNonNan;
+use crate::value_index::ValueIndex;
+use crate::{Edge, GraphError, InsertionPolicy, Path, SearchVisitor, Vertex, Weight};
+
+/// Vertex id as stored in per-vertex search state such as [`Predecessors`]
+/// and in the edge arrays of [`FrozenGraph`](crate::FrozenGraph), half the
+/// size of `usize` on 64-bit targets, which keeps searches on large graphs
+/// smaller and friendlier to the cache. Ids in the API stay `usize`.
+pub(crate) type CompactId = u32;
+
+/// Id of the predecessor of each vertex on the shortest path found to it,
+/// indexed by id.
+pub(crate) type Predecessors = Vec<Option<CompactId>>;
+
+/// Source of [`Graph::revision`] stamps, shared by all graphs so that no two
+/// graphs with different contents ever carry the same stamp.
+///
+/// Targets without 64-bit atomics, like many microcontrollers, count in
+/// `usize` instead.
+static NEXT_REVISION: AtomicRevision = AtomicRevision::new(0);
+
+#[allow(clippy::unnecessary_cast)]
+fn next_revision() -> u64 {
+    NEXT_REVISION.fetch_add(1, Ordering::Relaxed) as u64
+}
+
+/// A directed, weighted graph whose vertices carry a value of type `T` and
+/// whose edges cost a [`Weight`] of type `W` and carry a payload of type `E`,
+/// nothing by default.
+///
+/// Most algorithms beyond plain shortest paths work on `f64` costs only.
+///
+/// Vertex ids are never reused: an id kept after its vertex was removed
+/// fails with [`GraphError::UnknownVertex`] instead of referring to a newer
+/// vertex.
+pub struct Graph<T, W = f64, E = ()> {
+    /// Vertices indexed by id, with `None` left behind by removed vertices.
+    pub(crate) vertices: Vec<Option<Vertex<T>>>,
+    pub(crate) adjacency: Vec<Vec<Edge<W, E>>>,
+    /// Incoming edges of each vertex, pointing back at their source.
+    pub(crate) reverse_adjacency: Vec<Vec<Edge<W, E>>>,
+    /// Lookup from values to ids, if [`Graph::index_values`] was called.
+    pub(crate) value_index: Option<ValueIndex<T>>,
+    /// Stamp replaced on every change to the vertices, edges or costs.
+    pub(crate) revision: u64,
+    /// Checks applied to new edges.
+    pub(crate) policy: InsertionPolicy,
+    /// Room reserved for edges in the lists of each new vertex, from
+    /// [`Graph::with_capacity`].
+    pub(crate) edges_per_vertex: usize,
+    /// Id handed to the next edge added.
+    pub(crate) next_edge_id: usize,
+    /// Cost of entering each vertex, indexed by id, from
+    /// [`Graph::set_entry_cost`]. Vertices past its end cost nothing.
+    pub(crate) entry_costs: Vec<W>,
+}
+
+impl<T, W, E> Default for Graph<T, W, E>
+where
+    T: Clone,
+    W: Weight,
+    E: Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, W, E> Graph<T, W, E>
+where
+    T: Clone,
+    W: Weight,
+    E: Clone,
+{
+    /// Creates an empty graph.
+    pub fn new() -> Self {
+        Self {
+            vertices: Vec::new(),
+            adjacency: Vec::new(),
+            reverse_adjacency: Vec::new(),
+            value_index: None,
+            revision: next_revision(),
+            policy: InsertionPolicy::new(),
+            edges_per_vertex: 0,
+            next_edge_id: 0,
+            entry_costs: Vec::new(),
+        }
+    }
+
+    /// Creates an empty graph with room for `vertices` vertices and about
+    /// `edges` edges, so that building a large graph does not keep
+    /// reallocating.
+    pub fn with_capacity(vertices: usize, edges: usize) -> Self {
+        Self {
+            vertices: Vec::with_capacity(vertices),
+            adjacency: Vec::with_capacity(vertices),
+            reverse_adjacency: Vec::with_capacity(vertices),
+            edges_per_vertex: edges.div_ceil(vertices.max(1)),
+            ..Self::new()
+        }
+    }
+
+    /// Adds a vertex holding `value` and returns its id.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the graph already has `u32::MAX + 1` vertex ids, counting
+    /// removed ones, as searches store ids as `u32`.
+    pub fn add_vertex(&mut self, value: T) -> usize {
+        self.push_slot(Some(value))
+    }
+
+    /// Adds a vertex for each value, in order, and returns the range of their
+    /// ids.
+    pub fn extend_vertices<I>(&mut self, values: I) -> Range<usize>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let values = values.into_iter();
+        let start = self.vertices.len();
+        let additional = values.size_hint().0;
+        self.vertices.reserve(additional);
+        self.adjacency.reserve(additional);
+        self.reverse_adjacency.reserve(additional);
+        for value in values {
+            self.push_slot(Some(value));
+        }
+        start..self.vertices.len()
+    }
+
+    /// Appends a vertex slot, left empty as if removed when `value` is
+    /// `None`, and returns its id.
+    pub(crate) fn push_slot(&mut self, value: Option<T>) -> usize {
+        let id = self.vertices.len();
+        assert!(
+            CompactId::try_from(id).is_ok(),
+            "too many vertices for a graph"
+        );
+        if let (Some(index), Some(value)) = (&mut self.value_index, &value) {
+            index.insert(value, id);
+        }
+        self.vertices
+            .push(value.map(|value| Vertex::<T> { id, value }));
+        self.adjacency
+            .push(Vec::with_capacity(self.edges_per_vertex));
+        self.reverse_adjacency
+            .push(Vec::with_capacity(self.edges_per_vertex));
+        self.touch();
+        id
+    }
+
+    /// Returns the vertex with the given id.
+    pub fn get_vertex(&self, id: usize) -> Result<&Vertex<T>, GraphError> {
+        self.vertices
+            .get(id)
+            .and_then(Option::as_ref)
+            .ok_or(GraphError::UnknownVertex(id))
+    }
+
+    /// Returns the vertex with an id known to be live, e.g. an edge's target.
+    pub(crate) fn vertex(&self, id: usize) -> &Vertex<T> {
+        self.vertices[id].as_ref().unwrap()
+    }
+
+    /// Removes the vertex with the given id together with every edge from or
+    /// to it, and returns it. The ids of all other vertices stay valid and
+    /// the removed id is never handed out again.
+    pub fn remove_vertex(&mut self, id: usize) -> Result<Vertex<T>, GraphError> {
+        let vertex = self.get_vertex(id)?.id;
+
+        for edge in core::mem::take(&mut self.adjacency[vertex]) {
+            self.reverse_adjacency[edge.to].retain(|edge| edge.to != vertex);
+        }
+        for edge in core::mem::take(&mut self.reverse_adjacency[vertex]) {
+            self.adjacency[edge.to].retain(|edge| edge.to != vertex);
+        }
+
+        let removed = self.vertices[vertex].take().unwrap();
+        if let Some(index) = &mut self.value_index {
+            index.remove(&removed.value, vertex);
+        }
+        self.touch();
+        Ok(removed)
+    }
+
+    /// Adds a directed edge from `from` to `to` with the given cost and the
+    /// default payload.
+    ///
+    /// Fails if either vertex is unknown, the cost is NaN, or the
+    /// [insertion policy](Graph::set_insertion_policy) rejects the edge.
+    pub fn add_edge(&mut self, from: usize, to: usize, cost: W) -> Result<(), GraphError>
+    where
+        E: Default,
+    {
+        self.add_edge_with_data(from, to, cost, E::default())
+    }
+
+    /// Like [`Graph::add_edge`], but attaches `data` to the edge.
+    pub fn add_edge_with_data(
+        &mut self,
+        from: usize,
+        to: usize,
+        cost: W,
+        data: E,
+    ) -> Result<(), GraphError> {
+        Self::check_cost(cost)?;
+
+        self.get_vertex(to)?;
+        self.get_vertex(from)?;
+        if !self.admit_edge(from, to, cost, false)? {
+            return Ok(());
+        }
+        self.push_edge(from, Edge::with_data(to, cost, data));
+        self.touch();
+        Ok(())
+    }
+
+    /// Adds an edge between `a` and `b` that can be traversed in both
+    /// directions with the given cost and the default payload.
+    ///
+    /// Fails if either vertex is unknown, the cost is NaN, or the
+    /// [insertion policy](Graph::set_insertion_policy) rejects the edge.
+    pub fn add_edge_undirected(&mut self, a: usize, b: usize, cost: W) -> Result<(), GraphError>
+    where
+        E: Default,
+    {
+        self.add_edge_undirected_with_data(a, b, cost, E::default())
+    }
+
+    /// Like [`Graph::add_edge_undirected`], but attaches `data` to the edge.
+    pub fn add_edge_undirected_with_data(
+        &mut self,
+        a: usize,
+        b: usize,
+        cost: W,
+        data: E,
+    ) -> Result<(), GraphError> {
+        Self::check_cost(cost)?;
+
+        self.get_vertex(b)?;
+        self.get_vertex(a)?;
+        if !self.admit_edge(a, b, cost, true)? {
+            return Ok(());
+        }
+        self.push_edge(a, Edge::undirected_with_data(b, cost, data));
+        self.touch();
+        Ok(())
+    }
+
+    /// Adds a directed edge for each `(from, to, cost)`, as
+    /// [`Graph::add_edge`] would, but checks all of them before adding any and
+    /// reserves room for them at once.
+    ///
+    /// Fails, without adding any edge, if a vertex is unknown or a cost is
+    /// NaN. Under an [insertion policy](Graph::set_insertion_policy) other
+    /// than the default, the edges are added one by one, and those before
+    /// an edge the policy rejects stay added.
+    pub fn extend_edges<I>(&mut self, edges: I) -> Result<(), GraphError>
+    where
+        I: IntoIterator<Item = (usize, usize, W)>,
+        E: Default,
+    {
+        let edges: Vec<(usize, usize, W)> = edges.into_iter().collect();
+        for (from, to, cost) in &edges {
+            Self::check_cost(*cost)?;
+            self.get_vertex(*to)?;
+            self.get_vertex(*from)?;
+        }
+        if self.policy != InsertionPolicy::new() {
+            return edges
+                .into_iter()
+                .try_for_each(|(from, to, cost)| self.add_edge(from, to, cost));
+        }
+
+        let mut out_degree = vec![0; self.vertices.len()];
+        let mut in_degree = vec![0; self.vertices.len()];
+        for (from, to, _) in &edges {
+            out_degree[*from] += 1;
+            in_degree[*to] += 1;
+        }
+        for id in 0..self.vertices.len() {
+            self.adjacency[id].reserve(out_degree[id]);
+            self.reverse_adjacency[id].reserve(in_degree[id]);
+        }
+        for (from, to, cost) in edges {
+            self.push_edge(from, Edge::new(to, cost));
+        }
+        self.touch();
+        Ok(())
+    }
+
+    /// Stores `edge` out of `from` under the next edge id, together with its
+    /// mirror among the incoming edges of its target. Both vertices must
+    /// exist.
+    pub(crate) fn push_edge(&mut self, from: usize, mut edge: Edge<W, E>) {
+        edge.id = self.next_edge_id;
+        self.next_edge_id += 1;
+        self.reverse_adjacency[edge.to].push(Edge {
+            to: from,
+            ..edge.clone()
+        });
+        self.adjacency[from].push(edge);
+    }
+
+    /// Returns a copy of the graph with every directed edge pointing the
+    /// other way, e.g. for searching backwards from a target. Vertex ids and
+    /// undirected edges stay the same.
+    pub fn reversed(&self) -> Graph<T, W, E> {
+        // Every edge is stored at both ends, so the incoming lists are
+        // exactly the outgoing lists of the reversed graph.
+        Graph {
+            vertices: self.vertices.clone(),
+            adjacency: self.reverse_adjacency.clone(),
+            reverse_adjacency: self.adjacency.clone(),
+            value_index: self.value_index.clone(),
+            revision: next_revision(),
+            policy: self.policy,
+            edges_per_vertex: self.edges_per_vertex,
+            next_edge_id: self.next_edge_id,
+            entry_costs: self.entry_costs.clone(),
+        }
+    }
+
+    /// Returns a stamp that changes whenever a vertex or edge is added or
+    /// removed or an edge cost changes, e.g. to tell whether results
+    /// computed earlier are still valid. Different graphs never share a
+    /// stamp.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Replaces the revision stamp after a change.
+    pub(crate) fn touch(&mut self) {
+        self.revision = next_revision();
+    }
+
+    /// Rejects costs that do not compare equal to themselves, like NaN.
+    #[allow(clippy::eq_op)]
+    pub(crate) fn check_cost(cost: W) -> Result<(), GraphError> {
+        if cost != cost {
+            return Err(GraphError::InvalidCost(cost.to_f64()));
+        }
+        Ok(())
+    }
+
+    /// Edges that can be traversed out of `id`, including undirected edges
+    /// stored at their other endpoint.
+    pub(crate) fn outgoing(&self, id: usize) -> impl Iterator<Item = &Edge<W, E>> {
+        let undirected = self.reverse_adjacency[id]
+            .iter()
+            .filter(|edge| edge.undirected);
+        self.adjacency[id].iter().chain(undirected)
+    }
+
+    /// Edges that can be traversed into `id`, pointing back at their source.
+    pub(crate) fn incoming(&self, id: usize) -> impl Iterator<Item = &Edge<W, E>> {
+        let undirected = self.adjacency[id].iter().filter(|edge| edge.undirected);
+        self.reverse_adjacency[id].iter().chain(undirected)
+    }
+
+    /// Returns the cheapest path from `from` to `to`.
+    ///
+    /// Fails if either vertex is unknown or `to` is not reachable from `from`.
+    pub fn get_shortest_path(&self, from: usize, to: usize) -> Result<Path<T, W, E>, GraphError> {
+        span!(DEBUG, "Graph::get_shortest_path", from, to);
+        self.get_vertex(from)?;
+        self.get_vertex(to)?;
+
+        let (dist, prev) = self.search(from, Some(to), |_| W::ZERO);
+        if dist[to] == W::INFINITY {
+            return Err(GraphError::NoPathFound { from, to });
+        }
+
+        Ok(self.to_path(self.reconstruct_path(&prev, from, to), dist[to]))
+    }
+
+    /// Like [`Graph::get_shortest_path`], but gives up on every vertex
+    /// farther than `max_cost` from `from`, so that the search stays bounded
+    /// however large the graph is.
+    ///
+    /// Fails if either vertex is unknown or `max_cost` is NaN, and with
+    /// [`GraphError::NoPathWithin`] if `to` is farther than `max_cost` or not
+    /// reachable at all.
+    pub fn get_shortest_path_within(
+        &self,
+        from: usize,
+        to: usize,
+        max_cost: W,
+    ) -> Result<Path<T, W, E>, GraphError> {
+        span!(DEBUG, "Graph::get_shortest_path_within", from, to);
+        self.get_vertex(from)?;
+        self.get_vertex(to)?;
+        Self::check_cost(max_cost)?;
+
+        let is_end = |vertex: &Vertex<T>, distance| vertex.id == to || distance > max_cost;
+        let (dist, prev, end) =
+            self.search_until(&[(from, W::ZERO)], is_end, |_| W::ZERO, |_, _| true);
+        if end != Some(to) || dist[to] > max_cost {
+            return Err(GraphError::NoPathWithin(max_cost.to_f64()));
+        }
+
+        Ok(self.to_path(self.reconstruct_path(&prev, from, to), dist[to]))
+    }
+
+    /// Follows `prev` back from `end` to `start` and returns the vertices
+    /// along the way in order.
+    pub(crate) fn reconstruct_path(
+        &self,
+        prev: &[Option<CompactId>],
+        start: usize,
+        end: usize,
+    ) -> Vec<Vertex<T>> {
+        let mut path = Vec::new();
+        let mut at = end;
+        while at != start {
+            path.push(self.vertex(at).clone());
+            at = prev[at].unwrap() as usize;
+        }
+        path.push(self.vertex(at).clone());
+        path.reverse();
+        path
+    }
+
+    /// Best-first search from `start` until `end` is settled, or until the
+    /// frontier is exhausted if there is no `end`, ordering the frontier by
+    /// distance plus `heuristic`. A zero heuristic is Dijkstra.
+    pub(crate) fn search<H>(
+        &self,
+        start: usize,
+        end: Option<usize>,
+        heuristic: H,
+    ) -> (Vec<W>, Predecessors)
+    where
+        H: Fn(&Vertex<T>) -> W,
+    {
+        self.search_filtered(start, end, heuristic, |_, _| true)
+    }
+
+    /// Like [`Graph::search`], but only relaxes edges accepted by `filter`,
+    /// which receives the edge's source vertex and the edge itself.
+    pub(crate) fn search_filtered<H, F>(
+        &self,
+        start: usize,
+        end: Option<usize>,
+        heuristic: H,
+        filter: F,
+    ) -> (Vec<W>, Predecessors)
+    where
+        H: Fn(&Vertex<T>) -> W,
+        F: Fn(&Vertex<T>, &Edge<W, E>) -> bool,
+    {
+        let is_end = |vertex: &Vertex<T>, _| end == Some(vertex.id);
+        let (dist, prev, _) = self.search_until(&[(start, W::ZERO)], is_end, heuristic, filter);
+        (dist, prev)
+    }
+
+    /// Cost of the cheapest path from every vertex to `target`, found by
+    /// searching the edges backwards.
+    pub(crate) fn distances_to(&self, target: usize) -> Vec<W> {
+        self.search_backward(target).0
+    }
+
+    /// Searches the edges backwards from `target` and returns the cost of the
+    /// cheapest path from every vertex to it, along with the vertex each one
+    /// continues to on that path.
+    pub(crate) fn search_backward(&self, target: usize) -> (Vec<W>, Predecessors) {
+        let mut dist = vec![W::INFINITY; self.vertices.len()];
+        let mut next: Predecessors = vec![None; self.vertices.len()];
+        let mut queue = BinaryHeap::new();
+        dist[target] = W::ZERO;
+        queue.push((MinNonNan(W::ZERO), target));
+
+        while let Some((MinNonNan(cost), current)) = queue.pop() {
+            if cost > dist[current] {
+                continue;
+            }
+            let entry = self.entry_cost(current);
+            for edge in self.incoming(current) {
+                let new_dist = cost.saturating_add(edge.cost).saturating_add(entry);
+                if new_dist < dist[edge.to] {
+                    dist[edge.to] = new_dist;
+                    next[edge.to] = Some(current as CompactId);
+                    queue.push((MinNonNan(new_dist), edge.to));
+                }
+            }
+        }
+        (dist, next)
+    }
+
+    /// Core of all best-first searches over edge costs, plus the
+    /// [entry cost](Graph::set_entry_cost) of each vertex reached. Seeds the frontier
+    /// with each source id at its initial cost, stops at the first settled
+    /// vertex accepted by `is_goal`, which also receives its distance, and
+    /// returns its id alongside `dist` and `prev`.
+    pub(crate) fn search_until<G, H, F>(
+        &self,
+        sources: &[(usize, W)],
+        is_goal: G,
+        heuristic: H,
+        filter: F,
+    ) -> (Vec<W>, Predecessors, Option<usize>)
+    where
+        G: Fn(&Vertex<T>, W) -> bool,
+        H: Fn(&Vertex<T>) -> W,
+        F: Fn(&Vertex<T>, &Edge<W, E>) -> bool,
+    {
+        self.search_costed(sources, is_goal, heuristic, filter, |_, edge, _| edge.cost)
+    }
+
+    /// Like [`Graph::search_until`], but takes the cost of traversing an edge
+    /// from `cost`, which receives the edge's source vertex, the edge and the
+    /// distance at which the source was settled.
+    pub(crate) fn search_costed<G, H, F, C>(
+        &self,
+        sources: &[(usize, W)],
+        is_goal: G,
+        heuristic: H,
+        filter: F,
+        cost: C,
+    ) -> (Vec<W>, Predecessors, Option<usize>)
+    where
+        G: Fn(&Vertex<T>, W) -> bool,
+        H: Fn(&Vertex<T>) -> W,
+        F: Fn(&Vertex<T>, &Edge<W, E>) -> bool,
+        C: Fn(&Vertex<T>, &Edge<W, E>, W) -> W,
+    {
+        self.search_visited(sources, is_goal, heuristic, filter, cost, &mut ())
+    }
+
+    /// Like [`Graph::search_costed`], but reports every settled vertex and
+    /// examined edge to `visitor`.
+    pub(crate) fn search_visited<G, H, F, C, V>(
+        &self,
+        sources: &[(usize, W)],
+        is_goal: G,
+        heuristic: H,
+        filter: F,
+        cost: C,
+        visitor: &mut V,
+    ) -> (Vec<W>, Predecessors, Option<usize>)
+    where
+        G: Fn(&Vertex<T>, W) -> bool,
+        H: Fn(&Vertex<T>) -> W,
+        F: Fn(&Vertex<T>, &Edge<W, E>) -> bool,
+        C: Fn(&Vertex<T>, &Edge<W, E>, W) -> W,
+        V: SearchVisitor<T, W, E>,
+    {
+        let mut dist = vec![W::INFINITY; self.vertices.len()];
+        let mut queue = IndexedHeap::new(self.vertices.len());
+        for (source, initial) in sources {
+            if *initial < dist[*source] {
+                dist[*source] = *initial;
+                let estimate = heuristic(self.vertex(*source));
+                queue.push_or_decrease(*source, initial.saturating_add(estimate));
+            }
+        }
+
+        let mut visited = vec![false; self.vertices.len()];
+        let mut prev: Predecessors = vec![None; self.vertices.len()];
+
+        while let Some((_, current)) = queue.pop() {
+            visited[current] = true;
+            let vertex = self.vertex(current);
+            visitor.on_settle(vertex, dist[current]);
+
+            if is_goal(vertex, dist[current]) {
+                event!(
+                    DEBUG,
+                    goal = current,
+                    cost = dist[current].to_f64(),
+                    settled = visited.iter().filter(|settled| **settled).count(),
+                    "search reached its goal"
+                );
+                return (dist, prev, Some(current));
+            }
+
+            for edge in self.outgoing(current) {
+                if visited[edge.to] {
+                    visitor.on_skip(vertex, edge);
+                    continue;
+                }
+                if !filter(vertex, edge) {
+                    continue;
+                }
+
+                let new_dist = dist[current]
+                    .saturating_add(cost(vertex, edge, dist[current]))
+                    .saturating_add(self.entry_cost(edge.to));
+                if new_dist < dist[edge.to] {
+                    visitor.on_relax(vertex, edge, new_dist);
+                    prev[edge.to] = Some(current as CompactId);
+                    dist[edge.to] = new_dist;
+                    let estimate = heuristic(self.vertex(edge.to));
+                    queue.push_or_decrease(edge.to, new_dist.saturating_add(estimate));
+                } else {
+                    visitor.on_skip(vertex, edge);
+                }
+            }
+        }
+
+        event!(
+            DEBUG,
+            settled = visited.iter().filter(|settled| **settled).count(),
+            "search exhausted its frontier"
+        );
+        (dist, prev, None)
+    }
+}
+
+impl<T: Display, W: Display + Weight, E> Display for Graph<T, W, E>
+where
+    T: Clone,
+    E: Clone,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let mut graph_string: String = String::new();
+        for (vertex, edges) in self.vertices.iter().zip(&self.adjacency) {
+            let Some(vertex) = vertex else {
+                continue;
+            };
+            graph_string += format!("[{}] -> ", vertex.value).as_str();
+            for edge in edges {
+                let arrow = if edge.undirected { "<-> " } else { "" };
+                graph_string +=
+                    format!("[{}{} ({})]", arrow, self.vertex(edge.to).value, edge.cost).as_str();
+            }
+            graph_string += "\n"
+        }
+
+        write!(f, "{}", graph_string)
+    }
+}
+
+// Queries keep all their state local, so one graph can serve many threads.
+const _: fn() = || {
+    fn assert_send_sync<S: Send + Sync>() {}
+    assert_send_sync::<Graph<String>>();
+    assert_send_sync::<crate::FrozenGraph<String>>();
+};
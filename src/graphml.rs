@@ -0,0 +1,386 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use crate::{Graph, GraphError};
+
+impl<T: Display> Graph<T>
+where
+    T: Clone,
+{
+    /// Renders the graph as a GraphML document, storing vertex values under a
+    /// `label` node key and edge costs under a `weight` edge key.
+    pub fn to_graphml(&self) -> String {
+        let mut xml = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+            "  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n",
+            "  <key id=\"weight\" for=\"edge\" attr.name=\"weight\" attr.type=\"double\"/>\n",
+            "  <graph id=\"G\" edgedefault=\"directed\">\n",
+        ));
+        for vertex in self.vertices.iter().flatten() {
+            xml += &format!(
+                "    <node id=\"n{}\"><data key=\"label\">{}</data></node>\n",
+                vertex.id,
+                escape(&vertex.value.to_string())
+            );
+        }
+        for vertex in self.vertices.iter().flatten() {
+            for edge in &self.adjacency[vertex.id] {
+                let directed = if edge.undirected {
+                    " directed=\"false\""
+                } else {
+                    ""
+                };
+                xml += &format!(
+                    "    <edge source=\"n{}\" target=\"n{}\"{}><data key=\"weight\">{}</data></edge>\n",
+                    vertex.id, edge.to, directed, edge.cost
+                );
+            }
+        }
+        xml += "  </graph>\n</graphml>\n";
+        xml
+    }
+}
+
+impl Graph<String> {
+    /// Parses a GraphML document.
+    ///
+    /// Vertex values are the text of each node's `label` data exactly as
+    /// written, with entity and character references decoded, or its id if
+    /// it has none. Edge costs
+    /// come from the edge key named `weight` and default to 1. Edges follow
+    /// the graph's `edgedefault` unless they set `directed` themselves.
+    pub fn from_graphml(input: &str) -> Result<Self, GraphError> {
+        let mut reader = Reader {
+            input,
+            position: 0,
+            parent: Parent::Other,
+        };
+
+        let mut keys: HashMap<Cow<str>, Cow<str>> = HashMap::new();
+        let mut undirected_default = false;
+        let mut nodes: Vec<(Cow<str>, Option<Cow<str>>)> = Vec::new();
+        let mut edges: Vec<PendingEdge> = Vec::new();
+        let mut data_key: Option<Cow<str>> = None;
+
+        while let Some(event) = reader.next_event()? {
+            match event {
+                Event::Start {
+                    name,
+                    attributes,
+                    self_closing,
+                } => {
+                    let attribute = |key: &str| {
+                        attributes
+                            .iter()
+                            .find(|(k, _)| *k == key)
+                            .map(|(_, value)| value.clone())
+                    };
+                    match name {
+                        "key" => {
+                            if let (Some(id), Some(name)) =
+                                (attribute("id"), attribute("attr.name"))
+                            {
+                                keys.insert(id, name);
+                            }
+                        }
+                        "graph" => {
+                            undirected_default =
+                                attribute("edgedefault").as_deref() == Some("undirected")
+                        }
+                        "node" => {
+                            let id = attribute("id")
+                                .ok_or_else(|| reader.error("node without an id"))?;
+                            nodes.push((id, None));
+                        }
+                        "edge" => {
+                            let (Some(source), Some(target)) =
+                                (attribute("source"), attribute("target"))
+                            else {
+                                return Err(reader.error("edge without a source or target"));
+                            };
+                            let directed = attribute("directed").map(|d| d == "true");
+                            edges.push(PendingEdge {
+                                source,
+                                target,
+                                weight: None,
+                                directed,
+                                position: reader.position,
+                            });
+                        }
+                        "data" => {
+                            let key = attribute("key");
+                            let name = key.as_ref().map(|key| keys.get(key).unwrap_or(key));
+                            // Label data that is present but empty is an empty label.
+                            if name.is_some_and(|name| name == "label")
+                                && reader.parent == Parent::Node
+                            {
+                                if let Some(node) = nodes.last_mut() {
+                                    node.1 = Some(Cow::Borrowed(""));
+                                }
+                            }
+                            if !self_closing {
+                                data_key = key;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Text(text) => {
+                    let Some(key) = &data_key else {
+                        continue;
+                    };
+                    let name = keys.get(key).unwrap_or(key);
+                    match (name.as_ref(), reader.parent) {
+                        ("label", Parent::Node) => {
+                            if let Some(node) = nodes.last_mut() {
+                                append(&mut node.1, text);
+                            }
+                        }
+                        ("weight", Parent::Edge) => {
+                            if let Some(edge) = edges.last_mut() {
+                                append(&mut edge.weight, text);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Event::End(name) => {
+                    if name == "data" {
+                        data_key = None;
+                    }
+                }
+            }
+        }
+
+        let mut graph = Graph::new();
+        let mut ids: HashMap<&str, usize> = HashMap::new();
+        for (name, label) in &nodes {
+            let value = label.as_ref().unwrap_or(name).to_string();
+            ids.insert(name, graph.add_vertex(value));
+        }
+        for edge in &edges {
+            let PendingEdge {
+                source,
+                target,
+                weight,
+                directed,
+                position,
+            } = edge;
+            // Lines are only counted for errors, which keeps reading linear.
+            let error = |message: String| parse_error(line_at(input, *position), &message);
+            let unknown = |name: &str| error(format!("unknown node '{}'", name));
+            let from = *ids.get(source.as_ref()).ok_or_else(|| unknown(source))?;
+            let to = *ids.get(target.as_ref()).ok_or_else(|| unknown(target))?;
+            let cost = match weight {
+                Some(weight) => weight
+                    .trim()
+                    .parse::<f64>()
+                    .map_err(|_| error(format!("invalid edge weight '{}'", weight)))?,
+                None => 1.0,
+            };
+            if directed.unwrap_or(!undirected_default) {
+                graph.add_edge(from, to, cost)?;
+            } else {
+                graph.add_edge_undirected(from, to, cost)?;
+            }
+        }
+
+        Ok(graph)
+    }
+}
+
+/// An edge read from the document, added once all nodes are known.
+struct PendingEdge<'a> {
+    source: Cow<'a, str>,
+    target: Cow<'a, str>,
+    weight: Option<Cow<'a, str>>,
+    directed: Option<bool>,
+    /// Where the edge's start tag ends in the input, for errors.
+    position: usize,
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Appends `text` to the data read so far, without copying it if it is the
+/// only text, e.g. of data with a CDATA section between other text.
+fn append<'a>(data: &mut Option<Cow<'a, str>>, text: Cow<'a, str>) {
+    *data = Some(match data.take() {
+        Some(data) if !data.is_empty() => Cow::Owned(data.into_owned() + &text),
+        _ => text,
+    });
+}
+
+/// Returns the number of the line `position` lies on in `input`.
+fn line_at(input: &str, position: usize) -> usize {
+    input[..position].matches('\n').count() + 1
+}
+
+fn parse_error(line: usize, message: &str) -> GraphError {
+    GraphError::Parse {
+        line,
+        message: message.to_string(),
+    }
+}
+
+enum Event<'a> {
+    Start {
+        name: &'a str,
+        attributes: Vec<(&'a str, Cow<'a, str>)>,
+        self_closing: bool,
+    },
+    End(&'a str),
+    Text(Cow<'a, str>),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Parent {
+    Other,
+    Node,
+    Edge,
+}
+
+/// A minimal pull reader for the XML subset GraphML documents use.
+struct Reader<'a> {
+    input: &'a str,
+    position: usize,
+    parent: Parent,
+}
+
+impl<'a> Reader<'a> {
+    fn error(&self, message: &str) -> GraphError {
+        parse_error(line_at(self.input, self.position), message)
+    }
+
+    /// Replaces the predefined entities and character references in `text`
+    /// with the characters they stand for.
+    fn decode(&self, text: &'a str) -> Result<Cow<'a, str>, GraphError> {
+        if !text.contains('&') {
+            return Ok(Cow::Borrowed(text));
+        }
+        let mut decoded = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find('&') {
+            decoded.push_str(&rest[..start]);
+            let end = rest[start..]
+                .find(';')
+                .ok_or_else(|| self.error("unterminated entity reference"))?;
+            let name = &rest[start + 1..start + end];
+            let character = match name {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ => match name.strip_prefix('#') {
+                    Some(code) => match code.strip_prefix(['x', 'X']) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                        None => code.parse::<u32>().ok(),
+                    }
+                    .and_then(char::from_u32),
+                    None => None,
+                },
+            };
+            let character =
+                character.ok_or_else(|| self.error(&format!("unknown entity '&{};'", name)))?;
+            decoded.push(character);
+            rest = &rest[start + end + 1..];
+        }
+        decoded.push_str(rest);
+        Ok(Cow::Owned(decoded))
+    }
+
+    fn skip_past(&mut self, terminator: &str) -> Result<&'a str, GraphError> {
+        let rest = &self.input[self.position..];
+        let end = rest
+            .find(terminator)
+            .ok_or_else(|| self.error(&format!("expected '{}'", terminator)))?;
+        self.position += end + terminator.len();
+        Ok(&rest[..end])
+    }
+
+    fn next_event(&mut self) -> Result<Option<Event<'a>>, GraphError> {
+        loop {
+            let rest = &self.input[self.position..];
+            if rest.is_empty() {
+                return Ok(None);
+            }
+
+            if !rest.starts_with('<') {
+                let end = rest.find('<').unwrap_or(rest.len());
+                self.position += end;
+                return Ok(Some(Event::Text(self.decode(&rest[..end])?)));
+            }
+
+            if rest.starts_with("<?") {
+                self.skip_past("?>")?;
+            } else if rest.starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if rest.starts_with("<![CDATA[") {
+                self.position += "<![CDATA[".len();
+                return Ok(Some(Event::Text(Cow::Borrowed(self.skip_past("]]>")?))));
+            } else if rest.starts_with("<!") {
+                self.skip_past(">")?;
+            } else if let Some(rest) = rest.strip_prefix("</") {
+                let end = rest.find('>').ok_or_else(|| self.error("expected '>'"))?;
+                let name = rest[..end].trim();
+                self.position += end + 3;
+                if matches!(name, "node" | "edge") {
+                    self.parent = Parent::Other;
+                }
+                return Ok(Some(Event::End(name)));
+            } else {
+                return self.start_tag().map(Some);
+            }
+        }
+    }
+
+    fn start_tag(&mut self) -> Result<Event<'a>, GraphError> {
+        self.position += 1;
+        let tag = self.skip_past(">")?;
+        let (tag, self_closing) = match tag.strip_suffix('/') {
+            Some(tag) => (tag, true),
+            None => (tag, false),
+        };
+
+        let name_end = tag.find(|c: char| c.is_whitespace()).unwrap_or(tag.len());
+        let name = &tag[..name_end];
+
+        let mut attributes = Vec::new();
+        let mut rest = tag[name_end..].trim_start();
+        while !rest.is_empty() {
+            let equals = rest
+                .find('=')
+                .ok_or_else(|| self.error("expected '=' in attribute"))?;
+            let key = rest[..equals].trim();
+            let value = rest[equals + 1..].trim_start();
+            let quote = value
+                .chars()
+                .next()
+                .filter(|c| *c == '"' || *c == '\'')
+                .ok_or_else(|| self.error("expected a quoted attribute value"))?;
+            let end = value[1..]
+                .find(quote)
+                .ok_or_else(|| self.error("unterminated attribute value"))?;
+            attributes.push((key, self.decode(&value[1..end + 1])?));
+            rest = value[end + 2..].trim_start();
+        }
+
+        match name {
+            "node" if !self_closing => self.parent = Parent::Node,
+            "edge" if !self_closing => self.parent = Parent::Edge,
+            _ => {}
+        }
+        Ok(Event::Start {
+            name,
+            attributes,
+            self_closing,
+        })
+    }
+}
@@ -0,0 +1,176 @@
+use crate::{Graph, GraphError};
+
+/// Which neighbouring cells of a grid are connected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Up, down, left and right.
+    FourWay,
+    /// [`Connectivity::FourWay`] plus the four diagonals.
+    EightWay,
+}
+
+pub(crate) const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+pub(crate) const DIAGONAL: [(isize, isize); 4] = [(-1, -1), (-1, 1), (1, -1), (1, 1)];
+
+impl Graph<(usize, usize)> {
+    /// Builds a graph with one vertex per grid cell, valued `(row, col)` and
+    /// with id `row * width + col`, where `width` is the length of the longest
+    /// row.
+    ///
+    /// `costs[row][col]` is the cost of entering that cell; diagonal moves cost
+    /// √2 times as much. Cells with an infinite cost, or missing from a short
+    /// row, are walls. Diagonal moves may not cut the corner of a wall.
+    ///
+    /// Fails if a cost is NaN or negative.
+    pub fn from_grid(costs: &[Vec<f64>], connectivity: Connectivity) -> Result<Self, GraphError> {
+        let height = costs.len();
+        let width = costs.iter().map(Vec::len).max().unwrap_or(0);
+        let cost = |row: usize, col: usize| costs[row].get(col).copied().unwrap_or(f64::INFINITY);
+
+        if let Some(invalid) = costs
+            .iter()
+            .flatten()
+            .find(|cost| cost.is_nan() || **cost < 0.0)
+        {
+            return Err(GraphError::InvalidCost(*invalid));
+        }
+
+        let mut graph = Graph::new();
+        for row in 0..height {
+            for col in 0..width {
+                graph.add_vertex((row, col));
+            }
+        }
+
+        let neighbour = |row: usize, col: usize, (dr, dc): (isize, isize)| {
+            let (row, col) = (row.checked_add_signed(dr)?, col.checked_add_signed(dc)?);
+            (row < height && col < width && cost(row, col).is_finite()).then_some((row, col))
+        };
+
+        for row in 0..height {
+            for col in 0..width {
+                if !cost(row, col).is_finite() {
+                    continue;
+                }
+                let from = row * width + col;
+
+                for step in ORTHOGONAL {
+                    if let Some((r, c)) = neighbour(row, col, step) {
+                        graph.add_edge(from, r * width + c, cost(r, c))?;
+                    }
+                }
+
+                if connectivity == Connectivity::FourWay {
+                    continue;
+                }
+                for (dr, dc) in DIAGONAL {
+                    let corners_open = neighbour(row, col, (dr, 0)).is_some()
+                        && neighbour(row, col, (0, dc)).is_some();
+                    if let (true, Some((r, c))) = (corners_open, neighbour(row, col, (dr, dc))) {
+                        graph.add_edge(
+                            from,
+                            r * width + c,
+                            cost(r, c) * std::f64::consts::SQRT_2,
+                        )?;
+                    }
+                }
+            }
+        }
+
+        Ok(graph)
+    }
+}
+
+/// The walkable cells of a rectangular tile map, used by the searches that
+/// are specialised for uniform-cost grids.
+///
+/// Cell ids match those of [`Graph::from_grid`]: `row * width + col`.
+#[derive(Debug, Clone)]
+pub struct Grid {
+    width: usize,
+    height: usize,
+    walkable: Vec<bool>,
+}
+
+impl Grid {
+    /// Creates a grid from rows of walkable flags. Cells missing from a short
+    /// row are walls.
+    pub fn new(walkable: &[Vec<bool>]) -> Self {
+        let height = walkable.len();
+        let width = walkable.iter().map(Vec::len).max().unwrap_or(0);
+        let mut cells = vec![false; width * height];
+        for (row, flags) in walkable.iter().enumerate() {
+            cells[row * width..row * width + flags.len()].copy_from_slice(flags);
+        }
+        Grid {
+            width,
+            height,
+            walkable: cells,
+        }
+    }
+
+    /// Creates a grid from the same cost rows as [`Graph::from_grid`], treating
+    /// cells with a finite cost as walkable.
+    pub fn from_costs(costs: &[Vec<f64>]) -> Self {
+        let walkable: Vec<Vec<bool>> = costs
+            .iter()
+            .map(|row| row.iter().map(|cost| cost.is_finite()).collect())
+            .collect();
+        Self::new(&walkable)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the id of the cell at `row` and `col`.
+    pub fn id(&self, row: usize, col: usize) -> usize {
+        row * self.width + col
+    }
+
+    /// Returns the `(row, col)` of the cell with the given id.
+    pub fn cell(&self, id: usize) -> (usize, usize) {
+        (id / self.width, id % self.width)
+    }
+
+    /// Returns whether the cell at `row` and `col` exists and is walkable.
+    pub fn is_walkable(&self, row: isize, col: isize) -> bool {
+        row >= 0
+            && col >= 0
+            && (row as usize) < self.height
+            && (col as usize) < self.width
+            && self.walkable[row as usize * self.width + col as usize]
+    }
+
+    pub(crate) fn position(&self, id: usize) -> (isize, isize) {
+        let (row, col) = self.cell(id);
+        (row as isize, col as isize)
+    }
+
+    pub(crate) fn open(&self, (row, col): (isize, isize)) -> bool {
+        self.is_walkable(row, col)
+    }
+
+    /// Whether a single step in `direction` is allowed, without cutting corners.
+    pub(crate) fn can_step(&self, (r, c): (isize, isize), (dr, dc): (isize, isize)) -> bool {
+        self.open((r + dr, c + dc)) && self.open((r + dr, c)) && self.open((r, c + dc))
+    }
+
+    /// Checks that both endpoints of a grid search exist, and that they are
+    /// walkable unless they are the same cell.
+    pub(crate) fn check_endpoints(&self, from: usize, to: usize) -> Result<(), GraphError> {
+        for id in [from, to] {
+            if id >= self.width * self.height {
+                return Err(GraphError::UnknownVertex(id));
+            }
+        }
+        if from != to && (!self.open(self.position(from)) || !self.open(self.position(to))) {
+            return Err(GraphError::NoPathFound { from, to });
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path as FsPath;
+
+use crate::csv::split_row;
+use crate::{Graph, GraphError, Path};
+
+/// A vehicle arriving at or departing from a stop, the vertex type of a
+/// [`TransitNetwork`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StopEvent {
+    /// Index of the stop, see [`TransitNetwork::stop`].
+    pub stop: usize,
+    /// Seconds after midnight of the service day, which may exceed a day for
+    /// trips running past midnight.
+    pub time: f64,
+}
+
+/// A row of `stop_times.txt`, with its times in seconds.
+struct StopTime {
+    line: usize,
+    sequence: u32,
+    stop: usize,
+    arrival: f64,
+    departure: f64,
+}
+
+/// A public transit timetable as a time-expanded graph: every arrival and
+/// departure of a trip at a stop is a vertex, linked by edges for riding
+/// between stops, staying on board, and waiting at a stop for a later
+/// vehicle. Every edge costs the time it takes, so the cheapest path from a
+/// departure reaches each stop at the earliest possible time.
+///
+/// Built from GTFS data with [`TransitNetwork::from_gtfs`]. Every trip in
+/// the timetable is assumed to run; service calendars, transfer times and
+/// parent stations are ignored.
+pub struct TransitNetwork {
+    graph: Graph<StopEvent>,
+    /// GTFS id and name of each stop, by index.
+    stops: Vec<(String, String)>,
+    indices: HashMap<String, usize>,
+    /// Events at each stop as `(time, vertex id)`, sorted by time.
+    events: Vec<Vec<(f64, usize)>>,
+}
+
+impl TransitNetwork {
+    /// Reads `stops.txt` and `stop_times.txt` from a GTFS feed unpacked in
+    /// `directory`, as [`TransitNetwork::from_gtfs_readers`] would.
+    ///
+    /// Fails if either file cannot be read or does not parse.
+    pub fn from_gtfs<P: AsRef<FsPath>>(directory: P) -> Result<Self, GraphError> {
+        let open = |name: &str| {
+            File::open(directory.as_ref().join(name))
+                .map_err(|error| GraphError::Io(format!("{}: {}", name, error)))
+        };
+        Self::from_gtfs_readers(open("stops.txt")?, open("stop_times.txt")?)
+    }
+
+    /// Builds the network from the contents of a GTFS `stops.txt` and
+    /// `stop_times.txt`. Stop times without an arrival or departure time are
+    /// skipped, and trips ride straight on between the stops around them.
+    ///
+    /// Fails if a required column is missing, a time does not parse, a stop
+    /// time names an unknown stop, or times decrease along a trip.
+    pub fn from_gtfs_readers<R: Read, S: Read>(
+        stops: R,
+        stop_times: S,
+    ) -> Result<Self, GraphError> {
+        span!(INFO, "TransitNetwork::from_gtfs_readers");
+        let mut network = TransitNetwork {
+            graph: Graph::new(),
+            stops: Vec::new(),
+            indices: HashMap::new(),
+            events: Vec::new(),
+        };
+        for row in rows(stops, "stops.txt", &["stop_id", "stop_name"])? {
+            let (_, [id, name]) = row?;
+            if !network.indices.contains_key(&id) {
+                network.indices.insert(id.clone(), network.stops.len());
+                network.stops.push((id, name));
+                network.events.push(Vec::new());
+            }
+        }
+
+        let columns = [
+            "trip_id",
+            "stop_sequence",
+            "stop_id",
+            "arrival_time",
+            "departure_time",
+        ];
+        let mut trips: HashMap<String, Vec<StopTime>> = HashMap::new();
+        for row in rows(stop_times, "stop_times.txt", &columns)? {
+            let (line, [trip, sequence, stop, arrival, departure]) = row?;
+            let error = |message: String| GraphError::Parse {
+                line,
+                message: format!("stop_times.txt: {}", message),
+            };
+            let sequence: u32 = sequence
+                .parse()
+                .map_err(|_| error(format!("invalid stop_sequence '{}'", sequence)))?;
+            let stop = *network
+                .indices
+                .get(&stop)
+                .ok_or_else(|| error(format!("unknown stop '{}'", stop)))?;
+            let (arrival, departure) = match (arrival.is_empty(), departure.is_empty()) {
+                (true, true) => continue,
+                (true, false) => (departure.clone(), departure),
+                (false, true) => (arrival.clone(), arrival),
+                (false, false) => (arrival, departure),
+            };
+            let parse = |time: &str| {
+                parse_time(time).ok_or_else(|| error(format!("invalid time '{}'", time)))
+            };
+            let (arrival, departure) = (parse(&arrival)?, parse(&departure)?);
+            if departure < arrival {
+                return Err(error(format!("trip '{}' departs before it arrives", trip)));
+            }
+            trips.entry(trip).or_default().push(StopTime {
+                line,
+                sequence,
+                stop,
+                arrival,
+                departure,
+            });
+        }
+
+        let mut trips: Vec<(String, Vec<StopTime>)> = trips.into_iter().collect();
+        trips.sort_by(|a, b| a.0.cmp(&b.0));
+        for (trip, mut stop_times) in trips {
+            stop_times.sort_by_key(|stop_time| stop_time.sequence);
+            let mut previous: Option<(usize, f64)> = None;
+            for StopTime {
+                line,
+                stop,
+                arrival,
+                departure,
+                ..
+            } in stop_times
+            {
+                let arrives = network.add_event(stop, arrival);
+                let departs = network.add_event(stop, departure);
+                if let Some((from, time)) = previous {
+                    if arrival < time {
+                        return Err(GraphError::Parse {
+                            line,
+                            message: format!("stop_times.txt: trip '{}' goes back in time", trip),
+                        });
+                    }
+                    network.graph.add_edge(from, arrives, arrival - time)?;
+                }
+                network
+                    .graph
+                    .add_edge(arrives, departs, departure - arrival)?;
+                previous = Some((departs, departure));
+            }
+        }
+
+        for events in &mut network.events {
+            events.sort_by(|a, b| a.0.total_cmp(&b.0));
+            for pair in events.windows(2) {
+                network
+                    .graph
+                    .add_edge(pair[0].1, pair[1].1, pair[1].0 - pair[0].0)?;
+            }
+        }
+        event!(
+            INFO,
+            stops = network.stops.len(),
+            events = network.graph.vertex_count(),
+            "built transit network"
+        );
+        Ok(network)
+    }
+
+    /// Returns the time-expanded graph.
+    pub fn graph(&self) -> &Graph<StopEvent> {
+        &self.graph
+    }
+
+    /// Returns the index of the stop with the given GTFS `stop_id`.
+    pub fn stop(&self, id: &str) -> Option<usize> {
+        self.indices.get(id).copied()
+    }
+
+    /// Returns the GTFS `stop_id` and `stop_name` of the stop with the given
+    /// index.
+    pub fn stop_info(&self, stop: usize) -> Option<(&str, &str)> {
+        self.stops
+            .get(stop)
+            .map(|(id, name)| (id.as_str(), name.as_str()))
+    }
+
+    /// Returns the earliest time at which `to_stop` can be reached when
+    /// setting out from `from_stop` at `departure`, in seconds after
+    /// midnight, together with the events along the way from boarding at
+    /// `from_stop`. Stops are given by GTFS `stop_id`.
+    ///
+    /// Fails with [`GraphError::UnknownKey`] if either stop is unknown, and
+    /// with [`GraphError::NoPathFound`] between the stop indices if no
+    /// vehicle leaves `from_stop` at or after `departure` or `to_stop`
+    /// cannot be reached.
+    pub fn earliest_arrival(
+        &self,
+        from_stop: &str,
+        to_stop: &str,
+        departure: f64,
+    ) -> Result<(f64, Path<StopEvent>), GraphError> {
+        let from = self.stop(from_stop).ok_or(GraphError::UnknownKey)?;
+        let to = self.stop(to_stop).ok_or(GraphError::UnknownKey)?;
+        let no_path = GraphError::NoPathFound { from, to };
+
+        let events = &self.events[from];
+        let first = events.partition_point(|(time, _)| *time < departure);
+        let Some(&(time, start)) = events.get(first) else {
+            return Err(no_path);
+        };
+        let (dist, prev, end) = self.graph.search_until(
+            &[(start, time - departure)],
+            |event, _| event.value.stop == to,
+            |_| 0.0,
+            |_, _| true,
+        );
+        let end = end.ok_or(no_path)?;
+        let vertices = self.graph.reconstruct_path(&prev, start, end);
+        let arrival = departure + dist[end];
+        Ok((arrival, self.graph.to_path(vertices, arrival - time)))
+    }
+
+    fn add_event(&mut self, stop: usize, time: f64) -> usize {
+        let id = self.graph.add_vertex(StopEvent { stop, time });
+        self.events[stop].push((time, id));
+        id
+    }
+}
+
+/// Reads the rows of a GTFS file with a header, as the line number and the
+/// fields in the given `columns`, failing if a column is missing. Blank
+/// lines are skipped and missing trailing fields read as empty.
+fn rows<R: Read, const N: usize>(
+    reader: R,
+    file: &'static str,
+    columns: &[&str; N],
+) -> Result<impl Iterator<Item = Result<(usize, [String; N]), GraphError>>, GraphError> {
+    let io = move |error: std::io::Error| GraphError::Io(format!("{}: {}", file, error));
+    let mut lines = BufReader::new(reader).lines().enumerate();
+    let header = match lines.next() {
+        Some((_, line)) => split_row(line.map_err(io)?.trim_start_matches('\u{feff}'), 1)?,
+        None => Vec::new(),
+    };
+    let mut positions = [0; N];
+    for (position, column) in positions.iter_mut().zip(columns) {
+        *position = header
+            .iter()
+            .position(|name| name == column)
+            .ok_or_else(|| GraphError::Parse {
+                line: 1,
+                message: format!("{}: missing column '{}'", file, column),
+            })?;
+    }
+
+    Ok(lines
+        .map(move |(index, line)| {
+            let line = line.map_err(io)?;
+            if line.trim().is_empty() {
+                return Ok(None);
+            }
+            let fields = split_row(&line, index + 1)?;
+            let field = |position: usize| fields.get(position).cloned().unwrap_or_default();
+            Ok(Some((index + 1, positions.map(field))))
+        })
+        .filter_map(Result::transpose))
+}
+
+/// Parses a GTFS time of the form `H:MM:SS` into seconds. Hours may exceed
+/// 23 for trips running past midnight.
+fn parse_time(time: &str) -> Option<f64> {
+    let mut parts = time.trim().split(':');
+    let (Some(hours), Some(minutes), Some(seconds), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return None;
+    };
+    let [hours, minutes, seconds]: [u32; 3] = [
+        hours.parse().ok()?,
+        minutes.parse().ok()?,
+        seconds.parse().ok()?,
+    ];
+    (minutes < 60 && seconds < 60).then_some(f64::from(hours * 3600 + minutes * 60 + seconds))
+}
@@ -0,0 +1,80 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::min_non_nan::MinNonNan;
+use crate::{Graph, GraphError, Path, Weight};
+
+/// A vertex together with the number of edges taken to reach it.
+type State = (usize, usize);
+
+impl<T, W> Graph<T, W>
+where
+    T: Clone,
+    W: Weight,
+{
+    /// Like [`Graph::get_shortest_path`], but only considers paths of at most
+    /// `max_hops` edges, e.g. to allow no more than two transfers.
+    ///
+    /// The search tracks how many edges each vertex is reached with, and
+    /// only revisits a vertex when reaching it with fewer edges than before.
+    ///
+    /// Fails if either vertex is unknown or `to` is not reachable from `from`
+    /// within `max_hops` edges.
+    pub fn get_shortest_path_max_hops(
+        &self,
+        from: usize,
+        to: usize,
+        max_hops: usize,
+    ) -> Result<Path<T, W>, GraphError> {
+        span!(DEBUG, "Graph::get_shortest_path_max_hops", from, to);
+        self.get_vertex(from)?;
+        self.get_vertex(to)?;
+
+        let mut dist: HashMap<State, W> = HashMap::from([((from, 0), W::ZERO)]);
+        let mut prev: HashMap<State, State> = HashMap::new();
+        // Fewest edges each vertex has been settled with; settling it again
+        // only pays off with fewer, since every later settling costs more.
+        let mut fewest_hops = vec![usize::MAX; self.vertices.len()];
+        // Of equal costs, fewer edges leave the queue first.
+        let mut queue = BinaryHeap::from([(MinNonNan(W::ZERO), Reverse(0), from)]);
+
+        while let Some((MinNonNan(cost), Reverse(hops), current)) = queue.pop() {
+            let state = (current, hops);
+            if cost > dist[&state] || hops >= fewest_hops[current] {
+                continue;
+            }
+            fewest_hops[current] = hops;
+
+            if current == to {
+                let mut path = vec![self.vertex(current).clone()];
+                let mut at = state;
+                while let Some(before) = prev.get(&at) {
+                    path.push(self.vertex(before.0).clone());
+                    at = *before;
+                }
+                path.reverse();
+                return Ok(self.to_path(path, cost));
+            }
+            if hops == max_hops {
+                continue;
+            }
+
+            for edge in self.outgoing(current) {
+                let new_dist = cost
+                    .saturating_add(edge.cost)
+                    .saturating_add(self.entry_cost(edge.to));
+                let next = (edge.to, hops + 1);
+                if new_dist == W::INFINITY || hops + 1 >= fewest_hops[edge.to] {
+                    continue;
+                }
+                if dist.get(&next).is_none_or(|dist| new_dist < *dist) {
+                    dist.insert(next, new_dist);
+                    prev.insert(next, state);
+                    queue.push((MinNonNan(new_dist), Reverse(hops + 1), edge.to));
+                }
+            }
+        }
+
+        Err(GraphError::NoPathFound { from, to })
+    }
+}
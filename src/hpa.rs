@@ -0,0 +1,562 @@
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::f64::consts::SQRT_2;
+use std::ops::Range;
+
+use crate::grid::{DIAGONAL, ORTHOGONAL};
+use crate::jps::octile;
+use crate::min_non_nan::MinNonNan;
+use crate::{Connectivity, GraphError, Grid};
+
+/// Runs of open cells along a cluster border at least this long get a
+/// transition at each end instead of a single one in the middle.
+const LONG_ENTRANCE: usize = 6;
+
+/// An edge of the abstract graph of a [`GridHierarchy`].
+#[derive(Debug, Clone)]
+struct Link {
+    /// Index of the portal the link leads to.
+    to: usize,
+    cost: f64,
+    /// Cells after the source portal up to and including the target one.
+    cells: Vec<usize>,
+}
+
+/// A grid split into square clusters, with portals where clusters touch and
+/// precomputed paths between the portals of each cluster, for hierarchical
+/// path-finding (HPA*) on maps too large to search cell by cell.
+///
+/// Queries search the small abstract graph of portals instead of the grid,
+/// and only search cells locally in the clusters of the endpoints, or
+/// directly over both clusters if they are the same or neighbours. Paths
+/// between clusters further apart cross each border at a portal, by a
+/// straight step, and can cost a lot more than the cheapest path, as found by
+/// [`Grid::jump_point_search`], when portals are far apart. Refining them
+/// with [`GridHierarchy::get_refined_path`] removes most of the detour.
+///
+/// Built with [`Grid::build_hierarchy`]. Later changes to the grid are not
+/// reflected.
+#[derive(Debug, Clone)]
+pub struct GridHierarchy {
+    grid: Grid,
+    connectivity: Connectivity,
+    cluster_size: usize,
+    /// Cell id of each portal.
+    portals: Vec<usize>,
+    /// Index of the portal at each portal cell.
+    portal_at: HashMap<usize, usize>,
+    /// Portals inside each cluster, by cluster index.
+    cluster_portals: Vec<Vec<usize>>,
+    links: Vec<Vec<Link>>,
+}
+
+/// Distances from one cell to the others of its cluster, by searching only
+/// the cells inside it.
+struct LocalSearch {
+    rows: Range<usize>,
+    cols: Range<usize>,
+    dist: Vec<f64>,
+    /// Cell each cell was reached from, `usize::MAX` for the source.
+    prev: Vec<usize>,
+}
+
+impl LocalSearch {
+    fn index(&self, (row, col): (usize, usize)) -> Option<usize> {
+        (self.rows.contains(&row) && self.cols.contains(&col))
+            .then(|| (row - self.rows.start) * self.cols.len() + col - self.cols.start)
+    }
+}
+
+impl Grid {
+    /// Splits the grid into clusters of `cluster_size` by `cluster_size`
+    /// cells and precomputes the abstract graph of a [`GridHierarchy`].
+    /// Steps are straight, costing 1, or with [`Connectivity::EightWay`] also
+    /// diagonal, costing √2 without cutting the corner of a wall.
+    ///
+    /// Clusters of a few dozen cells across suit most maps: larger ones mean
+    /// fewer portals but slower local searches at the endpoints.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cluster_size` is zero.
+    pub fn build_hierarchy(
+        &self,
+        cluster_size: usize,
+        connectivity: Connectivity,
+    ) -> GridHierarchy {
+        assert!(cluster_size > 0, "clusters must have at least one cell");
+        span!(INFO, "Grid::build_hierarchy", cluster_size);
+        let mut hierarchy = GridHierarchy {
+            grid: self.clone(),
+            connectivity,
+            cluster_size,
+            portals: Vec::new(),
+            portal_at: HashMap::new(),
+            cluster_portals: Vec::new(),
+            links: Vec::new(),
+        };
+        let (cluster_rows, cluster_cols) = hierarchy.cluster_dimensions();
+        hierarchy.cluster_portals = vec![Vec::new(); cluster_rows * cluster_cols];
+
+        for cluster_row in 0..cluster_rows {
+            for cluster_col in 0..cluster_cols {
+                let rows = hierarchy.span(cluster_row, self.height());
+                let cols = hierarchy.span(cluster_col, self.width());
+                if cols.end < self.width() {
+                    let border = rows
+                        .clone()
+                        .map(|row| ((row, cols.end - 1), (row, cols.end)));
+                    hierarchy.add_entrances(border.collect());
+                }
+                if rows.end < self.height() {
+                    let border = cols.map(|col| ((rows.end - 1, col), (rows.end, col)));
+                    hierarchy.add_entrances(border.collect());
+                }
+            }
+        }
+
+        for cluster in hierarchy.cluster_portals.clone() {
+            for &portal in &cluster {
+                let search = hierarchy.local_search(hierarchy.portals[portal]);
+                for &other in &cluster {
+                    let cell = hierarchy.portals[other];
+                    let cost = hierarchy.local_dist(&search, cell);
+                    if other != portal && cost.is_finite() {
+                        let cells = hierarchy.local_path(&search, cell);
+                        hierarchy.links[portal].push(Link {
+                            to: other,
+                            cost,
+                            cells,
+                        });
+                    }
+                }
+            }
+        }
+        event!(
+            INFO,
+            portals = hierarchy.portals.len(),
+            "built grid hierarchy"
+        );
+        hierarchy
+    }
+}
+
+impl GridHierarchy {
+    /// Returns the width and height of the clusters, in cells.
+    pub fn cluster_size(&self) -> usize {
+        self.cluster_size
+    }
+
+    /// Returns the number of portals in the abstract graph.
+    pub fn portal_count(&self) -> usize {
+        self.portals.len()
+    }
+
+    /// Returns the ids of the cells where a short path from `from` to `to`
+    /// enters and leaves clusters, starting with `from` and ending with
+    /// `to`, and its cost, without working out the cells in between. Cheaper
+    /// than [`GridHierarchy::get_path`], e.g. to refine only the first leg
+    /// with [`GridHierarchy::get_path`] to the next waypoint.
+    ///
+    /// Fails if either cell does not exist, and with
+    /// [`GraphError::NoPathFound`] if either is a wall or no path is found.
+    pub fn get_abstract_path(
+        &self,
+        from: usize,
+        to: usize,
+    ) -> Result<(Vec<usize>, f64), GraphError> {
+        span!(DEBUG, "GridHierarchy::get_abstract_path", from, to);
+        let route = self.route(from, to)?;
+        let mut cells = vec![from];
+        cells.extend(route.portals.iter().map(|portal| self.portals[*portal]));
+        for pair in route.cells.windows(2) {
+            if self.cluster_of(pair[0]) != self.cluster_of(pair[1]) {
+                cells.extend(pair);
+            }
+        }
+        if to != from {
+            cells.push(to);
+        }
+        cells.dedup();
+        Ok((cells, route.cost))
+    }
+
+    /// Returns the ids of every cell along a short path from `from` to `to`,
+    /// and its cost, by refining the abstract path with the precomputed
+    /// paths inside each cluster.
+    ///
+    /// Fails if either cell does not exist, and with
+    /// [`GraphError::NoPathFound`] if either is a wall or no path is found.
+    pub fn get_path(&self, from: usize, to: usize) -> Result<(Vec<usize>, f64), GraphError> {
+        span!(DEBUG, "GridHierarchy::get_path", from, to);
+        let route = self.route(from, to)?;
+        let (Some(&first), Some(&last)) = (route.portals.first(), route.portals.last()) else {
+            return Ok((route.cells, route.cost));
+        };
+
+        let mut cells = vec![from];
+
+        cells.extend(self.local_path(&route.start, self.portals[first]));
+        for pair in route.portals.windows(2) {
+            let link = self.links[pair[0]]
+                .iter()
+                .filter(|link| link.to == pair[1])
+                .min_by(|a, b| a.cost.total_cmp(&b.cost))
+                .expect("consecutive portals are linked");
+            cells.extend(&link.cells);
+        }
+        // The goal search ran from `to`, so its path leads back to the portal.
+        let mut back = self.local_path(&route.goal, self.portals[last]);
+        back.reverse();
+        cells.extend(back.into_iter().skip(1));
+        cells.push(to);
+        cells.dedup();
+        Ok((cells, route.cost))
+    }
+
+    /// Like [`GridHierarchy::get_path`], but then searches again over the
+    /// cells of every cluster the path passes through, so that it crosses
+    /// borders wherever is cheapest instead of at the portals. The result
+    /// never costs more, but the search covers the whole corridor of
+    /// clusters rather than just the clusters of the endpoints.
+    ///
+    /// Fails as [`GridHierarchy::get_path`] does.
+    pub fn get_refined_path(
+        &self,
+        from: usize,
+        to: usize,
+    ) -> Result<(Vec<usize>, f64), GraphError> {
+        span!(DEBUG, "GridHierarchy::get_refined_path", from, to);
+        let (cells, cost) = self.get_path(from, to)?;
+        let corridor: HashSet<usize> = cells.iter().map(|cell| self.cluster_of(*cell)).collect();
+        Ok(self
+            .corridor_search(from, to, &corridor)
+            .filter(|(_, refined)| *refined < cost)
+            .unwrap_or((cells, cost)))
+    }
+
+    /// Finds the portals a short path from `from` to `to` passes through,
+    /// searching the abstract graph from the portals of the start cluster,
+    /// at their local distance from `from`, until no route through the
+    /// portals of the goal cluster can beat the best found so far, or the
+    /// path searched directly when both are in the same or neighbouring
+    /// clusters.
+    fn route(&self, from: usize, to: usize) -> Result<Route, GraphError> {
+        self.grid.check_endpoints(from, to)?;
+        let start = self.local_search(from);
+        let goal = self.local_search(to);
+
+        // Staying inside the clusters of the endpoints is a route too.
+        let (mut cells, mut best) = self
+            .neighbourhood(from, to)
+            .and_then(|clusters| self.corridor_search(from, to, &clusters))
+            .unwrap_or((Vec::new(), f64::INFINITY));
+        let mut best_end = None;
+        let mut dist = vec![f64::INFINITY; self.portals.len()];
+        let mut prev: Vec<Option<usize>> = vec![None; self.portals.len()];
+        let mut queue = BinaryHeap::new();
+        for &portal in &self.cluster_portals[self.cluster_of(from)] {
+            let cost = self.local_dist(&start, self.portals[portal]);
+            if cost < dist[portal] {
+                dist[portal] = cost;
+                queue.push((
+                    MinNonNan(cost + self.estimate(self.portals[portal], to)),
+                    portal,
+                ));
+            }
+        }
+        let goal_cluster = self.cluster_of(to);
+
+        while let Some((MinNonNan(estimate), current)) = queue.pop() {
+            if estimate >= best {
+                break;
+            }
+            let cell = self.portals[current];
+            if estimate > dist[current] + self.estimate(cell, to) {
+                continue;
+            }
+            if self.cluster_of(cell) == goal_cluster {
+                let total = dist[current] + self.local_dist(&goal, cell);
+                if total < best {
+                    best = total;
+                    best_end = Some(current);
+                }
+            }
+            for link in &self.links[current] {
+                let new_dist = dist[current] + link.cost;
+                if new_dist < dist[link.to] {
+                    dist[link.to] = new_dist;
+                    prev[link.to] = Some(current);
+                    queue.push((
+                        MinNonNan(new_dist + self.estimate(self.portals[link.to], to)),
+                        link.to,
+                    ));
+                }
+            }
+        }
+        if best == f64::INFINITY {
+            return Err(GraphError::NoPathFound { from, to });
+        }
+
+        let mut portals = Vec::new();
+        let mut at = best_end;
+        while let Some(portal) = at {
+            portals.push(portal);
+            at = prev[portal];
+        }
+        portals.reverse();
+        if !portals.is_empty() {
+            cells.clear();
+        }
+        Ok(Route {
+            start,
+            goal,
+            portals,
+            cells,
+            cost: best,
+        })
+    }
+
+    /// Estimates the cost from `cell` to `to`, never overestimating it.
+    fn estimate(&self, cell: usize, to: usize) -> f64 {
+        let (position, target) = (self.grid.position(cell), self.grid.position(to));
+        match self.connectivity {
+            Connectivity::FourWay => {
+                ((position.0 - target.0).abs() + (position.1 - target.1).abs()) as f64
+            }
+            Connectivity::EightWay => octile(position, target),
+        }
+    }
+
+    /// Returns the clusters covering both the cluster of `from` and that of
+    /// `to` if they are the same or touch, even only at a corner.
+    fn neighbourhood(&self, from: usize, to: usize) -> Option<HashSet<usize>> {
+        let (from_row, from_col) = self.grid.cell(from);
+        let (to_row, to_col) = self.grid.cell(to);
+        let rows = [from_row / self.cluster_size, to_row / self.cluster_size];
+        let cols = [from_col / self.cluster_size, to_col / self.cluster_size];
+        if rows[0].abs_diff(rows[1]) > 1 || cols[0].abs_diff(cols[1]) > 1 {
+            return None;
+        }
+
+        let (_, cluster_cols) = self.cluster_dimensions();
+        let mut clusters = HashSet::new();
+        for row in rows[0].min(rows[1])..=rows[0].max(rows[1]) {
+            for col in cols[0].min(cols[1])..=cols[0].max(cols[1]) {
+                clusters.insert(row * cluster_cols + col);
+            }
+        }
+        Some(clusters)
+    }
+
+    /// Searches for the cheapest path from `from` to `to` with A*, only
+    /// stepping onto cells in `clusters`, and returns its cells, starting
+    /// with `from`, and its cost.
+    fn corridor_search(
+        &self,
+        from: usize,
+        to: usize,
+        clusters: &HashSet<usize>,
+    ) -> Option<(Vec<usize>, f64)> {
+        let steps = self.steps();
+        let mut dist = HashMap::from([(from, 0.0)]);
+        let mut prev = HashMap::new();
+        let mut queue = BinaryHeap::from([(MinNonNan(self.estimate(from, to)), from)]);
+        while let Some((MinNonNan(estimate), current)) = queue.pop() {
+            let cost = dist[&current];
+            if current == to {
+                let mut cells = vec![to];
+                let mut at = to;
+                while let Some(&before) = prev.get(&at) {
+                    cells.push(before);
+                    at = before;
+                }
+                cells.reverse();
+                return Some((cells, cost));
+            }
+            if estimate > cost + self.estimate(current, to) {
+                continue;
+            }
+            let position = self.grid.position(current);
+            for &(step, step_cost) in &steps {
+                if !self.grid.can_step(position, step) {
+                    continue;
+                }
+                let next = self.grid.id(
+                    (position.0 + step.0) as usize,
+                    (position.1 + step.1) as usize,
+                );
+                let new_cost = cost + step_cost;
+                if clusters.contains(&self.cluster_of(next))
+                    && dist.get(&next).is_none_or(|known| new_cost < *known)
+                {
+                    dist.insert(next, new_cost);
+                    prev.insert(next, current);
+                    queue.push((MinNonNan(new_cost + self.estimate(next, to)), next));
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the steps between cells and their costs.
+    fn steps(&self) -> Vec<((isize, isize), f64)> {
+        match self.connectivity {
+            Connectivity::FourWay => ORTHOGONAL.iter().map(|step| (*step, 1.0)).collect(),
+            Connectivity::EightWay => ORTHOGONAL
+                .iter()
+                .map(|step| (*step, 1.0))
+                .chain(DIAGONAL.iter().map(|step| (*step, SQRT_2)))
+                .collect(),
+        }
+    }
+
+    /// Returns how many clusters there are down and across.
+    fn cluster_dimensions(&self) -> (usize, usize) {
+        (
+            self.grid.height().div_ceil(self.cluster_size),
+            self.grid.width().div_ceil(self.cluster_size),
+        )
+    }
+
+    /// Returns the rows or columns, below `limit`, of the clusters at `index`
+    /// down or across.
+    fn span(&self, index: usize, limit: usize) -> Range<usize> {
+        index * self.cluster_size..((index + 1) * self.cluster_size).min(limit)
+    }
+
+    fn cluster_of(&self, cell: usize) -> usize {
+        let (row, col) = self.grid.cell(cell);
+        let (_, cluster_cols) = self.cluster_dimensions();
+        row / self.cluster_size * cluster_cols + col / self.cluster_size
+    }
+
+    /// Adds transitions for the runs of open cell pairs along the border
+    /// between two clusters, given as pairs of facing cells.
+    fn add_entrances(&mut self, border: Vec<((usize, usize), (usize, usize))>) {
+        let open = |(row, col): (usize, usize)| self.grid.is_walkable(row as isize, col as isize);
+        let mut runs: Vec<Vec<_>> = Vec::new();
+        let mut in_run = false;
+        for pair in border {
+            match open(pair.0) && open(pair.1) {
+                true if in_run => runs.last_mut().unwrap().push(pair),
+                true => runs.push(vec![pair]),
+                false => {}
+            }
+            in_run = open(pair.0) && open(pair.1);
+        }
+
+        for run in runs {
+            let transitions = if run.len() >= LONG_ENTRANCE {
+                vec![run[0], run[run.len() - 1]]
+            } else {
+                vec![run[run.len() / 2]]
+            };
+            for (inside, outside) in transitions {
+                let a = self.portal(self.grid.id(inside.0, inside.1));
+                let b = self.portal(self.grid.id(outside.0, outside.1));
+                for (from, to) in [(a, b), (b, a)] {
+                    let cells = vec![self.portals[to]];
+                    self.links[from].push(Link {
+                        to,
+                        cost: 1.0,
+                        cells,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Returns the index of the portal at `cell`, adding it if needed.
+    fn portal(&mut self, cell: usize) -> usize {
+        if let Some(portal) = self.portal_at.get(&cell) {
+            return *portal;
+        }
+        let portal = self.portals.len();
+        self.portals.push(cell);
+        self.portal_at.insert(cell, portal);
+        self.links.push(Vec::new());
+        let cluster = self.cluster_of(cell);
+        self.cluster_portals[cluster].push(portal);
+        portal
+    }
+
+    /// Searches the cluster of `source` from it with Dijkstra.
+    fn local_search(&self, source: usize) -> LocalSearch {
+        let (row, col) = self.grid.cell(source);
+        let rows = self.span(row / self.cluster_size, self.grid.height());
+        let cols = self.span(col / self.cluster_size, self.grid.width());
+        let cells = rows.len() * cols.len();
+        let mut search = LocalSearch {
+            rows,
+            cols,
+            dist: vec![f64::INFINITY; cells],
+            prev: vec![usize::MAX; cells],
+        };
+        let steps = self.steps();
+
+        let mut queue = BinaryHeap::new();
+        let index = search.index((row, col)).unwrap();
+        search.dist[index] = 0.0;
+        queue.push((MinNonNan(0.0), source));
+        while let Some((MinNonNan(cost), current)) = queue.pop() {
+            let position = self.grid.position(current);
+            if cost > search.dist[search.index(self.grid.cell(current)).unwrap()] {
+                continue;
+            }
+            for &(step, step_cost) in &steps {
+                if !self.grid.can_step(position, step) {
+                    continue;
+                }
+                let next = (
+                    (position.0 + step.0) as usize,
+                    (position.1 + step.1) as usize,
+                );
+                let Some(index) = search.index(next) else {
+                    continue;
+                };
+                if cost + step_cost < search.dist[index] {
+                    search.dist[index] = cost + step_cost;
+                    search.prev[index] = current;
+                    queue.push((MinNonNan(cost + step_cost), self.grid.id(next.0, next.1)));
+                }
+            }
+        }
+        search
+    }
+
+    /// Distance of `cell` from the source of `search`, infinite if it lies
+    /// outside the searched cluster or was not reached.
+    fn local_dist(&self, search: &LocalSearch, cell: usize) -> f64 {
+        search
+            .index(self.grid.cell(cell))
+            .map_or(f64::INFINITY, |index| search.dist[index])
+    }
+
+    /// Cells after the source of `search` up to and including `cell`, which
+    /// must have been reached.
+    fn local_path(&self, search: &LocalSearch, cell: usize) -> Vec<usize> {
+        let mut cells = Vec::new();
+        let mut at = cell;
+        while at != usize::MAX {
+            cells.push(at);
+            at = search.prev[search.index(self.grid.cell(at)).unwrap()];
+        }
+        cells.pop();
+        cells.reverse();
+        cells
+    }
+}
+
+/// The outcome of [`GridHierarchy::route`].
+struct Route {
+    /// Local search of the start cluster from `from`.
+    start: LocalSearch,
+    /// Local search of the goal cluster from `to`.
+    goal: LocalSearch,
+    /// Portals along the way, empty if the path was searched directly.
+    portals: Vec<usize>,
+    /// Cells of the path searched directly, starting with `from`, or empty
+    /// if it goes through portals.
+    cells: Vec<usize>,
+    cost: f64,
+}
@@ -0,0 +1,117 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Number of children of each heap node. Four keeps the tree shallow while
+/// the children still share a cache line or two.
+const ARITY: usize = 4;
+
+/// A min-heap of ids keyed by cost that lowers the key of a queued id in
+/// place, so that every id is queued at most once.
+///
+/// Ties are broken towards the larger id.
+pub(crate) struct IndexedHeap<W> {
+    entries: Vec<(W, usize)>,
+    /// Position of each id in `entries`, `usize::MAX` if it is not queued.
+    positions: Vec<usize>,
+}
+
+impl<W: PartialOrd + Copy> IndexedHeap<W> {
+    /// Creates an empty heap for ids below `ids`.
+    pub(crate) fn new(ids: usize) -> Self {
+        IndexedHeap {
+            entries: Vec::new(),
+            positions: vec![usize::MAX; ids],
+        }
+    }
+
+    /// Empties the heap and makes room for ids below `ids`, in time linear
+    /// in the number of ids queued rather than the number of ids.
+    pub(crate) fn reset(&mut self, ids: usize) {
+        for (_, id) in self.entries.drain(..) {
+            self.positions[id] = usize::MAX;
+        }
+        if self.positions.len() < ids {
+            self.positions.resize(ids, usize::MAX);
+        }
+    }
+
+    /// Queues `id` under `key`, or lowers its key if it is queued under a
+    /// larger one.
+    pub(crate) fn push_or_decrease(&mut self, id: usize, key: W) {
+        let position = match self.positions[id] {
+            usize::MAX => {
+                self.entries.push((key, id));
+                self.entries.len() - 1
+            }
+            position if key < self.entries[position].0 => {
+                self.entries[position].0 = key;
+                position
+            }
+            _ => return,
+        };
+        self.sift_up(position);
+    }
+
+    /// Removes and returns the id with the smallest key, with its key.
+    pub(crate) fn pop(&mut self) -> Option<(W, usize)> {
+        let last = self.entries.pop()?;
+        let top = match self.entries.first_mut() {
+            Some(first) => core::mem::replace(first, last),
+            None => last,
+        };
+        self.positions[top.1] = usize::MAX;
+        if !self.entries.is_empty() {
+            self.sift_down(0);
+        }
+        Some(top)
+    }
+
+    /// Returns the queued ids with their keys, in no particular order.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (W, usize)> + '_ {
+        self.entries.iter().copied()
+    }
+
+    fn before(a: &(W, usize), b: &(W, usize)) -> bool {
+        a.0 < b.0 || (a.0 == b.0 && a.1 > b.1)
+    }
+
+    fn sift_up(&mut self, mut position: usize) {
+        let entry = self.entries[position];
+        while position > 0 {
+            let parent = (position - 1) / ARITY;
+            if !Self::before(&entry, &self.entries[parent]) {
+                break;
+            }
+            self.entries[position] = self.entries[parent];
+            self.positions[self.entries[position].1] = position;
+            position = parent;
+        }
+        self.entries[position] = entry;
+        self.positions[entry.1] = position;
+    }
+
+    fn sift_down(&mut self, mut position: usize) {
+        let entry = self.entries[position];
+        loop {
+            let first_child = position * ARITY + 1;
+            let children = first_child..(first_child + ARITY).min(self.entries.len());
+            let Some(child) = children.reduce(|best, child| {
+                if Self::before(&self.entries[child], &self.entries[best]) {
+                    child
+                } else {
+                    best
+                }
+            }) else {
+                break;
+            };
+            if !Self::before(&self.entries[child], &entry) {
+                break;
+            }
+            self.entries[position] = self.entries[child];
+            self.positions[self.entries[position].1] = position;
+            position = child;
+        }
+        self.entries[position] = entry;
+        self.positions[entry.1] = position;
+    }
+}
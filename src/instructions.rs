@@ -0,0 +1,225 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::{EdgeGeometry, LatLon, Path};
+
+/// Edge payloads that can name the street an edge belongs to, for
+/// [`Path::instructions`].
+///
+/// Implemented for [`StreetSegment`], `String`, `&str` and `Option<String>`
+/// names, and for `()`, whose edges are unnamed.
+pub trait StreetName {
+    /// Returns the name of the street, or `None` if it has none.
+    fn street_name(&self) -> Option<&str>;
+}
+
+impl StreetName for () {
+    fn street_name(&self) -> Option<&str> {
+        None
+    }
+}
+
+impl StreetName for String {
+    fn street_name(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+
+impl StreetName for &str {
+    fn street_name(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+
+impl StreetName for Option<String> {
+    fn street_name(&self) -> Option<&str> {
+        self.as_deref()
+    }
+}
+
+/// A ready-made edge payload for road networks with turn-by-turn
+/// directions, holding a street name and a shape, both optional.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StreetSegment {
+    pub name: Option<String>,
+    /// Points along the edge, see [`EdgeGeometry::polyline`].
+    pub polyline: Option<Vec<LatLon>>,
+}
+
+impl StreetSegment {
+    /// Creates a straight segment of the named street.
+    pub fn named(name: &str) -> Self {
+        StreetSegment {
+            name: Some(String::from(name)),
+            polyline: None,
+        }
+    }
+}
+
+impl StreetName for StreetSegment {
+    fn street_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
+impl EdgeGeometry for StreetSegment {
+    fn polyline(&self) -> Option<&[LatLon]> {
+        self.polyline.as_deref()
+    }
+}
+
+/// What to do at the start of an [`Instruction`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Maneuver {
+    /// Set out from the first vertex.
+    Depart,
+    /// Go on roughly straight ahead, onto a differently named street.
+    Continue,
+    SlightLeft,
+    Left,
+    SharpLeft,
+    SlightRight,
+    Right,
+    SharpRight,
+    UTurn,
+    /// Reach the last vertex.
+    Arrive,
+}
+
+impl Maneuver {
+    /// Classifies a change of heading by `angle` degrees, positive to the
+    /// right, in `-180.0..=180.0`.
+    fn from_turn(angle: f64) -> Maneuver {
+        match angle {
+            a if a.abs() < 20.0 => Maneuver::Continue,
+            a if a.abs() >= 170.0 => Maneuver::UTurn,
+            a if a >= 120.0 => Maneuver::SharpRight,
+            a if a >= 60.0 => Maneuver::Right,
+            a if a > 0.0 => Maneuver::SlightRight,
+            a if a <= -120.0 => Maneuver::SharpLeft,
+            a if a <= -60.0 => Maneuver::Left,
+            _ => Maneuver::SlightLeft,
+        }
+    }
+}
+
+/// One step of turn-by-turn directions along a path, from
+/// [`Path::instructions`]: a maneuver at a vertex, then a stretch along one
+/// street up to the next instruction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Instruction {
+    maneuver: Maneuver,
+    street: Option<String>,
+    distance: f64,
+    vertex: usize,
+}
+
+impl Instruction {
+    pub fn maneuver(&self) -> Maneuver {
+        self.maneuver
+    }
+
+    /// Returns the name of the street followed after the maneuver, if known.
+    pub fn street(&self) -> Option<&str> {
+        self.street.as_deref()
+    }
+
+    /// Returns the distance in metres to the next instruction, zero on
+    /// arrival.
+    pub fn distance(&self) -> f64 {
+        self.distance
+    }
+
+    /// Returns the id of the vertex where the maneuver happens.
+    pub fn vertex(&self) -> usize {
+        self.vertex
+    }
+}
+
+/// Renders the instruction in English, e.g. "Turn left onto Main Street for
+/// 250 m".
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let (verb, preposition) = match self.maneuver {
+            Maneuver::Depart => ("Head off", "on"),
+            Maneuver::Continue => ("Continue", "on"),
+            Maneuver::SlightLeft => ("Bear left", "onto"),
+            Maneuver::Left => ("Turn left", "onto"),
+            Maneuver::SharpLeft => ("Turn sharp left", "onto"),
+            Maneuver::SlightRight => ("Bear right", "onto"),
+            Maneuver::Right => ("Turn right", "onto"),
+            Maneuver::SharpRight => ("Turn sharp right", "onto"),
+            Maneuver::UTurn => ("Make a U-turn", "onto"),
+            Maneuver::Arrive => return write!(f, "Arrive at your destination"),
+        };
+        write!(f, "{}", verb)?;
+        if let Some(street) = &self.street {
+            write!(f, " {} {}", preposition, street)?;
+        }
+        write!(f, " for {:.0} m", self.distance)
+    }
+}
+
+impl<W, E: EdgeGeometry + StreetName> Path<LatLon, W, E> {
+    /// Turns the path into turn-by-turn directions: a departure, a maneuver
+    /// wherever the street name changes or the path turns by more than a
+    /// slight bend, and an arrival. Turns are told from the bearings of the
+    /// step [geometry](Path::geometry) on either side of a vertex, and
+    /// distances are measured along it in metres, whatever the edges cost.
+    ///
+    /// A path of a single vertex gives just the arrival.
+    pub fn instructions(&self) -> Vec<Instruction> {
+        let mut instructions: Vec<Instruction> = Vec::new();
+        // Bearing at which the previous step entered its last vertex.
+        let mut heading: Option<f64> = None;
+        for step in 0..self.costs().len() {
+            let shape = self.step_geometry(step);
+            let length: f64 = shape.windows(2).map(|s| s[0].distance_to(&s[1])).sum();
+            let street = self.data().get(step).and_then(StreetName::street_name);
+            let (leaving, entering) = match shape.len() {
+                0 | 1 => (heading, heading),
+                n => (
+                    Some(shape[0].bearing_to(&shape[1])),
+                    Some(shape[n - 2].bearing_to(&shape[n - 1])),
+                ),
+            };
+
+            let turn = match (heading, leaving) {
+                (Some(before), Some(after)) => {
+                    Maneuver::from_turn((after - before + 540.0).rem_euclid(360.0) - 180.0)
+                }
+                _ => Maneuver::Continue,
+            };
+            let bend = matches!(
+                turn,
+                Maneuver::Continue | Maneuver::SlightLeft | Maneuver::SlightRight
+            );
+            match instructions.last_mut() {
+                Some(current) if bend && current.street() == street => current.distance += length,
+                current => {
+                    let maneuver = if current.is_none() {
+                        Maneuver::Depart
+                    } else {
+                        turn
+                    };
+                    instructions.push(Instruction {
+                        maneuver,
+                        street: street.map(String::from),
+                        distance: length,
+                        vertex: self.vertices()[step].id,
+                    });
+                }
+            }
+            heading = entering;
+        }
+
+        if let Some(last) = self.vertices().last() {
+            instructions.push(Instruction {
+                maneuver: Maneuver::Arrive,
+                street: None,
+                distance: 0.0,
+                vertex: last.id,
+            });
+        }
+        instructions
+    }
+}
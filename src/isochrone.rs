@@ -0,0 +1,37 @@
+use crate::{Graph, GraphError};
+
+impl<T> Graph<T>
+where
+    T: Clone,
+{
+    /// Returns every vertex whose shortest path cost from `source` is at most
+    /// `max_cost`, with that cost, in increasing order of cost. Unreachable
+    /// vertices are left out even if `max_cost` is infinite.
+    pub fn reachable_within(
+        &self,
+        source: usize,
+        max_cost: f64,
+    ) -> Result<Vec<(usize, f64)>, GraphError> {
+        if max_cost.is_nan() {
+            return Err(GraphError::InvalidCost(max_cost));
+        }
+        self.get_vertex(source)?;
+
+        let (dist, _, _) = self.search_until(
+            &[(source, 0.0)],
+            |_, distance| distance > max_cost,
+            |_| 0.0,
+            |_, _| true,
+        );
+
+        let mut reachable: Vec<(usize, f64)> = dist
+            .into_iter()
+            .enumerate()
+            .filter(|(id, distance)| {
+                *distance <= max_cost && *distance != f64::INFINITY && self.vertices[*id].is_some()
+            })
+            .collect();
+        reachable.sort_by(|a, b| a.1.total_cmp(&b.1));
+        Ok(reachable)
+    }
+}
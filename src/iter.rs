@@ -0,0 +1,81 @@
+use core::iter::{Flatten, FusedIterator};
+use core::slice;
+
+use crate::{Edge, Graph, GraphError, Vertex, Weight};
+
+/// Iterator over the vertices of a graph in ascending order of id, skipping
+/// removed ids.
+///
+/// Returned by [`Graph::vertices`].
+#[derive(Debug, Clone)]
+pub struct Vertices<'a, T> {
+    inner: Flatten<slice::Iter<'a, Option<Vertex<T>>>>,
+}
+
+impl<'a, T> Iterator for Vertices<'a, T> {
+    type Item = &'a Vertex<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+impl<T> DoubleEndedIterator for Vertices<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> FusedIterator for Vertices<'_, T> {}
+
+impl<T, W, E> Graph<T, W, E>
+where
+    T: Clone,
+    W: Weight,
+    E: Clone,
+{
+    /// Returns an iterator over the vertices in ascending order of id.
+    pub fn vertices(&self) -> Vertices<'_, T> {
+        Vertices {
+            inner: self.vertices.iter().flatten(),
+        }
+    }
+
+    /// Returns an iterator over every edge as `(from, edge)`, where `from` is
+    /// the id of the vertex the edge was added from. Undirected edges appear
+    /// once, from the first vertex they were added with.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, &Edge<W, E>)> + '_ {
+        self.vertices().flat_map(|vertex| {
+            self.adjacency[vertex.id]
+                .iter()
+                .map(move |edge| (vertex.id, edge))
+        })
+    }
+
+    /// Returns an iterator over the edges that can be traversed out of `id`,
+    /// whose `to` is the neighbour they lead to. Undirected edges added from
+    /// the other end are included, pointing at it.
+    ///
+    /// Fails if the vertex is unknown.
+    pub fn neighbors(
+        &self,
+        id: usize,
+    ) -> Result<impl Iterator<Item = &Edge<W, E>> + '_, GraphError> {
+        self.get_vertex(id)?;
+        Ok(self.outgoing(id))
+    }
+}
+
+impl<'a, T, W, E> IntoIterator for &'a Graph<T, W, E>
+where
+    T: Clone,
+    W: Weight,
+    E: Clone,
+{
+    type Item = &'a Vertex<T>;
+    type IntoIter = Vertices<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.vertices()
+    }
+}
@@ -0,0 +1,44 @@
+use crate::{AllPairsShortestPaths, Graph, GraphError};
+
+impl<T> Graph<T>
+where
+    T: Clone,
+{
+    /// Computes shortest paths between all pairs of vertices with Johnson's
+    /// algorithm, which is faster than
+    /// [`Graph::all_pairs_shortest_paths`] on sparse graphs. Negative edge
+    /// costs are supported.
+    ///
+    /// Fails with [`GraphError::NegativeCycle`] if the graph has a negative cycle.
+    pub fn all_pairs_shortest_paths_johnson(&self) -> Result<AllPairsShortestPaths, GraphError> {
+        // Starting every vertex at zero is equivalent to relaxing from a
+        // virtual source with zero-cost edges to all of them.
+        let (potential, _) = self.bellman_ford(vec![0.0; self.vertices.len()])?;
+
+        // Searching with the negated potential as heuristic is Dijkstra on
+        // the reweighted, non-negative costs, but yields original distances.
+        let n = self.vertices.len();
+        let mut distances = Vec::with_capacity(n);
+        let mut predecessors = Vec::with_capacity(n);
+        for vertex in &self.vertices {
+            let Some(vertex) = vertex else {
+                distances.push(vec![f64::INFINITY; n]);
+                predecessors.push(vec![None; n]);
+                continue;
+            };
+
+            let (dist, prev) = self.search(vertex.id, None, |v| -potential[v.id]);
+            distances.push(dist);
+            predecessors.push(
+                prev.into_iter()
+                    .map(|id| id.map(|id| id as usize))
+                    .collect(),
+            );
+        }
+
+        Ok(AllPairsShortestPaths {
+            distances,
+            predecessors,
+        })
+    }
+}
@@ -0,0 +1,164 @@
+use std::collections::BinaryHeap;
+use std::f64::consts::SQRT_2;
+
+use crate::min_non_nan::MinNonNan;
+use crate::{GraphError, Grid};
+
+/// Octile distance between two cells of an eight-way grid.
+pub(crate) fn octile(from: (isize, isize), to: (isize, isize)) -> f64 {
+    let (dr, dc) = ((from.0 - to.0).abs(), (from.1 - to.1).abs());
+    let (short, long) = (dr.min(dc), dr.max(dc));
+    short as f64 * SQRT_2 + (long - short) as f64
+}
+
+impl Grid {
+    /// Finds the cheapest eight-way path between two walkable cells,
+    /// where straight steps cost 1 and diagonal steps √2, using Jump Point
+    /// Search. Diagonal steps may not cut the corner of a wall, as in
+    /// [`Graph::from_grid`](crate::Graph::from_grid).
+    ///
+    /// Returns the ids of every cell along the path and its cost.
+    pub fn jump_point_search(
+        &self,
+        from: usize,
+        to: usize,
+    ) -> Result<(Vec<usize>, f64), GraphError> {
+        self.check_endpoints(from, to)?;
+        let cells = self.width() * self.height();
+        let (start, goal) = (self.position(from), self.position(to));
+
+        let mut dist = vec![f64::INFINITY; cells];
+        let mut prev: Vec<Option<usize>> = vec![None; cells];
+        let mut closed = vec![false; cells];
+        let mut open = BinaryHeap::new();
+        dist[from] = 0.0;
+        open.push((MinNonNan(octile(start, goal)), from));
+
+        while let Some((_, current)) = open.pop() {
+            if closed[current] {
+                continue;
+            }
+            closed[current] = true;
+            if current == to {
+                return Ok((self.expand(&prev, from, to), dist[to]));
+            }
+
+            let position = self.position(current);
+            let parent = prev[current].map(|parent| self.position(parent));
+            for direction in self.pruned_directions(position, parent) {
+                let Some(jump) = self.jump(position, direction, goal) else {
+                    continue;
+                };
+                let id = self.id(jump.0 as usize, jump.1 as usize);
+                if closed[id] {
+                    continue;
+                }
+
+                let new_dist = dist[current] + octile(position, jump);
+                if new_dist < dist[id] {
+                    dist[id] = new_dist;
+                    prev[id] = Some(current);
+                    open.push((MinNonNan(new_dist + octile(jump, goal)), id));
+                }
+            }
+        }
+
+        Err(GraphError::NoPathFound { from, to })
+    }
+
+    /// Directions worth exploring from `at` when it was reached from `parent`.
+    fn pruned_directions(
+        &self,
+        at: (isize, isize),
+        parent: Option<(isize, isize)>,
+    ) -> Vec<(isize, isize)> {
+        let (r, c) = at;
+        let Some(parent) = parent else {
+            return [-1, 0, 1]
+                .iter()
+                .flat_map(|dr| [-1, 0, 1].iter().map(move |dc| (*dr, *dc)))
+                .filter(|(dr, dc)| (*dr, *dc) != (0, 0))
+                .filter(|(dr, dc)| self.can_step(at, (*dr, *dc)))
+                .collect();
+        };
+
+        let (dr, dc) = ((r - parent.0).signum(), (c - parent.1).signum());
+        let mut directions = Vec::with_capacity(5);
+        if dr != 0 && dc != 0 {
+            directions.extend([(dr, 0), (0, dc), (dr, dc)]);
+        } else if dr != 0 {
+            directions.extend([(dr, 0), (dr, -1), (dr, 1), (0, -1), (0, 1)]);
+        } else {
+            directions.extend([(0, dc), (-1, dc), (1, dc), (-1, 0), (1, 0)]);
+        }
+        directions.retain(|direction| self.can_step(at, *direction));
+        directions
+    }
+
+    /// Moves from `from` in `direction` until reaching the goal, a cell with a
+    /// forced neighbour, or a dead end.
+    fn jump(
+        &self,
+        from: (isize, isize),
+        (dr, dc): (isize, isize),
+        goal: (isize, isize),
+    ) -> Option<(isize, isize)> {
+        let (mut r, mut c) = (from.0 + dr, from.1 + dc);
+        loop {
+            if !self.open((r, c)) {
+                return None;
+            }
+            if (r, c) == goal {
+                return Some((r, c));
+            }
+
+            if dr != 0 && dc != 0 {
+                if self.jump((r, c), (dr, 0), goal).is_some()
+                    || self.jump((r, c), (0, dc), goal).is_some()
+                {
+                    return Some((r, c));
+                }
+            } else if dr != 0 {
+                if (self.open((r, c - 1)) && !self.open((r - dr, c - 1)))
+                    || (self.open((r, c + 1)) && !self.open((r - dr, c + 1)))
+                {
+                    return Some((r, c));
+                }
+            } else if (self.open((r - 1, c)) && !self.open((r - 1, c - dc)))
+                || (self.open((r + 1, c)) && !self.open((r + 1, c - dc)))
+            {
+                return Some((r, c));
+            }
+
+            if !self.can_step((r, c), (dr, dc)) {
+                return None;
+            }
+            r += dr;
+            c += dc;
+        }
+    }
+
+    /// Fills in the cells between consecutive jump points.
+    fn expand(&self, prev: &[Option<usize>], from: usize, to: usize) -> Vec<usize> {
+        let mut jump_points = vec![to];
+        let mut at = to;
+        while at != from {
+            at = prev[at].unwrap();
+            jump_points.push(at);
+        }
+        jump_points.reverse();
+
+        let mut path = vec![from];
+        for pair in jump_points.windows(2) {
+            let (mut r, mut c) = self.position(pair[0]);
+            let target = self.position(pair[1]);
+            let (dr, dc) = ((target.0 - r).signum(), (target.1 - c).signum());
+            while (r, c) != target {
+                r += dr;
+                c += dc;
+                path.push(self.id(r as usize, c as usize));
+            }
+        }
+        path
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::{Graph, GraphError, Weight};
+
+/// A [`Graph`] whose vertices are created and referred to by a key, such as
+/// a name, instead of the id returned from [`Graph::add_vertex`].
+///
+/// Adding an edge between keys that have no vertex yet creates them, with
+/// the key converted into the vertex value. The underlying graph is
+/// available through [`KeyedGraph::graph`] for every other algorithm, with
+/// [`KeyedGraph::id`] translating keys to ids.
+pub struct KeyedGraph<K, T = K, W = f64> {
+    graph: Graph<T, W>,
+    ids: HashMap<K, usize>,
+    /// Key of each vertex, indexed by id.
+    keys: Vec<K>,
+}
+
+impl<K, T, W> Default for KeyedGraph<K, T, W>
+where
+    K: Hash + Eq + Clone,
+    T: Clone,
+    W: Weight,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, T, W> KeyedGraph<K, T, W>
+where
+    K: Hash + Eq + Clone,
+    T: Clone,
+    W: Weight,
+{
+    /// Creates an empty graph.
+    pub fn new() -> Self {
+        KeyedGraph {
+            graph: Graph::new(),
+            ids: HashMap::new(),
+            keys: Vec::new(),
+        }
+    }
+
+    pub fn graph(&self) -> &Graph<T, W> {
+        &self.graph
+    }
+
+    /// Consumes the keyed graph and returns the underlying graph.
+    pub fn into_graph(self) -> Graph<T, W> {
+        self.graph
+    }
+
+    /// Returns the id of the vertex with the given key.
+    pub fn id(&self, key: &K) -> Option<usize> {
+        self.ids.get(key).copied()
+    }
+
+    /// Returns the key of the vertex with the given id.
+    pub fn key(&self, id: usize) -> Option<&K> {
+        self.keys.get(id)
+    }
+
+    /// Adds a vertex holding `value` under `key`, or replaces the value if the
+    /// key already has a vertex, and returns its id.
+    pub fn add_vertex(&mut self, key: K, value: T) -> usize {
+        if let Some(id) = self.id(&key) {
+            self.graph.vertices[id].as_mut().unwrap().value = value;
+            self.graph.touch();
+            return id;
+        }
+        let id = self.graph.add_vertex(value);
+        self.ids.insert(key.clone(), id);
+        self.keys.push(key);
+        id
+    }
+
+    /// Adds a directed edge from `from` to `to` with the given cost, creating
+    /// missing vertices.
+    ///
+    /// Fails if the cost is NaN.
+    pub fn add_edge(&mut self, from: K, to: K, cost: W) -> Result<(), GraphError>
+    where
+        T: From<K>,
+    {
+        let from = self.vertex_for(from);
+        let to = self.vertex_for(to);
+        self.graph.add_edge(from, to, cost)
+    }
+
+    /// Adds an edge between `a` and `b` that can be traversed in both
+    /// directions with the given cost, creating missing vertices.
+    ///
+    /// Fails if the cost is NaN.
+    pub fn add_edge_undirected(&mut self, a: K, b: K, cost: W) -> Result<(), GraphError>
+    where
+        T: From<K>,
+    {
+        let a = self.vertex_for(a);
+        let b = self.vertex_for(b);
+        self.graph.add_edge_undirected(a, b, cost)
+    }
+
+    /// Returns the keys along the cheapest path from `from` to `to` together
+    /// with its total cost.
+    ///
+    /// Fails if either key has no vertex or `to` is not reachable from `from`.
+    pub fn get_shortest_path(&self, from: &K, to: &K) -> Result<(Vec<K>, W), GraphError> {
+        let from = self.id(from).ok_or(GraphError::UnknownKey)?;
+        let to = self.id(to).ok_or(GraphError::UnknownKey)?;
+        let path = self.graph.get_shortest_path(from, to)?;
+        let keys = path
+            .iter()
+            .map(|vertex| self.keys[vertex.id].clone())
+            .collect();
+        Ok((keys, path.cost()))
+    }
+
+    /// Returns the id of the vertex with the given key, creating it with the
+    /// key as its value if there is none.
+    fn vertex_for(&mut self, key: K) -> usize
+    where
+        T: From<K>,
+    {
+        match self.id(&key) {
+            Some(id) => id,
+            None => self.add_vertex(key.clone(), T::from(key)),
+        }
+    }
+}
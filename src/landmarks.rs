@@ -0,0 +1,160 @@
+use std::ops::ControlFlow;
+
+use crate::progress::Progress;
+use crate::{Graph, GraphError, Heuristic, Vertex};
+
+/// Precomputed distances from and to a few landmark vertices, giving an
+/// admissible and consistent A* heuristic through the triangle inequality.
+///
+/// Built with [`Graph::build_landmarks`] and reusable across queries on the
+/// same graph; pass `&landmarks` as the heuristic of
+/// [`Graph::get_shortest_path_astar`]. Tables go stale when the graph
+/// changes.
+pub struct Landmarks {
+    landmarks: Vec<usize>,
+    /// Distance from each landmark to every vertex, indexed by landmark.
+    from_landmark: Vec<Option<Vec<f64>>>,
+    /// Distance from every vertex to each landmark, indexed by landmark.
+    to_landmark: Vec<Option<Vec<f64>>>,
+}
+
+impl Landmarks {
+    /// Ids of the chosen landmarks.
+    pub fn landmarks(&self) -> &[usize] {
+        &self.landmarks
+    }
+
+    /// Lower bound on the cost of the cheapest path from `from` to `to`.
+    pub fn lower_bound(&self, from: usize, to: usize) -> f64 {
+        // d(L, to) <= d(L, from) + d(from, to) and
+        // d(from, L) <= d(from, to) + d(to, L).
+        let differences = self
+            .from_landmark
+            .iter()
+            .flatten()
+            .map(|dist| (dist.get(to), dist.get(from)))
+            .chain(
+                self.to_landmark
+                    .iter()
+                    .flatten()
+                    .map(|dist| (dist.get(from), dist.get(to))),
+            );
+
+        let mut bound: f64 = 0.0;
+        for (larger, smaller) in differences {
+            if let (Some(larger), Some(smaller)) = (larger, smaller) {
+                if larger.is_finite() && smaller.is_finite() {
+                    bound = bound.max(larger - smaller);
+                }
+            }
+        }
+        bound
+    }
+}
+
+impl<T> Heuristic<T> for &Landmarks {
+    /// Landmarks work on vertex ids, so there is no estimate from values alone.
+    fn estimate(&self, _from: &T, _to: &T) -> f64 {
+        0.0
+    }
+
+    fn estimate_vertices(&self, from: &Vertex<T>, to: &Vertex<T>) -> f64
+    where
+        T: Clone,
+    {
+        self.lower_bound(from.id, to.id)
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Clone,
+{
+    /// Picks up to `k` landmarks spread far apart, each the vertex farthest
+    /// from those already chosen, and precomputes their distance tables.
+    ///
+    /// A table is only kept if no edge leads into the part of the graph the
+    /// landmark reaches, or out of the part that reaches it, as the bounds
+    /// would otherwise not be consistent.
+    ///
+    /// Fails if an edge has a negative cost.
+    pub fn build_landmarks(&self, k: usize) -> Result<Landmarks, GraphError> {
+        self.build_landmarks_with_progress(k, |_| ControlFlow::Continue(()))
+    }
+
+    /// Like [`Graph::build_landmarks`], but calls `progress` with the
+    /// percentage of landmarks picked so far, and stops when it returns
+    /// [`ControlFlow::Break`].
+    ///
+    /// Fails with [`GraphError::Cancelled`] if stopped.
+    pub fn build_landmarks_with_progress<P>(
+        &self,
+        k: usize,
+        progress: P,
+    ) -> Result<Landmarks, GraphError>
+    where
+        P: FnMut(f64) -> ControlFlow<()>,
+    {
+        span!(INFO, "Graph::build_landmarks", k);
+        let mut progress = Progress::start(progress)?;
+        if let Some(edge) = self.adjacency.iter().flatten().find(|edge| edge.cost < 0.0) {
+            return Err(GraphError::InvalidCost(edge.cost));
+        }
+
+        let mut landmarks = Landmarks {
+            landmarks: Vec::new(),
+            from_landmark: Vec::new(),
+            to_landmark: Vec::new(),
+        };
+        let Some(first) = self.vertices.iter().flatten().next() else {
+            progress.finish();
+            return Ok(landmarks);
+        };
+        let total = k.min(self.vertex_count());
+
+        // Distance of each vertex to its closest landmark so far, in either
+        // direction, so that the next pick is the farthest from all of them.
+        let mut closest = vec![f64::INFINITY; self.vertices.len()];
+        let mut next = Some(first.id);
+        while let Some(landmark) = next.filter(|_| landmarks.landmarks.len() < k) {
+            let (from_landmark, _) = self.search(landmark, None, |_| 0.0);
+            let to_landmark = self.distances_to(landmark);
+            for (id, closest) in closest.iter_mut().enumerate() {
+                *closest = closest.min(from_landmark[id].min(to_landmark[id]));
+            }
+
+            let reached = |id: usize| from_landmark[id].is_finite();
+            let unreaching = |id: usize| to_landmark[id].is_infinite();
+            let forward = (!self.enters(reached)).then_some(from_landmark);
+            let backward = (!self.enters(unreaching)).then_some(to_landmark);
+            landmarks.landmarks.push(landmark);
+            landmarks.from_landmark.push(forward);
+            landmarks.to_landmark.push(backward);
+            event!(DEBUG, landmark, "picked landmark");
+            progress.steps(landmarks.landmarks.len(), total)?;
+
+            // Vertices no landmark reaches yet are the farthest of all.
+            next = self
+                .vertices
+                .iter()
+                .flatten()
+                .map(|vertex| (vertex.id, closest[vertex.id]))
+                .filter(|(_, distance)| *distance > 0.0)
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(id, _)| id);
+        }
+
+        progress.finish();
+        Ok(landmarks)
+    }
+
+    /// Whether any edge leads from a vertex outside `inside` to one in it.
+    fn enters<F>(&self, inside: F) -> bool
+    where
+        F: Fn(usize) -> bool,
+    {
+        self.vertices.iter().flatten().any(|vertex| {
+            !inside(vertex.id) && self.outgoing(vertex.id).any(|edge| inside(edge.to))
+        })
+    }
+}
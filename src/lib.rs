@@ -0,0 +1,259 @@
+//! Dijkstra's shortest path algorithm over a generic, weighted, directed graph.
+//!
+//! Build a [`Graph`] with [`Graph::add_vertex`] and [`Graph::add_edge`], then
+//! query it with [`Graph::get_shortest_path`].
+//!
+//! Without the default `std` feature the crate only needs `alloc`, and
+//! offers the graph itself with its editing, iteration and statistics
+//! methods, Dijkstra, A*, time-dependent searches and [`FrozenGraph`]. Every
+//! other algorithm and format requires `std`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// Some helpers of the core modules are only used by algorithms that need
+// `std`.
+#![cfg_attr(not(feature = "std"), allow(dead_code))]
+
+extern crate alloc;
+
+// Declared first so that its macros are in scope in every other module.
+#[macro_use]
+mod trace;
+
+#[cfg(feature = "std")]
+mod all_pairs;
+#[cfg(feature = "std")]
+mod alternatives;
+#[cfg(feature = "std")]
+mod arc_flags;
+mod astar;
+#[cfg(feature = "std")]
+mod bellman_ford;
+#[cfg(feature = "std")]
+mod bidirectional;
+#[cfg(feature = "std")]
+mod binary;
+#[cfg(feature = "std")]
+mod budget;
+#[cfg(feature = "std")]
+mod builder;
+#[cfg(feature = "std")]
+mod cache;
+#[cfg(feature = "std")]
+mod centrality;
+#[cfg(feature = "std")]
+mod ch;
+#[cfg(feature = "std")]
+mod co_optimal;
+#[cfg(feature = "std")]
+mod connectivity;
+mod context;
+#[cfg(feature = "std")]
+mod csv;
+mod custom_cost;
+#[cfg(feature = "std")]
+mod dag;
+#[cfg(feature = "std")]
+mod dial;
+mod diff;
+#[cfg(feature = "std")]
+mod disjoint;
+#[cfg(feature = "std")]
+mod distance_matrix;
+#[cfg(feature = "std")]
+mod dot;
+#[cfg(feature = "std")]
+mod dstar_lite;
+#[cfg(feature = "std")]
+mod dynamic;
+mod edge;
+mod edge_lookup;
+mod edit;
+mod entry_costs;
+mod error;
+#[cfg(feature = "ffi")]
+mod ffi;
+#[cfg(feature = "std")]
+mod flow;
+mod frozen;
+#[cfg(feature = "std")]
+mod generators;
+#[cfg(feature = "std")]
+mod geo;
+#[cfg(feature = "std")]
+mod geojson;
+#[cfg(feature = "std")]
+mod geometry;
+mod graph;
+#[cfg(feature = "std")]
+mod graphml;
+#[cfg(feature = "std")]
+mod grid;
+#[cfg(feature = "std")]
+mod gtfs;
+#[cfg(feature = "std")]
+mod hops;
+#[cfg(feature = "std")]
+mod hpa;
+mod indexed_heap;
+#[cfg(feature = "std")]
+mod instructions;
+#[cfg(feature = "std")]
+mod isochrone;
+mod iter;
+#[cfg(feature = "std")]
+mod johnson;
+#[cfg(feature = "std")]
+mod jps;
+#[cfg(feature = "std")]
+mod keyed;
+#[cfg(feature = "std")]
+mod landmarks;
+#[cfg(feature = "mmap")]
+mod mapped;
+mod matrix;
+#[cfg(feature = "std")]
+mod maze;
+mod merge;
+mod min_non_nan;
+mod modes;
+#[cfg(feature = "std")]
+mod mst;
+#[cfg(feature = "std")]
+mod multi_source;
+#[cfg(feature = "std")]
+mod nearest;
+#[cfg(feature = "osm")]
+mod osm;
+#[cfg(feature = "parallel")]
+mod par;
+#[cfg(feature = "std")]
+mod pareto;
+mod path;
+#[cfg(feature = "petgraph")]
+mod petgraph_interop;
+mod policy;
+#[cfg(feature = "std")]
+mod progress;
+#[cfg(feature = "python")]
+mod python;
+#[cfg(feature = "std")]
+mod query;
+#[cfg(feature = "std")]
+mod scc;
+#[cfg(feature = "serde")]
+mod serialization;
+#[cfg(feature = "std")]
+mod shortest_path_tree;
+#[cfg(feature = "std")]
+mod smoothing;
+#[cfg(feature = "std")]
+mod stats;
+#[cfg(feature = "std")]
+mod steps;
+mod storage;
+mod streaming;
+mod subgraph;
+mod summary;
+#[cfg(feature = "std")]
+mod theta_star;
+mod time_dependent;
+#[cfg(feature = "std")]
+mod turns;
+#[cfg(feature = "std")]
+mod validate;
+mod value_index;
+mod vertex;
+mod visitor;
+#[cfg(feature = "wasm")]
+mod wasm;
+mod weight;
+#[cfg(feature = "std")]
+mod yen;
+#[cfg(feature = "std")]
+mod zero_one;
+
+#[cfg(feature = "std")]
+pub use all_pairs::AllPairsShortestPaths;
+#[cfg(feature = "std")]
+pub use arc_flags::ArcFlags;
+pub use astar::Heuristic;
+#[cfg(feature = "std")]
+pub use binary::BinaryValue;
+#[cfg(feature = "std")]
+pub use budget::Budget;
+#[cfg(feature = "std")]
+pub use builder::GraphBuilder;
+#[cfg(feature = "std")]
+pub use cache::PathCache;
+#[cfg(feature = "std")]
+pub use ch::ContractedGraph;
+pub use context::SearchContext;
+pub use diff::{GraphChange, GraphDiff};
+#[cfg(feature = "std")]
+pub use dot::render_svg;
+#[cfg(feature = "std")]
+pub use dstar_lite::DStarLite;
+#[cfg(feature = "std")]
+pub use dynamic::DynamicShortestPaths;
+pub use edge::Edge;
+pub use error::GraphError;
+#[cfg(feature = "std")]
+pub use flow::MaxFlow;
+pub use frozen::FrozenGraph;
+#[cfg(feature = "std")]
+pub use generators::SeededRng;
+#[cfg(feature = "std")]
+pub use geo::{GreatCircle, LatLon};
+#[cfg(feature = "std")]
+pub use geometry::EdgeGeometry;
+pub use graph::Graph;
+#[cfg(feature = "std")]
+pub use grid::{Connectivity, Grid};
+#[cfg(feature = "std")]
+pub use gtfs::{StopEvent, TransitNetwork};
+#[cfg(feature = "std")]
+pub use hpa::GridHierarchy;
+#[cfg(feature = "std")]
+pub use instructions::{Instruction, Maneuver, StreetName, StreetSegment};
+pub use iter::Vertices;
+#[cfg(feature = "std")]
+pub use keyed::KeyedGraph;
+#[cfg(feature = "std")]
+pub use landmarks::Landmarks;
+#[cfg(feature = "mmap")]
+pub use mapped::MappedGraph;
+pub use matrix::AdjacencyMatrix;
+#[cfg(feature = "std")]
+pub use maze::Maze;
+pub use modes::Modes;
+#[cfg(feature = "std")]
+pub use multi_source::ShortestPathForest;
+#[cfg(feature = "osm")]
+pub use osm::OsmCost;
+pub use path::Path;
+pub use policy::{DuplicateEdges, InsertionPolicy};
+#[cfg(feature = "python")]
+pub use python::PyGraph;
+#[cfg(feature = "std")]
+pub use query::QueryOptions;
+#[cfg(feature = "std")]
+pub use scc::Condensation;
+#[cfg(feature = "std")]
+pub use shortest_path_tree::{PathIds, ShortestPathTree, ShortestPathsTo};
+#[cfg(feature = "std")]
+pub use stats::SearchStats;
+#[cfg(feature = "std")]
+pub use steps::DijkstraSteps;
+pub use storage::GraphStorage;
+pub use streaming::StreamingBuilder;
+pub use summary::GraphStats;
+pub use time_dependent::TravelTimeProfile;
+#[cfg(feature = "std")]
+pub use turns::TurnCosts;
+#[cfg(feature = "std")]
+pub use validate::{ValidationIssue, ValidationReport};
+pub use vertex::Vertex;
+pub use visitor::SearchVisitor;
+#[cfg(feature = "wasm")]
+pub use wasm::{WasmGraph, WasmPath};
+pub use weight::Weight;
@@ -1,258 +1,266 @@
-use std::cmp::Ordering;
-use std::collections::BinaryHeap;
-use std::collections::HashMap;
-use std::fmt::{Display, Formatter, Result};
-use std::hash::{Hash, Hasher};
-
-#[derive(PartialEq)]
-struct MinNonNan(f64);
-
-impl Eq for MinNonNan {}
-
-impl PartialOrd for MinNonNan {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        other.0.partial_cmp(&self.0)
-    }
+use std::fs::File;
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+use dijkstra_rust::{render_svg, Graph, GraphError, Path};
+
+/// Shortest path queries over CSV edge lists of `from,to,cost` rows.
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
 }
 
-impl Ord for MinNonNan {
-    fn cmp(&self, other: &MinNonNan) -> Ordering {
-        self.partial_cmp(other).unwrap()
-    }
-}
-
-#[derive(Debug, Copy, Eq)]
-struct Vertex<T>
-where
-    T: Ord + PartialOrd + Eq + PartialEq + Clone + Copy,
-{
-    id: usize,
-    value: T,
+#[derive(Subcommand)]
+enum Command {
+    /// Print the cheapest path between two vertices and its cost
+    Route {
+        #[arg(long)]
+        graph: PathBuf,
+        #[arg(long)]
+        from: String,
+        #[arg(long)]
+        to: String,
+    },
+    /// Print the adjacency list of the graph
+    Print {
+        #[arg(long)]
+        graph: PathBuf,
+    },
+    /// Print the size, density and edge costs of the graph
+    Stats {
+        #[arg(long)]
+        graph: PathBuf,
+    },
+    /// Check the graph file for structural problems
+    Validate {
+        #[arg(long)]
+        graph: PathBuf,
+        /// Also report the vertices this vertex cannot reach
+        #[arg(long)]
+        root: Option<String>,
+    },
+    /// Write the graph in Graphviz DOT format to stdout
+    ExportDot {
+        #[arg(long)]
+        graph: PathBuf,
+        /// Highlight the cheapest path from this vertex, requires --to
+        #[arg(long, requires = "to")]
+        from: Option<String>,
+        /// Highlight the cheapest path to this vertex, requires --from
+        #[arg(long, requires = "from")]
+        to: Option<String>,
+        /// Highlight the shortest path tree grown from this vertex
+        #[arg(long, conflicts_with = "from")]
+        tree: Option<String>,
+        /// Write SVG rendered by the Graphviz dot command instead
+        #[arg(long)]
+        svg: bool,
+    },
+    /// Explore a graph at an interactive prompt, keeping it in memory
+    Shell {
+        /// Load this graph file first
+        #[arg(long)]
+        graph: Option<PathBuf>,
+    },
 }
 
-impl<T> Ord for Vertex<T>
-where
-    T: Ord + PartialOrd + Eq + PartialEq + Clone + Copy,
-{
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.id.cmp(&other.id)
-    }
-}
-
-impl<T> PartialOrd for Vertex<T>
-where
-    T: Ord + PartialOrd + Eq + PartialEq + Clone + Copy,
-{
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl<T> Clone for Vertex<T>
-where
-    T: Ord + PartialOrd + Eq + PartialEq + Clone + Copy,
-{
-    fn clone(&self) -> Self {
-        Vertex {
-            id: self.id,
-            value: self.value.clone(),
-        }
-    }
+const SHELL_HELP: &str = "\
+load FILE                      replace the graph with a CSV edge list
+addv NAME                      add a vertex
+adde FROM TO COST [undirected] add an edge
+route FROM TO                  print the cheapest path
+print                          print the adjacency list
+stats                          print the size, density and edge costs
+validate [ROOT]                check for structural problems
+export dot|svg FILE [FROM TO]  write the graph, highlighting a path
+help                           show this list
+quit                           leave the shell
+";
+
+fn load(path: &PathBuf) -> Result<Graph<String>, String> {
+    let file = File::open(path).map_err(|error| format!("{}: {}", path.display(), error))?;
+    Graph::from_edge_list_csv(file).map_err(|error| format!("{}: {}", path.display(), error))
 }
 
-impl<T> PartialEq for Vertex<T>
-where
-    T: Ord + PartialOrd + Eq + PartialEq + Clone + Copy,
-{
-    fn eq(&self, other: &Self) -> bool {
-        self.id == other.id
-    }
+fn find(graph: &Graph<String>, name: &str) -> Result<usize, String> {
+    graph
+        .find_vertex(&name.to_string())
+        .ok_or_else(|| format!("There is no vertex named {}", name))
 }
 
-impl<T> Hash for Vertex<T>
-where
-    T: Ord + PartialOrd + Eq + PartialEq + Clone + Copy,
-{
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.id.hash(state);
-    }
+fn route(graph: &Graph<String>, from: &str, to: &str) -> Result<Path<String>, String> {
+    graph
+        .get_shortest_path(find(graph, from)?, find(graph, to)?)
+        .map_err(|error| match error {
+            GraphError::NoPathFound { .. } => format!("There is no path from {} to {}", from, to),
+            error => error.to_string(),
+        })
 }
 
-struct Edge<T>
-where
-    T: Ord + PartialOrd + Eq + PartialEq + Clone + Copy,
-{
-    to: Vertex<T>,
-    cost: f64,
+fn print_route(graph: &Graph<String>, from: &str, to: &str) -> Result<(), String> {
+    let path = route(graph, from, to)?;
+    let names: Vec<&str> = path.iter().map(|vertex| vertex.value.as_str()).collect();
+    println!(
+        "The shortest path has value of {} and leads via {}",
+        path.cost(),
+        names.join(" -> ")
+    );
+    Ok(())
 }
 
-impl<T> Edge<T>
-where
-    T: Ord + PartialOrd + Eq + PartialEq + Clone + Copy,
-{
-    fn new(to: Vertex<T>, cost: f64) -> Self {
-        Self { to, cost }
+/// Reads commands from stdin until `quit` or the end of input, reporting
+/// errors without leaving.
+fn shell(path: Option<PathBuf>) -> Result<(), String> {
+    let mut graph = match path {
+        Some(path) => load(&path)?,
+        None => Graph::new(),
+    };
+    let mut line = String::new();
+    loop {
+        print!("> ");
+        io::stdout().flush().map_err(|error| error.to_string())?;
+        line.clear();
+        if io::stdin()
+            .lock()
+            .read_line(&mut line)
+            .map_err(|error| error.to_string())?
+            == 0
+        {
+            println!();
+            return Ok(());
+        }
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match execute(&mut graph, &words) {
+            Ok(true) => {}
+            Ok(false) => return Ok(()),
+            Err(error) => eprintln!("error: {}", error),
+        }
     }
 }
 
-struct Graph<T>
-where
-    T: Ord + PartialOrd + Eq + PartialEq + Clone + Copy,
-{
-    graph: HashMap<Vertex<T>, Vec<Edge<T>>>,
-    head: usize,
-}
-
-impl<T> Graph<T>
-where
-    T: Ord + PartialOrd + Eq + PartialEq + Clone + Copy,
-{
-    fn new() -> Self {
-        Self {
-            graph: HashMap::new(),
-            head: 0,
+/// Runs one shell command on `graph`, returning whether to keep going.
+fn execute(graph: &mut Graph<String>, words: &[&str]) -> Result<bool, String> {
+    match words {
+        [] => {}
+        ["help"] => print!("{}", SHELL_HELP),
+        ["quit" | "exit"] => return Ok(false),
+        ["load", path] => {
+            *graph = load(&PathBuf::from(path))?;
+            println!("Loaded {} vertices", graph.vertex_count());
         }
-    }
-
-    fn add_vertex(&mut self, value: T) -> usize {
-        let vertex = Vertex::<T> {
-            id: self.head,
-            value,
-        };
-        self.graph.insert(vertex, Vec::new());
-        self.head += 1;
-        self.head - 1
-    }
-
-    fn get_vertex(&self, id: usize) -> &Vertex<T> {
-        self.graph
-            .iter()
-            .find(|(key, _)| key.id == id)
-            .map(|(key, _)| key)
-            .unwrap_or_else(|| panic!("There is no vertex with id {}", id))
-    }
-
-    fn add_edge(&mut self, from: usize, to: usize, cost: f64) {
-        let edge = Edge::new(*self.get_vertex(to), cost);
-        self.graph
-            .entry(*self.get_vertex(from))
-            .or_insert(vec![])
-            .push(edge);
-    }
-
-    fn get_shortest_path(&self, from: usize, to: usize) -> (Vec<Vertex<T>>, f64) {
-        let start = self.get_vertex(from);
-        let end = self.get_vertex(to);
-
-        let (prev, distance, start, end) = self.dijkstra(*start, *end);
-
-        let mut path = Vec::new();
-        let mut at = end;
-        while at.id != start.id {
-            path.push(at);
-            at = prev[at.id].unwrap();
+        ["addv", name] => {
+            if graph.find_vertex(&name.to_string()).is_some() {
+                return Err(format!("There already is a vertex named {}", name));
+            }
+            graph.add_vertex(name.to_string());
         }
-        path.push(at);
-        path.reverse();
-
-        (path, distance)
+        ["adde", from, to, cost, rest @ ..] if matches!(rest, [] | ["undirected"]) => {
+            let (from, to) = (find(graph, from)?, find(graph, to)?);
+            let cost: f64 = cost
+                .parse()
+                .map_err(|_| format!("Invalid edge cost {}", cost))?;
+            let added = if rest.is_empty() {
+                graph.add_edge(from, to, cost)
+            } else {
+                graph.add_edge_undirected(from, to, cost)
+            };
+            added.map_err(|error| error.to_string())?;
+        }
+        ["route", from, to] => print_route(graph, from, to)?,
+        ["print"] => print!("{}", graph),
+        ["stats"] => print!("{}", graph.stats()),
+        ["validate", root @ ..] if root.len() <= 1 => {
+            let root = root.first().map(|root| find(graph, root)).transpose()?;
+            let report = graph.validate(root).map_err(|error| error.to_string())?;
+            if report.is_ok() {
+                println!("ok");
+            } else {
+                print!("{}", report);
+            }
+        }
+        ["export", format @ ("dot" | "svg"), file, ends @ ..] if matches!(ends.len(), 0 | 2) => {
+            let dot = match ends {
+                [from, to] => graph.to_dot_with_route(&route(graph, from, to)?),
+                _ => graph.to_dot(),
+            };
+            let output = if *format == "svg" {
+                render_svg(&dot).map_err(|error| error.to_string())?
+            } else {
+                dot
+            };
+            std::fs::write(file, output).map_err(|error| format!("{}: {}", file, error))?;
+            println!("Wrote {}", file);
+        }
+        [command, ..]
+            if SHELL_HELP
+                .lines()
+                .any(|usage| usage.split(' ').next() == Some(command)) =>
+        {
+            return Err(format!("Wrong arguments for {}, try help", command));
+        }
+        [command, ..] => return Err(format!("Unknown command {}, try help", command)),
     }
+    Ok(true)
+}
 
-    fn dijkstra(
-        &self,
-        start: Vertex<T>,
-        end: Vertex<T>,
-    ) -> (Vec<Option<Vertex<T>>>, f64, Vertex<T>, Vertex<T>) {
-        let mut dist = vec![f64::INFINITY; self.graph.len()];
-        dist[start.id] = 0.0;
-
-        let mut queue = BinaryHeap::new();
-        queue.push((MinNonNan(0.0), start));
-
-        let mut visited = vec![false; self.graph.len()];
-        let mut prev: Vec<Option<Vertex<T>>> = vec![None; self.graph.len()];
-
-        while let Some((current_cost, current)) = queue.pop() {
-            visited[current.id] = true;
-
-            if dist[current.id] < current_cost.0 {
-                continue;
+fn run(cli: Cli) -> Result<(), String> {
+    match cli.command {
+        Command::Route { graph, from, to } => print_route(&load(&graph)?, &from, &to)?,
+        Command::Print { graph } => print!("{}", load(&graph)?),
+        Command::Stats { graph } => print!("{}", load(&graph)?.stats()),
+        Command::Validate { graph: path, root } => {
+            let graph = load(&path)?;
+            let root = root.map(|root| find(&graph, &root)).transpose()?;
+            let report = graph.validate(root).map_err(|error| error.to_string())?;
+            if !report.is_ok() {
+                print!("{}", report);
+                return Err(format!(
+                    "{}: {} problems found",
+                    path.display(),
+                    report.issues().len()
+                ));
             }
-
-            let edges = self.graph.get(&current).unwrap();
-            for edge in edges {
-                if visited[edge.to.id] {
-                    continue;
-                }
-
-                let new_dist = dist[current.id] + edge.cost;
-                if new_dist < dist[edge.to.id] {
-                    prev[edge.to.id] = Some(current);
-                    dist[edge.to.id] = new_dist;
-                    queue.push((MinNonNan(new_dist), edge.to))
+            println!("{}: ok", path.display());
+        }
+        Command::ExportDot {
+            graph,
+            from,
+            to,
+            tree,
+            svg,
+        } => {
+            let graph = load(&graph)?;
+            let dot = match (from, to, tree) {
+                (Some(from), Some(to), _) => graph.to_dot_with_route(&route(&graph, &from, &to)?),
+                (_, _, Some(root)) => {
+                    let tree = graph
+                        .shortest_paths_from(find(&graph, &root)?)
+                        .map_err(|error| error.to_string())?;
+                    graph.to_dot_with_tree(&tree)
                 }
-            }
-            if current.id == end.id {
-                return (prev, dist[end.id], start, end);
+                _ => graph.to_dot(),
+            };
+            if svg {
+                print!("{}", render_svg(&dot).map_err(|error| error.to_string())?);
+            } else {
+                print!("{}", dot);
             }
         }
-
-        (prev, f64::INFINITY, start, end)
+        Command::Shell { graph } => shell(graph)?,
     }
+    Ok(())
 }
 
-impl<T: Display> Display for Graph<T>
-where
-    T: Ord + PartialOrd + Eq + PartialEq + Clone + Copy,
-{
-    fn fmt(&self, f: &mut Formatter) -> Result {
-        let mut graph_string: String = String::new();
-        for (key, value) in &self.graph {
-            graph_string += format!("[{}] -> ", key.value).as_str();
-            for edge in value {
-                graph_string += format!("[{} ({})]", edge.to.value, edge.cost).as_str();
-            }
-            graph_string += "\n"
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
         }
-
-        write!(f, "{}", graph_string)
     }
 }
-
-fn main() {
-    let mut graph = Graph::<&'static str>::new();
-
-    let a = graph.add_vertex(&"A");
-    let b = graph.add_vertex(&"B");
-    let c = graph.add_vertex(&"C");
-    let d = graph.add_vertex(&"D");
-    let e = graph.add_vertex(&"E");
-
-    graph.add_edge(a, b, 1.0);
-    graph.add_edge(a, c, 3.0);
-    graph.add_edge(b, d, 2.0);
-    graph.add_edge(b, e, 8.0);
-    graph.add_edge(b, c, 1.0);
-    graph.add_edge(c, d, 1.0);
-    graph.add_edge(d, e, 4.0);
-    graph.add_edge(e, c, 3.0);
-    graph.add_edge(c, e, 3.0);
-
-    print!("{}", graph);
-
-    let shortest_path = graph.get_shortest_path(a, e);
-    print!(
-        "The shortest path has value of {} and leads via {:?}",
-        shortest_path.1, shortest_path.0
-    );
-
-    /*
-    Output:    
-    [D] -> [E (4)]
-    [C] -> [D (1)][E (3)]
-    [A] -> [B (1)][C (3)]
-    [B] -> [D (2)][E (8)][C (1)]
-    [E] -> [C (3)]
-    The shortest path has value of 5 and leads via [Vertex { id: 0, value: "A" }, Vertex { id: 1, value: "B" }, Vertex { id: 2, value: "C" }, Vertex { id: 4, value: "E" }]
-    */
-}
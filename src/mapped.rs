@@ -0,0 +1,297 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::ops::Range;
+use std::path::Path as FsPath;
+
+use memmap2::Mmap;
+
+use crate::indexed_heap::IndexedHeap;
+use crate::{Graph, GraphError, Path, Vertex};
+
+/// Identifies a file written by [`Graph::write_mapped`], including the
+/// version of the layout.
+const MAGIC: &[u8; 8] = b"DJKCSR01";
+
+/// Size of the header: the magic, then the vertex id count and the edge
+/// count as little-endian `u64`.
+const HEADER: usize = 24;
+
+/// Marks a vertex without predecessor.
+const NO_VERTEX: u32 = u32::MAX;
+
+/// Rounds `bytes` up to a multiple of eight, so every section of the file
+/// starts aligned for the widest value it holds.
+fn padded(bytes: usize) -> usize {
+    bytes.div_ceil(8) * 8
+}
+
+/// Byte ranges of the sections of a mapped graph file.
+struct Layout {
+    /// One byte per vertex id, non-zero for live vertices.
+    live: Range<usize>,
+    /// `u64` start of each vertex's edges, with a final entry for the end.
+    offsets: Range<usize>,
+    /// `u32` target of each edge.
+    targets: Range<usize>,
+    /// `f64` cost of each edge.
+    costs: Range<usize>,
+}
+
+impl Layout {
+    /// Returns the layout for the given counts, or `None` if its size
+    /// overflows `usize`.
+    fn new(slots: usize, edges: usize) -> Option<Layout> {
+        let live = HEADER..HEADER.checked_add(slots)?;
+        let offsets_start = HEADER.checked_add(padded(slots))?;
+        let offsets =
+            offsets_start..offsets_start.checked_add(slots.checked_add(1)?.checked_mul(8)?)?;
+        let targets = offsets.end..offsets.end.checked_add(edges.checked_mul(4)?)?;
+        let costs_start = offsets.end.checked_add(padded(targets.len()))?;
+        let costs = costs_start..costs_start.checked_add(edges.checked_mul(8)?)?;
+        Some(Layout {
+            live,
+            offsets,
+            targets,
+            costs,
+        })
+    }
+}
+
+/// A graph read straight from a memory-mapped file in compressed sparse row
+/// form, as written by [`Graph::write_mapped`]. Nothing is loaded up front:
+/// the operating system pages in the parts of the file a query touches and
+/// may drop them again under memory pressure, so graphs larger than the
+/// available memory can be queried.
+///
+/// Only vertex ids and edge costs are stored, so paths carry `()` values.
+pub struct MappedGraph {
+    map: Mmap,
+    slots: usize,
+    edges: usize,
+    layout: Layout,
+}
+
+impl MappedGraph {
+    /// Maps the graph file at `path`, checking its header and size but not
+    /// its contents.
+    ///
+    /// Fails with [`GraphError::Io`] if the file cannot be opened or mapped,
+    /// and with [`GraphError::Parse`] if it is not a graph file, is
+    /// truncated, or the target is big-endian.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated while the graph is in use,
+    /// as the mapping would change under it. A file with a valid header but
+    /// corrupt contents makes queries panic or return wrong paths.
+    pub unsafe fn open<P: AsRef<FsPath>>(path: P) -> Result<Self, GraphError> {
+        let io = |error: std::io::Error| GraphError::Io(error.to_string());
+        let file = File::open(path).map_err(io)?;
+        // SAFETY: the caller guarantees the file is left unchanged.
+        let map = unsafe { Mmap::map(&file) }.map_err(io)?;
+        let invalid = |message: &str| GraphError::Parse {
+            line: 0,
+            message: message.to_string(),
+        };
+
+        if cfg!(target_endian = "big") {
+            return Err(invalid("mapped graphs are little-endian"));
+        }
+        if map.len() < HEADER || &map[..8] != MAGIC {
+            return Err(invalid("not a mapped graph file"));
+        }
+        let count = |at: usize| u64::from_le_bytes(map[at..at + 8].try_into().unwrap());
+        let (slots, edges) = (count(8), count(16));
+        let layout = usize::try_from(slots)
+            .ok()
+            .zip(usize::try_from(edges).ok())
+            .and_then(|(slots, edges)| Layout::new(slots, edges))
+            .filter(|layout| layout.costs.end == map.len())
+            .ok_or_else(|| invalid("mapped graph file has the wrong size"))?;
+
+        let graph = MappedGraph {
+            map,
+            slots: slots as usize,
+            edges: edges as usize,
+            layout,
+        };
+        if graph.offsets().last() != Some(&edges) {
+            return Err(invalid("mapped graph file has inconsistent offsets"));
+        }
+        Ok(graph)
+    }
+
+    /// Returns the number of vertex ids, counting removed vertices.
+    pub fn vertex_count(&self) -> usize {
+        self.slots
+    }
+
+    /// Returns the number of edges that can be traversed, counting undirected
+    /// edges once per direction.
+    pub fn edge_count(&self) -> usize {
+        self.edges
+    }
+
+    /// Whether a vertex with the given id exists.
+    pub fn contains_vertex(&self, id: usize) -> bool {
+        id < self.slots && self.map[self.layout.live.start + id] != 0
+    }
+
+    /// Returns the cheapest path from `from` to `to`, as
+    /// [`Graph::get_shortest_path`] would on the graph the file was written
+    /// from.
+    ///
+    /// Fails if either vertex is unknown or `to` is not reachable from `from`.
+    pub fn get_shortest_path(&self, from: usize, to: usize) -> Result<Path<()>, GraphError> {
+        span!(DEBUG, "MappedGraph::get_shortest_path", from, to);
+        for id in [from, to] {
+            if !self.contains_vertex(id) {
+                return Err(GraphError::UnknownVertex(id));
+            }
+        }
+        let (offsets, targets, costs) = (self.offsets(), self.targets(), self.costs());
+
+        let mut dist = vec![f64::INFINITY; self.slots];
+        let mut prev = vec![NO_VERTEX; self.slots];
+        // Cost of the edge each vertex was reached by.
+        let mut step = vec![0.0; self.slots];
+        let mut settled = vec![false; self.slots];
+        let mut queue = IndexedHeap::new(self.slots);
+        dist[from] = 0.0;
+        queue.push_or_decrease(from, 0.0);
+
+        while let Some((cost, current)) = queue.pop() {
+            if current == to {
+                break;
+            }
+            settled[current] = true;
+            let edges = offsets[current] as usize..offsets[current + 1] as usize;
+            for (&next, &edge_cost) in targets[edges.clone()].iter().zip(&costs[edges]) {
+                let next = next as usize;
+                if settled[next] {
+                    continue;
+                }
+                let new_dist = cost + edge_cost;
+                if new_dist < dist[next] {
+                    dist[next] = new_dist;
+                    prev[next] = current as u32;
+                    step[next] = edge_cost;
+                    queue.push_or_decrease(next, new_dist);
+                }
+            }
+        }
+        event!(
+            DEBUG,
+            cost = dist[to],
+            settled = settled.iter().filter(|settled| **settled).count(),
+            "search finished"
+        );
+        if dist[to] == f64::INFINITY {
+            return Err(GraphError::NoPathFound { from, to });
+        }
+
+        let mut ids = vec![to];
+        while prev[*ids.last().unwrap()] != NO_VERTEX {
+            ids.push(prev[*ids.last().unwrap()] as usize);
+        }
+        ids.reverse();
+        let costs = ids[1..].iter().map(|id| step[*id]).collect();
+        let vertices = ids.into_iter().map(|id| Vertex { id, value: () }).collect();
+        Ok(Path::new(vertices, costs, dist[to]))
+    }
+
+    fn offsets(&self) -> &[u64] {
+        // SAFETY: the section lies within the mapping, which is page-aligned,
+        // at an offset that is a multiple of eight.
+        unsafe { self.section(&self.layout.offsets) }
+    }
+
+    fn targets(&self) -> &[u32] {
+        // SAFETY: as for `offsets`.
+        unsafe { self.section(&self.layout.targets) }
+    }
+
+    fn costs(&self) -> &[f64] {
+        // SAFETY: as for `offsets`.
+        unsafe { self.section(&self.layout.costs) }
+    }
+
+    /// Views a section of the file as a slice of `V`.
+    ///
+    /// # Safety
+    ///
+    /// `range` must lie within the mapping, start aligned for `V` and span a
+    /// whole number of `V`, and every bit pattern must be a valid `V`.
+    unsafe fn section<V>(&self, range: &Range<usize>) -> &[V] {
+        let len = range.len() / std::mem::size_of::<V>();
+        let start = self.map[range.clone()].as_ptr();
+        debug_assert_eq!(start.align_offset(std::mem::align_of::<V>()), 0);
+        // SAFETY: guaranteed by the caller.
+        unsafe { std::slice::from_raw_parts(start.cast::<V>(), len) }
+    }
+}
+
+impl<T, E> Graph<T, f64, E>
+where
+    T: Clone,
+    E: Clone,
+{
+    /// Writes the graph to `path` in the flat format read by
+    /// [`MappedGraph::open`]. Vertex values and edge payloads are left out,
+    /// and entry costs are added to the edges leading into each vertex.
+    ///
+    /// Fails with [`GraphError::Io`] if the file cannot be written.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the graph has `u32::MAX` or more vertex ids, counting
+    /// removed ones.
+    pub fn write_mapped<P: AsRef<FsPath>>(&self, path: P) -> Result<(), GraphError> {
+        assert!(
+            self.vertices.len() < NO_VERTEX as usize,
+            "too many vertices to write"
+        );
+        let io = |error: std::io::Error| GraphError::Io(error.to_string());
+        let slots = self.vertices.len();
+        let edges: usize = (0..slots)
+            .filter(|id| self.vertices[*id].is_some())
+            .map(|id| self.outgoing(id).count())
+            .sum();
+        let mut out = BufWriter::new(File::create(path).map_err(io)?);
+        let pad = |out: &mut BufWriter<File>, bytes: usize| {
+            out.write_all(&[0; 8][..padded(bytes) - bytes])
+        };
+
+        out.write_all(MAGIC).map_err(io)?;
+        out.write_all(&(slots as u64).to_le_bytes()).map_err(io)?;
+        out.write_all(&(edges as u64).to_le_bytes()).map_err(io)?;
+        for vertex in &self.vertices {
+            out.write_all(&[u8::from(vertex.is_some())]).map_err(io)?;
+        }
+        pad(&mut out, slots).map_err(io)?;
+
+        let mut offset = 0u64;
+        for id in 0..slots {
+            out.write_all(&offset.to_le_bytes()).map_err(io)?;
+            if self.vertices[id].is_some() {
+                offset += self.outgoing(id).count() as u64;
+            }
+        }
+        out.write_all(&offset.to_le_bytes()).map_err(io)?;
+
+        let live = || (0..slots).filter(|id| self.vertices[*id].is_some());
+        for id in live() {
+            for edge in self.outgoing(id) {
+                out.write_all(&(edge.to as u32).to_le_bytes()).map_err(io)?;
+            }
+        }
+        pad(&mut out, edges * 4).map_err(io)?;
+        for id in live() {
+            for edge in self.outgoing(id) {
+                let cost = edge.cost + self.entry_cost(edge.to);
+                out.write_all(&cost.to_le_bytes()).map_err(io)?;
+            }
+        }
+        out.flush().map_err(io)
+    }
+}
@@ -0,0 +1,224 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::indexed_heap::IndexedHeap;
+use crate::{Graph, GraphError, GraphStorage, Path, StreamingBuilder, Vertex, Weight};
+
+/// A graph stored as a dense matrix of edge costs, with a cell for every
+/// ordered pair of vertices.
+///
+/// Takes memory quadratic in the number of vertices, but looks up the edge
+/// between two vertices in constant time and scans rows without chasing
+/// pointers, which makes it faster than [`Graph`] for small, dense graphs
+/// and for algorithms such as Floyd-Warshall in
+/// [`AdjacencyMatrix::all_pairs_shortest_paths`]. Vertices are numbered from
+/// 0 and hold no values, and at most one edge is kept per ordered pair.
+///
+/// Edges are read through [`GraphStorage`], like those of the other
+/// representations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdjacencyMatrix<W = f64> {
+    vertices: usize,
+    /// Cost of the edge from `from` to `to` at `from * vertices + to`, or
+    /// infinity without an edge.
+    costs: Vec<W>,
+}
+
+impl<W: Weight> AdjacencyMatrix<W> {
+    /// Creates a matrix of `vertices` vertices without edges.
+    pub fn new(vertices: usize) -> Self {
+        AdjacencyMatrix {
+            vertices,
+            costs: vec![W::INFINITY; vertices * vertices],
+        }
+    }
+
+    /// Copies the edges of any graph representation into a matrix, keeping
+    /// vertex ids and, of parallel edges, the cheapest. Ids of removed
+    /// vertices become vertices without edges, and entry costs are left out.
+    pub fn from_storage<S: GraphStorage<W>>(storage: &S) -> Self {
+        let mut matrix = Self::new(storage.id_bound());
+        for from in 0..matrix.vertices {
+            for (to, cost) in storage.edges_from(from) {
+                let cell = &mut matrix.costs[from * matrix.vertices + to];
+                if cost < *cell {
+                    *cell = cost;
+                }
+            }
+        }
+        matrix
+    }
+
+    /// Returns the number of vertices.
+    pub fn vertex_count(&self) -> usize {
+        self.vertices
+    }
+
+    /// Returns the number of edges.
+    pub fn edge_count(&self) -> usize {
+        self.costs
+            .iter()
+            .filter(|cost| **cost != W::INFINITY)
+            .count()
+    }
+
+    /// Adds a vertex without edges and returns its id. Takes time quadratic
+    /// in the number of vertices, as every row moves.
+    pub fn add_vertex(&mut self) -> usize {
+        let old = self.vertices;
+        let mut costs = vec![W::INFINITY; (old + 1) * (old + 1)];
+        for row in 0..old {
+            costs[row * (old + 1)..row * (old + 1) + old]
+                .copy_from_slice(&self.costs[row * old..(row + 1) * old]);
+        }
+        self.costs = costs;
+        self.vertices += 1;
+        old
+    }
+
+    /// Sets the cost of the edge from `from` to `to`, adding the edge or
+    /// replacing its cost. An infinite cost removes it.
+    ///
+    /// Fails if either vertex is unknown or the cost is NaN.
+    pub fn set_edge(&mut self, from: usize, to: usize, cost: W) -> Result<(), GraphError> {
+        let cell = self.cell(from, to)?;
+        Graph::<usize, W>::check_cost(cost)?;
+        self.costs[cell] = cost;
+        Ok(())
+    }
+
+    /// Like [`AdjacencyMatrix::set_edge`], but sets the edges both ways.
+    pub fn set_edge_undirected(&mut self, a: usize, b: usize, cost: W) -> Result<(), GraphError> {
+        self.set_edge(a, b, cost)?;
+        self.set_edge(b, a, cost)
+    }
+
+    /// Removes the edge from `from` to `to` and returns its cost, or `None`
+    /// if there was none.
+    ///
+    /// Fails if either vertex is unknown.
+    pub fn remove_edge(&mut self, from: usize, to: usize) -> Result<Option<W>, GraphError> {
+        let cell = self.cell(from, to)?;
+        let cost = core::mem::replace(&mut self.costs[cell], W::INFINITY);
+        Ok((cost != W::INFINITY).then_some(cost))
+    }
+
+    /// Copies the matrix into a [`Graph`] with adjacency lists, each vertex
+    /// holding its own id, e.g. once the graph has grown too sparse for a
+    /// matrix.
+    pub fn to_graph(&self) -> Graph<usize, W> {
+        let mut builder = StreamingBuilder::with_capacity(self.edge_count());
+        builder.ensure_vertices(self.vertices);
+        for from in 0..self.vertices {
+            for (to, cost) in self.edges_from(from) {
+                builder
+                    .push(from, to, cost)
+                    .expect("matrix costs are never NaN");
+            }
+        }
+        builder.finish()
+    }
+
+    /// Returns the cheapest path from `from` to `to`, as
+    /// [`Graph::get_shortest_path`] would on the same edges.
+    ///
+    /// Scanning whole rows of the matrix makes it faster than [`Graph`] once
+    /// most pairs of vertices are joined, and slower on sparse graphs.
+    ///
+    /// Fails if either vertex is unknown or `to` is not reachable from `from`.
+    pub fn get_shortest_path(&self, from: usize, to: usize) -> Result<Path<(), W>, GraphError> {
+        span!(DEBUG, "AdjacencyMatrix::get_shortest_path", from, to);
+        self.cell(from, to)?;
+        let n = self.vertices;
+        let mut dist = vec![W::INFINITY; n];
+        let mut prev = vec![usize::MAX; n];
+        let mut settled = vec![false; n];
+        let mut queue = IndexedHeap::new(n);
+        dist[from] = W::ZERO;
+        queue.push_or_decrease(from, W::ZERO);
+
+        while let Some((cost, current)) = queue.pop() {
+            settled[current] = true;
+            if current == to {
+                break;
+            }
+            let row = &self.costs[current * n..(current + 1) * n];
+            for (next, &edge_cost) in row.iter().enumerate() {
+                if edge_cost == W::INFINITY || settled[next] {
+                    continue;
+                }
+                let new_dist = cost.saturating_add(edge_cost);
+                if new_dist < dist[next] {
+                    dist[next] = new_dist;
+                    prev[next] = current;
+                    queue.push_or_decrease(next, new_dist);
+                }
+            }
+        }
+        if !settled[to] {
+            return Err(GraphError::NoPathFound { from, to });
+        }
+
+        let mut ids = vec![to];
+        while prev[*ids.last().unwrap()] != usize::MAX {
+            ids.push(prev[*ids.last().unwrap()]);
+        }
+        ids.reverse();
+        let costs = ids
+            .windows(2)
+            .map(|step| self.costs[step[0] * n + step[1]])
+            .collect();
+        let vertices = ids.into_iter().map(|id| Vertex { id, value: () }).collect();
+        Ok(Path::new(vertices, costs, dist[to]))
+    }
+
+    /// Returns the index of the cell for the edge from `from` to `to`.
+    ///
+    /// Fails if either vertex is unknown.
+    fn cell(&self, from: usize, to: usize) -> Result<usize, GraphError> {
+        for id in [from, to] {
+            if id >= self.vertices {
+                return Err(GraphError::UnknownVertex(id));
+            }
+        }
+        Ok(from * self.vertices + to)
+    }
+}
+
+impl<T, W, E> From<&Graph<T, W, E>> for AdjacencyMatrix<W>
+where
+    T: Clone,
+    W: Weight,
+    E: Clone,
+{
+    fn from(graph: &Graph<T, W, E>) -> Self {
+        Self::from_storage(graph)
+    }
+}
+
+impl<W: Weight> GraphStorage<W> for AdjacencyMatrix<W> {
+    fn id_bound(&self) -> usize {
+        self.vertices
+    }
+
+    fn contains_vertex(&self, id: usize) -> bool {
+        id < self.vertices
+    }
+
+    fn edges_from(&self, id: usize) -> impl Iterator<Item = (usize, W)> + '_ {
+        let row = if self.contains_vertex(id) {
+            &self.costs[id * self.vertices..(id + 1) * self.vertices]
+        } else {
+            &[]
+        };
+        row.iter()
+            .enumerate()
+            .filter(|(_, cost)| **cost != W::INFINITY)
+            .map(|(to, cost)| (to, *cost))
+    }
+
+    fn edge_cost(&self, from: usize, to: usize) -> Option<W> {
+        let cost = self.costs[self.cell(from, to).ok()?];
+        (cost != W::INFINITY).then_some(cost)
+    }
+}
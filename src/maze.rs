@@ -0,0 +1,97 @@
+use crate::{Connectivity, Graph, GraphError};
+
+/// A grid graph parsed from an ASCII map, with its start and end markers.
+pub struct Maze {
+    pub graph: Graph<(usize, usize)>,
+    pub start: usize,
+    pub end: usize,
+    rows: Vec<Vec<char>>,
+    width: usize,
+}
+
+impl Maze {
+    /// Parses an ASCII map where `#` is a wall, `.` or a space is floor, and
+    /// `S` and `E` mark the single start and end cells. Every step costs 1,
+    /// or √2 diagonally.
+    pub fn parse(map: &str, connectivity: Connectivity) -> Result<Self, GraphError> {
+        let rows: Vec<Vec<char>> = map.lines().map(|line| line.chars().collect()).collect();
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+
+        let mut start = None;
+        let mut end = None;
+        let mut costs = Vec::with_capacity(rows.len());
+        for (row, line) in rows.iter().enumerate() {
+            let mut row_costs = Vec::with_capacity(line.len());
+            for (col, cell) in line.iter().enumerate() {
+                let marker = match cell {
+                    'S' => Some(&mut start),
+                    'E' => Some(&mut end),
+                    '.' | ' ' | '#' => None,
+                    _ => {
+                        return Err(parse_error(
+                            row,
+                            &format!("unexpected character '{}'", cell),
+                        ))
+                    }
+                };
+                if let Some(marker) = marker {
+                    if marker.replace(row * width + col).is_some() {
+                        return Err(parse_error(
+                            row,
+                            &format!("more than one '{}' marker", cell),
+                        ));
+                    }
+                }
+                row_costs.push(if *cell == '#' { f64::INFINITY } else { 1.0 });
+            }
+            costs.push(row_costs);
+        }
+
+        let missing = |marker| {
+            parse_error(
+                rows.len().saturating_sub(1),
+                &format!("missing '{}' marker", marker),
+            )
+        };
+        let start = start.ok_or_else(|| missing('S'))?;
+        let end = end.ok_or_else(|| missing('E'))?;
+
+        Ok(Maze {
+            graph: Graph::from_grid(&costs, connectivity)?,
+            start,
+            end,
+            rows,
+            width,
+        })
+    }
+
+    /// Returns the id of the vertex for the cell at `row` and `col`.
+    pub fn id(&self, row: usize, col: usize) -> usize {
+        row * self.width + col
+    }
+
+    /// Renders the map with the cells of `path`, given as vertex ids, drawn
+    /// as `*`. The start and end markers are kept.
+    pub fn render(&self, path: &[usize]) -> String {
+        let mut rows = self.rows.clone();
+        for id in path {
+            let (row, col) = (id / self.width, id % self.width);
+            if let Some(cell) = rows.get_mut(row).and_then(|row| row.get_mut(col)) {
+                if *cell != 'S' && *cell != 'E' {
+                    *cell = '*';
+                }
+            }
+        }
+
+        rows.iter()
+            .map(|row| row.iter().collect::<String>() + "\n")
+            .collect()
+    }
+}
+
+fn parse_error(row: usize, message: &str) -> GraphError {
+    GraphError::Parse {
+        line: row + 1,
+        message: message.to_string(),
+    }
+}
@@ -0,0 +1,91 @@
+use alloc::vec::Vec;
+
+use crate::{Edge, Graph, GraphError, Vertex, Weight};
+
+impl<T, W> Graph<T, W>
+where
+    T: Clone,
+    W: Weight,
+{
+    /// Adds a copy of every vertex and edge of `other` to this graph, as a
+    /// disjoint union, and returns the new id of each vertex of `other`,
+    /// indexed by its id there, with `None` for removed ids.
+    ///
+    /// Edges are added under new ids and this graph's
+    /// [insertion policy](Graph::set_insertion_policy). Fails if the policy
+    /// rejects an edge, in which case the vertices and the edges before it
+    /// stay added.
+    pub fn merge(&mut self, other: &Graph<T, W>) -> Result<Vec<Option<usize>>, GraphError> {
+        self.merge_with(other, |graph, vertex| {
+            graph.add_vertex(vertex.value.clone())
+        })
+    }
+
+    /// Like [`Graph::merge`], but unifies each vertex of `other` with the
+    /// vertex of this graph holding an equal value, the smallest id if
+    /// several do, and only adds vertices whose value is new. Vertices of
+    /// `other` holding equal values end up as one, and vertices already in
+    /// this graph keep their [entry cost](Graph::set_entry_cost).
+    ///
+    /// Looking values up takes linear time per vertex unless
+    /// [`Graph::index_values`] was called on this graph.
+    pub fn merge_by_value(&mut self, other: &Graph<T, W>) -> Result<Vec<Option<usize>>, GraphError>
+    where
+        T: PartialEq,
+    {
+        self.merge_with(other, |graph, vertex| {
+            graph
+                .find_vertex(&vertex.value)
+                .unwrap_or_else(|| graph.add_vertex(vertex.value.clone()))
+        })
+    }
+
+    /// Maps every vertex of `other` to an id of this graph with `id_of`, then
+    /// copies the edges of `other` between the mapped ids.
+    fn merge_with<F>(
+        &mut self,
+        other: &Graph<T, W>,
+        mut id_of: F,
+    ) -> Result<Vec<Option<usize>>, GraphError>
+    where
+        F: FnMut(&mut Self, &Vertex<T>) -> usize,
+    {
+        let before = self.vertices.len();
+        let new_ids: Vec<Option<usize>> = other
+            .vertices
+            .iter()
+            .map(|vertex| vertex.as_ref().map(|vertex| id_of(self, vertex)))
+            .collect();
+        let added = new_ids
+            .iter()
+            .enumerate()
+            .filter_map(|(old, new)| new.filter(|new| *new >= before).map(|new| (new, old)));
+        self.copy_entry_costs(other, added);
+
+        let copied = self.copy_edges(other, &new_ids);
+        // Edges may have been added even if one was rejected.
+        self.touch();
+        copied?;
+        Ok(new_ids)
+    }
+
+    /// Adds a copy of each edge of `other` between the ids it maps to, under
+    /// the insertion policy.
+    fn copy_edges(
+        &mut self,
+        other: &Graph<T, W>,
+        new_ids: &[Option<usize>],
+    ) -> Result<(), GraphError> {
+        for vertex in other.vertices() {
+            let from = new_ids[vertex.id].unwrap();
+            for edge in &other.adjacency[vertex.id] {
+                let to = new_ids[edge.to].unwrap();
+                if !self.admit_edge(from, to, edge.cost, edge.undirected)? {
+                    continue;
+                }
+                self.push_edge(from, Edge { to, ..edge.clone() });
+            }
+        }
+        Ok(())
+    }
+}
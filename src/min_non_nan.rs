@@ -0,0 +1,20 @@
+use core::cmp::Ordering;
+
+/// A cost ordered in reverse, so that a max-heap pops the smallest first.
+/// Comparing NaN panics.
+#[derive(PartialEq)]
+pub(crate) struct MinNonNan<W = f64>(pub(crate) W);
+
+impl<W: PartialOrd> Eq for MinNonNan<W> {}
+
+impl<W: PartialOrd> PartialOrd for MinNonNan<W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<W: PartialOrd> Ord for MinNonNan<W> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.partial_cmp(&self.0).unwrap()
+    }
+}
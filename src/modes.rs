@@ -0,0 +1,124 @@
+use core::ops::{BitAnd, BitOr, BitOrAssign};
+
+use crate::{Graph, GraphError, Weight};
+
+/// A set of travel modes, stored on every edge to say who may use it, so
+/// that one graph can answer e.g. both walking and driving queries.
+///
+/// Modes combine with `|`; edges allow [`Modes::ALL`] unless set with
+/// [`Graph::set_edge_modes`], and queries are restricted to some modes with
+/// [`QueryOptions::allow_modes`](crate::QueryOptions::allow_modes).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Modes(u8);
+
+impl Modes {
+    pub const NONE: Modes = Modes(0);
+    pub const WALK: Modes = Modes(1);
+    pub const BIKE: Modes = Modes(1 << 1);
+    pub const CAR: Modes = Modes(1 << 2);
+    pub const TRANSIT: Modes = Modes(1 << 3);
+    /// Every mode, including those not named by a constant, which can be
+    /// made with [`Modes::from_bits`].
+    pub const ALL: Modes = Modes(u8::MAX);
+
+    /// Returns the modes whose bits are set in `bits`.
+    pub const fn from_bits(bits: u8) -> Self {
+        Modes(bits)
+    }
+
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Returns whether every mode in `other` is also in `self`.
+    pub const fn contains(self, other: Modes) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns whether `self` and `other` have any mode in common.
+    pub const fn intersects(self, other: Modes) -> bool {
+        self.0 & other.0 != 0
+    }
+}
+
+impl Default for Modes {
+    fn default() -> Self {
+        Modes::ALL
+    }
+}
+
+impl BitOr for Modes {
+    type Output = Modes;
+
+    fn bitor(self, other: Modes) -> Modes {
+        Modes(self.0 | other.0)
+    }
+}
+
+impl BitOrAssign for Modes {
+    fn bitor_assign(&mut self, other: Modes) {
+        self.0 |= other.0;
+    }
+}
+
+impl BitAnd for Modes {
+    type Output = Modes;
+
+    fn bitand(self, other: Modes) -> Modes {
+        Modes(self.0 & other.0)
+    }
+}
+
+impl<T, W, E> Graph<T, W, E>
+where
+    T: Clone,
+    W: Weight,
+    E: Clone,
+{
+    /// Sets the modes that may use every edge that can be traversed from
+    /// `from` to `to`, including parallel edges and undirected edges between
+    /// the two.
+    ///
+    /// Fails if either vertex is unknown or there is no such edge.
+    pub fn set_edge_modes(
+        &mut self,
+        from: usize,
+        to: usize,
+        modes: Modes,
+    ) -> Result<(), GraphError> {
+        self.get_vertex(from)?;
+        self.get_vertex(to)?;
+
+        let mut updated = false;
+        for edge in &mut self.adjacency[from] {
+            if edge.to == to {
+                edge.modes = modes;
+                updated = true;
+            }
+        }
+        for edge in &mut self.adjacency[to] {
+            if edge.undirected && edge.to == from {
+                edge.modes = modes;
+                updated = true;
+            }
+        }
+        for edge in &mut self.reverse_adjacency[to] {
+            if edge.to == from {
+                edge.modes = modes;
+            }
+        }
+        for edge in &mut self.reverse_adjacency[from] {
+            if edge.undirected && edge.to == to {
+                edge.modes = modes;
+            }
+        }
+
+        if !updated {
+            return Err(GraphError::UnknownEdge { from, to });
+        }
+        self.touch();
+        Ok(())
+    }
+}
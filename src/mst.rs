@@ -0,0 +1,55 @@
+use crate::{Graph, Weight};
+
+impl<T, W> Graph<T, W>
+where
+    T: Clone,
+    W: Weight,
+{
+    /// Returns the edges of a minimum spanning tree, as `(from, to, cost)`
+    /// in ascending order of cost, together with their total cost, using
+    /// Kruskal's algorithm.
+    ///
+    /// Every edge is treated as undirected, whatever its direction. If the
+    /// graph is not connected, the result spans each of its
+    /// [connected components](Graph::connected_components) instead. Edges of
+    /// infinite cost count as missing.
+    pub fn minimum_spanning_tree(&self) -> (Vec<(usize, usize, W)>, W) {
+        let mut edges: Vec<(usize, usize, W)> = self
+            .vertices
+            .iter()
+            .flatten()
+            .flat_map(|vertex| {
+                self.adjacency[vertex.id]
+                    .iter()
+                    .map(move |edge| (vertex.id, edge.to, edge.cost))
+            })
+            .filter(|(_, _, cost)| *cost != W::INFINITY)
+            .collect();
+        // Costs are never NaN, so they are totally ordered.
+        edges.sort_by(|a, b| a.2.partial_cmp(&b.2).unwrap());
+
+        let mut parent: Vec<usize> = (0..self.vertices.len()).collect();
+        let mut tree = Vec::new();
+        let mut total = W::ZERO;
+        for (from, to, cost) in edges {
+            let (a, b) = (find_root(&mut parent, from), find_root(&mut parent, to));
+            if a == b {
+                continue;
+            }
+            parent[a] = b;
+            tree.push((from, to, cost));
+            total = total.saturating_add(cost);
+        }
+        (tree, total)
+    }
+}
+
+/// Returns the representative of the set holding `id`, halving the path to
+/// it along the way.
+fn find_root(parent: &mut [usize], mut id: usize) -> usize {
+    while parent[id] != id {
+        parent[id] = parent[parent[id]];
+        id = parent[id];
+    }
+    id
+}
@@ -0,0 +1,84 @@
+use crate::graph::Predecessors;
+use crate::{Graph, GraphError};
+
+/// Shortest paths from the nearest of several source vertices to every vertex
+/// reachable from any of them.
+#[derive(Debug, Clone)]
+pub struct ShortestPathForest {
+    pub(crate) dist: Vec<f64>,
+    pub(crate) prev: Predecessors,
+}
+
+impl ShortestPathForest {
+    /// Returns the cost from the nearest source to `id`, including that
+    /// source's initial offset, or `None` if it is unreachable.
+    pub fn distance_to(&self, id: usize) -> Option<f64> {
+        self.dist
+            .get(id)
+            .copied()
+            .filter(|distance| *distance != f64::INFINITY)
+    }
+
+    /// Returns the id of the source nearest to `id`, or `None` if it is
+    /// unreachable.
+    pub fn source_of(&self, id: usize) -> Option<usize> {
+        self.distance_to(id)?;
+
+        let mut at = id;
+        while let Some(prev) = self.prev[at] {
+            at = prev as usize;
+        }
+        Some(at)
+    }
+
+    /// Returns the vertex ids along the path from the nearest source to `id`,
+    /// or `None` if it is unreachable.
+    pub fn path_to(&self, id: usize) -> Option<Vec<usize>> {
+        self.distance_to(id)?;
+
+        let mut path = vec![id];
+        let mut at = id;
+        while let Some(prev) = self.prev[at] {
+            let prev = prev as usize;
+            path.push(prev);
+            at = prev;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Clone,
+{
+    /// Runs a single Dijkstra seeded with all `sources` and computes the
+    /// distance from the nearest source to every vertex.
+    pub fn multi_source_shortest_paths(
+        &self,
+        sources: &[usize],
+    ) -> Result<ShortestPathForest, GraphError> {
+        let sources: Vec<(usize, f64)> = sources.iter().map(|source| (*source, 0.0)).collect();
+        self.multi_source_shortest_paths_with_offsets(&sources)
+    }
+
+    /// Like [`Graph::multi_source_shortest_paths`], but each source starts
+    /// with the given initial cost instead of zero.
+    pub fn multi_source_shortest_paths_with_offsets(
+        &self,
+        sources: &[(usize, f64)],
+    ) -> Result<ShortestPathForest, GraphError> {
+        let mut seeds = Vec::with_capacity(sources.len());
+        for (source, offset) in sources {
+            if offset.is_nan() {
+                return Err(GraphError::InvalidCost(*offset));
+            }
+            self.get_vertex(*source)?;
+            seeds.push((*source, *offset));
+        }
+
+        let (dist, prev, _) = self.search_until(&seeds, |_, _| false, |_| 0.0, |_, _| true);
+
+        Ok(ShortestPathForest { dist, prev })
+    }
+}
@@ -0,0 +1,35 @@
+use crate::{Graph, GraphError, Path};
+
+impl<T> Graph<T>
+where
+    T: Clone,
+{
+    /// Finds the target closest to `from` with a single search and returns its
+    /// id together with the path to it.
+    ///
+    /// Fails if any vertex is unknown or none of the targets is reachable.
+    pub fn get_nearest(
+        &self,
+        from: usize,
+        targets: &[usize],
+    ) -> Result<(usize, Path<T>), GraphError> {
+        self.get_vertex(from)?;
+        let mut is_target = vec![false; self.vertices.len()];
+        for target in targets {
+            is_target[self.get_vertex(*target)?.id] = true;
+        }
+
+        let (dist, prev, reached) = self.search_until(
+            &[(from, 0.0)],
+            |vertex, _| is_target[vertex.id],
+            |_| 0.0,
+            |_, _| true,
+        );
+        let end = reached.ok_or(GraphError::NoTargetReachable { from })?;
+
+        Ok((
+            end,
+            self.to_path(self.reconstruct_path(&prev, from, end), dist[end]),
+        ))
+    }
+}
@@ -0,0 +1,210 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::ControlFlow;
+use std::path::Path;
+
+use osmpbf::{BlobDecode, BlobReader, ByteOffset, Element};
+
+use crate::progress::Progress;
+use crate::{Graph, GraphError, LatLon};
+
+/// What the edges of a graph read by [`Graph::from_osm_pbf`] cost.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OsmCost {
+    /// The length of the road in metres.
+    #[default]
+    Distance,
+    /// The time needed to drive the road in seconds, at its `maxspeed` or
+    /// else a typical speed for its kind of highway.
+    TravelTime,
+}
+
+/// Which way a road can be driven, relative to the order of its nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Both,
+    Forward,
+    Backward,
+}
+
+/// A road read in the first pass, whose nodes are located in the second.
+struct Road {
+    nodes: Vec<i64>,
+    direction: Direction,
+    /// Speed in kilometres per hour.
+    speed: f64,
+}
+
+impl Graph<LatLon> {
+    /// Reads a road network from an OpenStreetMap PBF extract: every way
+    /// tagged with a drivable `highway` becomes a chain of edges between its
+    /// nodes, which become vertices at their coordinates. Roads are one-way
+    /// as tagged by `oneway`, and by default for roundabouts and motorways;
+    /// ways tagged `access=no` or `access=private` are left out.
+    ///
+    /// Returns the graph together with the vertex id of each OpenStreetMap
+    /// node on a road. Segments whose nodes are missing from the extract are
+    /// skipped.
+    ///
+    /// Fails if the file cannot be read or is not valid PBF.
+    pub fn from_osm_pbf<P: AsRef<Path>>(
+        path: P,
+        cost: OsmCost,
+    ) -> Result<(Self, HashMap<i64, usize>), GraphError> {
+        Self::from_osm_pbf_with_progress(path, cost, |_| ControlFlow::Continue(()))
+    }
+
+    /// Like [`Graph::from_osm_pbf`], but calls `progress` with the percentage
+    /// done as the extract is read, and stops when it returns
+    /// [`ControlFlow::Break`].
+    ///
+    /// Fails with [`GraphError::Cancelled`] if stopped.
+    pub fn from_osm_pbf_with_progress<P, F>(
+        path: P,
+        cost: OsmCost,
+        progress: F,
+    ) -> Result<(Self, HashMap<i64, usize>), GraphError>
+    where
+        P: AsRef<Path>,
+        F: FnMut(f64) -> ControlFlow<()>,
+    {
+        let path = path.as_ref();
+        span!(INFO, "Graph::from_osm_pbf", path = %path.display());
+        let mut progress = Progress::start(progress)?;
+        let mut roads = Vec::new();
+        for_each_element(path, &mut progress, (0.0, 0.45), |element| {
+            if let Element::Way(way) = element {
+                let tags: HashMap<&str, &str> = way.tags().collect();
+                if let Some(road) = road(&tags) {
+                    roads.push(Road {
+                        nodes: way.refs().collect(),
+                        ..road
+                    });
+                }
+            }
+            event!(INFO, roads = roads.len(), "read roads");
+        })?;
+
+        let needed: HashSet<i64> = roads
+            .iter()
+            .flat_map(|road| road.nodes.iter().copied())
+            .collect();
+        let mut graph = Graph::new();
+        let mut ids = HashMap::new();
+        let mut add_node = |id: i64, lat: f64, lon: f64| {
+            if needed.contains(&id) {
+                ids.insert(id, graph.add_vertex(LatLon::new(lat, lon)));
+            }
+        };
+        for_each_element(path, &mut progress, (0.45, 0.9), |element| match element {
+            Element::Node(node) => add_node(node.id(), node.lat(), node.lon()),
+            Element::DenseNode(node) => add_node(node.id(), node.lat(), node.lon()),
+            _ => {}
+        })?;
+        event!(INFO, vertices = ids.len(), "located road nodes");
+
+        for (index, road) in roads.iter().enumerate() {
+            progress.update(0.9 + 0.1 * index as f64 / roads.len() as f64)?;
+            for segment in road.nodes.windows(2) {
+                let (Some(&a), Some(&b)) = (ids.get(&segment[0]), ids.get(&segment[1])) else {
+                    continue;
+                };
+                let length = graph.vertex(a).value.distance_to(&graph.vertex(b).value);
+                let cost = match cost {
+                    OsmCost::Distance => length,
+                    OsmCost::TravelTime => length / (road.speed / 3.6),
+                };
+                match road.direction {
+                    Direction::Both => graph.add_edge_undirected(a, b, cost)?,
+                    Direction::Forward => graph.add_edge(a, b, cost)?,
+                    Direction::Backward => graph.add_edge(b, a, cost)?,
+                }
+            }
+        }
+        event!(INFO, edges = graph.edge_count(), "added road segments");
+        progress.finish();
+        Ok((graph, ids))
+    }
+}
+
+/// Calls `f` on every element of the extract at `path`, reporting how far
+/// through the file it has got as progress from `start` to `end`.
+fn for_each_element<P, F>(
+    path: &Path,
+    progress: &mut Progress<P>,
+    (start, end): (f64, f64),
+    mut f: F,
+) -> Result<(), GraphError>
+where
+    P: FnMut(f64) -> ControlFlow<()>,
+    F: for<'a> FnMut(Element<'a>),
+{
+    let size = std::fs::metadata(path)
+        .map_err(|error| GraphError::Io(error.to_string()))?
+        .len()
+        .max(1);
+    for blob in BlobReader::seekable_from_path(path).map_err(osm_error)? {
+        let blob = blob.map_err(osm_error)?;
+        if let Some(ByteOffset(offset)) = blob.offset() {
+            progress.update(start + (end - start) * offset as f64 / size as f64)?;
+        }
+        if let BlobDecode::OsmData(block) = blob.decode().map_err(osm_error)? {
+            block.for_each_element(&mut f);
+        }
+    }
+    Ok(())
+}
+
+/// Returns the direction and speed of a drivable way from its tags, without
+/// its nodes, or `None` if it is not a road cars may use.
+fn road(tags: &HashMap<&str, &str>) -> Option<Road> {
+    let highway = *tags.get("highway")?;
+    let typical_speed = match highway.trim_end_matches("_link") {
+        "motorway" => 110.0,
+        "trunk" => 90.0,
+        "primary" => 70.0,
+        "secondary" => 60.0,
+        "tertiary" => 50.0,
+        "unclassified" | "road" => 40.0,
+        "residential" => 30.0,
+        "service" => 20.0,
+        "living_street" => 10.0,
+        _ => return None,
+    };
+    if matches!(tags.get("access"), Some(&"no" | &"private")) {
+        return None;
+    }
+
+    let direction = match tags.get("oneway") {
+        Some(&"yes" | &"true" | &"1") => Direction::Forward,
+        Some(&"-1" | &"reverse") => Direction::Backward,
+        Some(&"no" | &"false" | &"0") => Direction::Both,
+        _ if highway.starts_with("motorway") || tags.get("junction") == Some(&"roundabout") => {
+            Direction::Forward
+        }
+        _ => Direction::Both,
+    };
+    let speed = tags
+        .get("maxspeed")
+        .and_then(|speed| parse_speed(speed))
+        .unwrap_or(typical_speed);
+    Some(Road {
+        nodes: Vec::new(),
+        direction,
+        speed,
+    })
+}
+
+/// Parses a `maxspeed` value in kilometres per hour, or in miles per hour
+/// with an `mph` suffix, into kilometres per hour.
+fn parse_speed(speed: &str) -> Option<f64> {
+    let (number, factor) = match speed.strip_suffix("mph") {
+        Some(number) => (number, 1.609_344),
+        None => (speed, 1.0),
+    };
+    let speed: f64 = number.trim().parse().ok()?;
+    (speed > 0.0 && speed.is_finite()).then_some(speed * factor)
+}
+
+fn osm_error(error: osmpbf::Error) -> GraphError {
+    GraphError::Io(error.to_string())
+}
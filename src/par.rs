@@ -0,0 +1,64 @@
+use rayon::prelude::*;
+
+use crate::{FrozenGraph, Graph, GraphError, Path, Weight};
+
+impl<T, W> Graph<T, W>
+where
+    T: Clone + Send + Sync,
+    W: Weight + Send + Sync,
+{
+    /// Answers every `(from, to)` query in `queries` with
+    /// [`Graph::get_shortest_path`], spreading them across threads.
+    ///
+    /// Returns one result per query, in the same order.
+    pub fn par_shortest_paths(
+        &self,
+        queries: &[(usize, usize)],
+    ) -> Vec<Result<Path<T, W>, GraphError>> {
+        queries
+            .par_iter()
+            .map(|(from, to)| self.get_shortest_path(*from, *to))
+            .collect()
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Clone + Send + Sync,
+{
+    /// Like [`Graph::distance_matrix`], but runs the per-source searches
+    /// across threads.
+    pub fn par_distance_matrix(
+        &self,
+        sources: &[usize],
+        targets: &[usize],
+    ) -> Result<Vec<Vec<f64>>, GraphError> {
+        self.check_all(sources)?;
+        self.check_all(targets)?;
+
+        Ok(sources
+            .par_iter()
+            .map(|source| self.distance_row(*source, targets))
+            .collect())
+    }
+}
+
+impl<T, W> FrozenGraph<T, W>
+where
+    T: Clone + Send + Sync,
+    W: Weight + Send + Sync,
+{
+    /// Answers every `(from, to)` query in `queries` with
+    /// [`FrozenGraph::get_shortest_path`], spreading them across threads.
+    ///
+    /// Returns one result per query, in the same order.
+    pub fn par_shortest_paths(
+        &self,
+        queries: &[(usize, usize)],
+    ) -> Vec<Result<Path<T, W>, GraphError>> {
+        queries
+            .par_iter()
+            .map(|(from, to)| self.get_shortest_path(*from, *to))
+            .collect()
+    }
+}
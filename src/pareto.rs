@@ -0,0 +1,201 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::min_non_nan::MinNonNan;
+use crate::{Edge, Graph, GraphError, Path};
+
+/// A partial path in the multi-criteria search.
+struct Label {
+    vertex: usize,
+    costs: Vec<f64>,
+    /// Label this one was extended from.
+    prev: Option<usize>,
+    /// Set once a label reaching the same vertex has dominated it.
+    dominated: bool,
+}
+
+/// Whether `a` is at least as good as `b` under every criterion.
+fn dominates(a: &[f64], b: &[f64]) -> bool {
+    a.iter().zip(b).all(|(a, b)| a <= b)
+}
+
+impl<T> Graph<T>
+where
+    T: Clone,
+{
+    /// Gives every edge that can be traversed from `from` to `to` a cost
+    /// under each of several criteria, including parallel edges and
+    /// undirected edges between the two. Single-criterion searches keep
+    /// using the edges' costs.
+    ///
+    /// Fails if either vertex is unknown, there is no such edge, or a
+    /// criterion is NaN or negative.
+    pub fn set_edge_criteria(
+        &mut self,
+        from: usize,
+        to: usize,
+        criteria: &[f64],
+    ) -> Result<(), GraphError> {
+        self.get_vertex(from)?;
+        self.get_vertex(to)?;
+        if let Some(cost) = criteria.iter().find(|cost| cost.is_nan() || **cost < 0.0) {
+            return Err(GraphError::InvalidCost(*cost));
+        }
+
+        let mut updated = false;
+        for edge in &mut self.adjacency[from] {
+            if edge.to == to {
+                edge.criteria = criteria.to_vec();
+                updated = true;
+            }
+        }
+        for edge in &mut self.adjacency[to] {
+            if edge.undirected && edge.to == from {
+                edge.criteria = criteria.to_vec();
+                updated = true;
+            }
+        }
+        for edge in &mut self.reverse_adjacency[to] {
+            if edge.to == from {
+                edge.criteria = criteria.to_vec();
+            }
+        }
+        for edge in &mut self.reverse_adjacency[from] {
+            if edge.undirected && edge.to == to {
+                edge.criteria = criteria.to_vec();
+            }
+        }
+
+        if !updated {
+            return Err(GraphError::UnknownEdge { from, to });
+        }
+        Ok(())
+    }
+
+    /// Returns every Pareto-optimal path from `from` to `to`, i.e. each path
+    /// no other path beats under one criterion without losing under another,
+    /// whose cost holds a value for every criterion. Paths are sorted by
+    /// their first criterion, and of paths with equal costs only one is kept.
+    ///
+    /// Edges without criteria count their cost as the first criterion and
+    /// nothing for the others; missing trailing criteria count as zero.
+    ///
+    /// Fails if either vertex is unknown, an edge cost is negative, or `to`
+    /// is not reachable from `from`.
+    pub fn get_pareto_paths(
+        &self,
+        from: usize,
+        to: usize,
+    ) -> Result<Vec<Path<T, Vec<f64>>>, GraphError> {
+        self.get_vertex(from)?;
+        self.get_vertex(to)?;
+        if let Some(edge) = self.adjacency.iter().flatten().find(|edge| edge.cost < 0.0) {
+            return Err(GraphError::InvalidCost(edge.cost));
+        }
+        let dimensions = self
+            .adjacency
+            .iter()
+            .flatten()
+            .map(|edge| edge.criteria.len())
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        let criteria = |edge: &Edge| -> Vec<f64> {
+            let mut costs = if edge.criteria.is_empty() {
+                vec![edge.cost]
+            } else {
+                edge.criteria.clone()
+            };
+            costs.resize(dimensions, 0.0);
+            costs
+        };
+
+        let mut labels = vec![Label {
+            vertex: from,
+            costs: vec![0.0; dimensions],
+            prev: None,
+            dominated: false,
+        }];
+        // Live labels at each vertex, none dominating another.
+        let mut at: Vec<Vec<usize>> = vec![Vec::new(); self.vertices.len()];
+        at[from].push(0);
+        let mut found = Vec::new();
+
+        // Labels leave the queue by ascending sum of their costs, so a label
+        // can no longer be dominated once it leaves.
+        let mut queue = BinaryHeap::from([(MinNonNan(0.0), Reverse(0))]);
+        while let Some((_, Reverse(label))) = queue.pop() {
+            if labels[label].dominated {
+                continue;
+            }
+            let current = labels[label].vertex;
+            if current == to {
+                found.push(label);
+                continue;
+            }
+
+            for edge in self.outgoing(current) {
+                let costs: Vec<f64> = labels[label]
+                    .costs
+                    .iter()
+                    .zip(criteria(edge))
+                    .map(|(cost, step)| cost + step)
+                    .collect();
+                if costs.contains(&f64::INFINITY) {
+                    continue;
+                }
+                let next = edge.to;
+                let beaten = |other: &usize| dominates(&labels[*other].costs, &costs);
+                if found.iter().any(beaten) || at[next].iter().any(beaten) {
+                    continue;
+                }
+
+                at[next].retain(|other| {
+                    let keep = !dominates(&costs, &labels[*other].costs);
+                    if !keep {
+                        labels[*other].dominated = true;
+                    }
+                    keep
+                });
+                let sum = costs.iter().sum();
+                labels.push(Label {
+                    vertex: next,
+                    costs,
+                    prev: Some(label),
+                    dominated: false,
+                });
+                at[next].push(labels.len() - 1);
+                queue.push((MinNonNan(sum), Reverse(labels.len() - 1)));
+            }
+        }
+
+        if found.is_empty() {
+            return Err(GraphError::NoPathFound { from, to });
+        }
+
+        let mut paths: Vec<Path<T, Vec<f64>>> = found
+            .into_iter()
+            .map(|label| {
+                let mut chain = vec![label];
+                while let Some(before) = labels[*chain.last().unwrap()].prev {
+                    chain.push(before);
+                }
+                chain.reverse();
+                let steps = chain
+                    .windows(2)
+                    .map(|step| {
+                        let (before, after) = (&labels[step[0]].costs, &labels[step[1]].costs);
+                        after.iter().zip(before).map(|(a, b)| a - b).collect()
+                    })
+                    .collect();
+                let path = chain
+                    .iter()
+                    .map(|label| self.vertex(labels[*label].vertex).clone())
+                    .collect();
+                Path::new(path, steps, labels[label].costs.clone())
+            })
+            .collect();
+        paths.sort_by(|a, b| a.cost().partial_cmp(&b.cost()).unwrap());
+        Ok(paths)
+    }
+}
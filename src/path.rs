@@ -0,0 +1,213 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+
+use crate::{Edge, Graph, Vertex, Weight};
+
+/// A path through a graph: its vertices in order, the cost and edge payload
+/// of each step between consecutive vertices, and the total cost.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Path<T, W = f64, E = ()> {
+    vertices: Vec<Vertex<T>>,
+    /// Cost of the step into each vertex after the first.
+    costs: Vec<W>,
+    /// Id of the edge taken by each step, if known.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    edge_ids: Vec<usize>,
+    /// Payload of the edge taken by each step, left out of serialized paths
+    /// when it carries nothing.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "Vec::new", skip_serializing_if = "is_unit")
+    )]
+    data: Vec<E>,
+    cost: W,
+}
+
+impl<T, W> Path<T, W> {
+    pub(crate) fn new(vertices: Vec<Vertex<T>>, costs: Vec<W>, cost: W) -> Self {
+        let data = vec![(); costs.len()];
+        Path::with_edges(vertices, costs, Vec::new(), data, cost)
+    }
+}
+
+impl<T, W, E> Path<T, W, E> {
+    pub(crate) fn with_edges(
+        vertices: Vec<Vertex<T>>,
+        costs: Vec<W>,
+        edge_ids: Vec<usize>,
+        data: Vec<E>,
+        cost: W,
+    ) -> Self {
+        Path {
+            vertices,
+            costs,
+            edge_ids,
+            data,
+            cost,
+        }
+    }
+
+    pub fn vertices(&self) -> &[Vertex<T>] {
+        &self.vertices
+    }
+
+    /// Consumes the path and returns its vertices.
+    pub fn into_vertices(self) -> Vec<Vertex<T>> {
+        self.vertices
+    }
+
+    /// Returns the ids of the vertices in order.
+    pub fn ids(&self) -> Vec<usize> {
+        self.vertices.iter().map(|vertex| vertex.id).collect()
+    }
+
+    /// Returns the cost of each step, one fewer than there are vertices.
+    pub fn costs(&self) -> &[W] {
+        &self.costs
+    }
+
+    /// Returns the id of the edge taken by each step, one fewer than there
+    /// are vertices, which tells apart parallel edges between the same two
+    /// vertices.
+    ///
+    /// Empty for paths whose steps are not single edges of a [`Graph`], such
+    /// as those of [`ContractedGraph`](crate::ContractedGraph) and
+    /// [`FrozenGraph`](crate::FrozenGraph), or that are priced by more than
+    /// the edge cost, such as time-dependent and turn-aware paths.
+    pub fn edge_ids(&self) -> &[usize] {
+        &self.edge_ids
+    }
+
+    /// Returns the payload of the edge taken by each step, one fewer than
+    /// there are vertices.
+    pub fn data(&self) -> &[E] {
+        &self.data
+    }
+
+    /// Returns the total cost of the path.
+    pub fn cost(&self) -> W
+    where
+        W: Clone,
+    {
+        self.cost.clone()
+    }
+
+    /// Returns the number of vertices on the path.
+    pub fn len(&self) -> usize {
+        self.vertices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    pub fn iter(&self) -> core::slice::Iter<'_, Vertex<T>> {
+        self.vertices.iter()
+    }
+}
+
+impl<T, W, E> IntoIterator for Path<T, W, E> {
+    type Item = Vertex<T>;
+    type IntoIter = alloc::vec::IntoIter<Vertex<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.vertices.into_iter()
+    }
+}
+
+impl<'a, T, W, E> IntoIterator for &'a Path<T, W, E> {
+    type Item = &'a Vertex<T>;
+    type IntoIter = core::slice::Iter<'a, Vertex<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.vertices.iter()
+    }
+}
+
+impl<T: Display, W: Display, E> Display for Path<T, W, E> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for (index, vertex) in self.vertices.iter().enumerate() {
+            if index > 0 {
+                write!(f, " -> ")?;
+            }
+            write!(f, "{}", vertex.value)?;
+        }
+        write!(f, " ({})", self.cost)
+    }
+}
+
+/// Whether serializing a path's payloads can be skipped, because they are
+/// zero-sized like `()` and carry no information.
+#[cfg(feature = "serde")]
+fn is_unit<E>(_: &[E]) -> bool {
+    core::mem::size_of::<E>() == 0
+}
+
+impl<T, W, E> Graph<T, W, E>
+where
+    T: Clone,
+    W: Weight,
+    E: Clone,
+{
+    /// Builds the path through `vertices` with the given total cost, taking
+    /// each step's cost, edge id and payload from the cheapest edge between
+    /// its two vertices, adding the entry cost of the vertex it leads to.
+    pub(crate) fn to_path(&self, vertices: Vec<Vertex<T>>, cost: W) -> Path<T, W, E> {
+        self.to_path_filtered(vertices, cost, |_, _| true)
+    }
+
+    /// Like [`Graph::to_path`], but only takes step costs from edges accepted
+    /// by `filter`, as the search that found the path did.
+    pub(crate) fn to_path_filtered<F>(
+        &self,
+        vertices: Vec<Vertex<T>>,
+        cost: W,
+        filter: F,
+    ) -> Path<T, W, E>
+    where
+        F: Fn(&Vertex<T>, &Edge<W, E>) -> bool,
+    {
+        self.to_path_costed(vertices, cost, filter, |edge| edge.cost)
+    }
+
+    /// Like [`Graph::to_path_filtered`], but prices edges with `edge_cost`
+    /// instead of their stored costs, as the search that found the path did.
+    pub(crate) fn to_path_costed<F, C>(
+        &self,
+        vertices: Vec<Vertex<T>>,
+        cost: W,
+        filter: F,
+        edge_cost: C,
+    ) -> Path<T, W, E>
+    where
+        F: Fn(&Vertex<T>, &Edge<W, E>) -> bool,
+        C: Fn(&Edge<W, E>) -> W,
+    {
+        let mut costs = Vec::with_capacity(vertices.len().saturating_sub(1));
+        let mut edge_ids = Vec::with_capacity(costs.capacity());
+        let mut data = Vec::with_capacity(costs.capacity());
+        for step in vertices.windows(2) {
+            let (cheapest, step_cost) = self
+                .outgoing(step[0].id)
+                .filter(|edge| edge.to == step[1].id && filter(&step[0], edge))
+                .map(|edge| (edge, edge_cost(edge)))
+                .fold(
+                    None,
+                    |best: Option<(&Edge<W, E>, W)>, (edge, cost)| match best {
+                        Some(best) if best.1 <= cost => Some(best),
+                        _ => Some((edge, cost)),
+                    },
+                )
+                .expect("every step of a path follows an edge");
+            costs.push(step_cost.saturating_add(self.entry_cost(step[1].id)));
+            edge_ids.push(cheapest.id);
+            data.push(cheapest.data.clone());
+        }
+        Path::with_edges(vertices, costs, edge_ids, data, cost)
+    }
+}
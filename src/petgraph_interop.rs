@@ -0,0 +1,62 @@
+use petgraph::graph::NodeIndex;
+use petgraph::EdgeType;
+
+use crate::{Graph, GraphError};
+
+impl<T, Ty> TryFrom<petgraph::Graph<T, f64, Ty>> for Graph<T>
+where
+    T: Clone,
+    Ty: EdgeType,
+{
+    type Error = GraphError;
+
+    /// Converts a petgraph graph, giving each node the id of its index.
+    /// Edges of an undirected petgraph graph become undirected edges.
+    ///
+    /// Fails with [`GraphError::InvalidCost`] if an edge weight is NaN.
+    fn try_from(other: petgraph::Graph<T, f64, Ty>) -> Result<Self, GraphError> {
+        let mut graph = Graph::new();
+        for node in other.node_weights() {
+            graph.add_vertex(node.clone());
+        }
+        for edge in other.raw_edges() {
+            let (from, to) = (edge.source().index(), edge.target().index());
+            let added = if Ty::is_directed() {
+                graph.add_edge(from, to, edge.weight)
+            } else {
+                graph.add_edge_undirected(from, to, edge.weight)
+            };
+            added?;
+        }
+        Ok(graph)
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Clone,
+{
+    /// Converts the graph into a directed petgraph graph with vertex values as
+    /// node weights and costs as edge weights. Undirected edges become a pair
+    /// of opposite edges.
+    ///
+    /// Node indices equal vertex ids unless vertices have been removed, in
+    /// which case the remaining vertices are renumbered in id order.
+    pub fn to_petgraph(&self) -> petgraph::Graph<T, f64> {
+        let mut other = petgraph::Graph::with_capacity(self.vertices.len(), 0);
+        let mut indices = vec![NodeIndex::end(); self.vertices.len()];
+        for vertex in self.vertices.iter().flatten() {
+            indices[vertex.id] = other.add_node(vertex.value.clone());
+        }
+        for vertex in self.vertices.iter().flatten() {
+            for edge in &self.adjacency[vertex.id] {
+                let (from, to) = (indices[vertex.id], indices[edge.to]);
+                other.add_edge(from, to, edge.cost);
+                if edge.undirected {
+                    other.add_edge(to, from, edge.cost);
+                }
+            }
+        }
+        other
+    }
+}
@@ -0,0 +1,121 @@
+use alloc::vec::Vec;
+
+use crate::{Graph, GraphError, Weight};
+
+/// What [`Graph::add_edge`] and [`Graph::add_edge_undirected`] do when an
+/// edge already leads between the same vertices in a direction the new edge
+/// can be traversed in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicateEdges {
+    /// Adds the new edge next to the existing ones, as a parallel edge.
+    #[default]
+    Allow,
+    /// Fails with [`GraphError::DuplicateEdge`].
+    Reject,
+    /// Removes the existing edges and adds the new one.
+    Replace,
+    /// Keeps the existing edges if one costs no more than the new edge, and
+    /// replaces them otherwise.
+    KeepMin,
+}
+
+/// Checks applied to every edge as it is added, set with
+/// [`Graph::set_insertion_policy`], so that mistakes in input data surface
+/// where they are made instead of as surprising answers later.
+///
+/// NaN costs are always rejected. By default everything else is accepted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InsertionPolicy {
+    duplicates: DuplicateEdges,
+    reject_negative_costs: bool,
+    reject_self_loops: bool,
+}
+
+impl InsertionPolicy {
+    /// Creates the policy that accepts every edge with a cost other than NaN.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets what happens when an edge duplicates an existing one.
+    pub fn on_duplicate(&mut self, duplicates: DuplicateEdges) -> &mut Self {
+        self.duplicates = duplicates;
+        self
+    }
+
+    /// Rejects edges with a negative cost with [`GraphError::InvalidCost`],
+    /// since Dijkstra's algorithm gives wrong answers with them.
+    pub fn reject_negative_costs(&mut self) -> &mut Self {
+        self.reject_negative_costs = true;
+        self
+    }
+
+    /// Rejects edges from a vertex to itself with [`GraphError::SelfLoop`].
+    pub fn reject_self_loops(&mut self) -> &mut Self {
+        self.reject_self_loops = true;
+        self
+    }
+}
+
+impl<T, W, E> Graph<T, W, E>
+where
+    T: Clone,
+    W: Weight,
+    E: Clone,
+{
+    /// Makes every edge added from now on subject to `policy`. Edges already
+    /// in the graph are left as they are.
+    pub fn set_insertion_policy(&mut self, policy: &InsertionPolicy) {
+        self.policy = *policy;
+    }
+
+    /// Returns the policy edges are added under.
+    pub fn insertion_policy(&self) -> &InsertionPolicy {
+        &self.policy
+    }
+
+    /// Applies the insertion policy to a new edge from `from` to `to`, both
+    /// known to exist, removing any edges it replaces. Returns whether the
+    /// edge should be added.
+    pub(crate) fn admit_edge(
+        &mut self,
+        from: usize,
+        to: usize,
+        cost: W,
+        undirected: bool,
+    ) -> Result<bool, GraphError> {
+        let policy = self.policy;
+        if policy.reject_negative_costs && cost < W::ZERO {
+            return Err(GraphError::InvalidCost(cost.to_f64()));
+        }
+        if policy.reject_self_loops && from == to {
+            return Err(GraphError::SelfLoop(from));
+        }
+        if policy.duplicates == DuplicateEdges::Allow {
+            return Ok(true);
+        }
+
+        let mut existing = self
+            .outgoing(from)
+            .filter(|edge| edge.to == to)
+            .collect::<Vec<_>>();
+        if undirected {
+            existing.extend(self.outgoing(to).filter(|edge| edge.to == from));
+        }
+        if existing.is_empty() {
+            return Ok(true);
+        }
+        match policy.duplicates {
+            DuplicateEdges::Allow => Ok(true),
+            DuplicateEdges::Reject => Err(GraphError::DuplicateEdge { from, to }),
+            DuplicateEdges::KeepMin if existing.iter().any(|edge| edge.cost <= cost) => Ok(false),
+            DuplicateEdges::Replace | DuplicateEdges::KeepMin => {
+                self.remove_edges_between(from, to);
+                if undirected {
+                    self.remove_edges_between(to, from);
+                }
+                Ok(true)
+            }
+        }
+    }
+}
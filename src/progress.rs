@@ -0,0 +1,62 @@
+use std::ops::ControlFlow;
+
+use crate::GraphError;
+
+/// Reports how far a long-running operation has got to a callback given by
+/// the caller, such as the `progress` argument of
+/// [`Graph::contract_with_progress`](crate::Graph::contract_with_progress),
+/// and turns a request to stop into [`GraphError::Cancelled`].
+pub(crate) struct Progress<F> {
+    report: F,
+    /// Whole percentage last reported, so that the callback is called at
+    /// most about a hundred times however many steps there are.
+    reported: Option<u32>,
+}
+
+impl<F> Progress<F>
+where
+    F: FnMut(f64) -> ControlFlow<()>,
+{
+    /// Wraps `report` and tells it that the operation has started.
+    pub(crate) fn start(report: F) -> Result<Self, GraphError> {
+        let mut progress = Progress {
+            report,
+            reported: None,
+        };
+        progress.update(0.0)?;
+        Ok(progress)
+    }
+
+    /// Reports that `fraction` of the work is done, as a percentage, if it
+    /// has moved on by a whole percent since the last report.
+    ///
+    /// Fails with [`GraphError::Cancelled`] if the callback asks to stop.
+    pub(crate) fn update(&mut self, fraction: f64) -> Result<(), GraphError> {
+        let percent = (fraction * 100.0).clamp(0.0, 100.0);
+        let whole = percent as u32;
+        if self.reported.is_some_and(|reported| reported >= whole) {
+            return Ok(());
+        }
+        self.reported = Some(whole);
+        match (self.report)(percent) {
+            ControlFlow::Continue(()) => Ok(()),
+            ControlFlow::Break(()) => Err(GraphError::Cancelled),
+        }
+    }
+
+    /// Reports that the operation is complete. Asking to stop has no effect
+    /// any more.
+    pub(crate) fn finish(mut self) {
+        let _ = self.update(1.0);
+    }
+
+    /// Reports that `done` of `total` steps are done, as
+    /// [`Progress::update`] does.
+    pub(crate) fn steps(&mut self, done: usize, total: usize) -> Result<(), GraphError> {
+        self.update(if total == 0 {
+            1.0
+        } else {
+            done as f64 / total as f64
+        })
+    }
+}
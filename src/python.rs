@@ -0,0 +1,82 @@
+use pyo3::exceptions::{PyIndexError, PyValueError};
+use pyo3::prelude::*;
+
+use crate::{Graph, GraphError};
+
+// The crate builds as a Rust library only, so that it also links without
+// `std`. Build the extension module with `cargo rustc --lib --release
+// --features python --crate-type cdylib` and rename the output to
+// `dijkstra_rust.so`, or `dijkstra_rust.pyd` on Windows.
+
+/// A graph for Python, exported as `dijkstra_rust.Graph`: vertices hold any
+/// Python object and edges cost a float.
+#[pyclass(name = "Graph", module = "dijkstra_rust")]
+pub struct PyGraph {
+    graph: Graph<()>,
+    /// Value of each vertex, indexed by id.
+    values: Vec<Py<PyAny>>,
+}
+
+#[pymethods]
+impl PyGraph {
+    /// Creates an empty graph.
+    #[new]
+    fn new() -> Self {
+        PyGraph {
+            graph: Graph::new(),
+            values: Vec::new(),
+        }
+    }
+
+    /// Adds a vertex holding `value` and returns its id.
+    #[pyo3(signature = (value = None))]
+    fn add_vertex(&mut self, py: Python<'_>, value: Option<Py<PyAny>>) -> usize {
+        self.values
+            .push(value.unwrap_or_else(|| py.None().into_any()));
+        self.graph.add_vertex(())
+    }
+
+    /// Returns the value of the vertex with the given id.
+    fn value(&self, py: Python<'_>, id: usize) -> PyResult<Py<PyAny>> {
+        self.graph.get_vertex(id).map_err(to_py_err)?;
+        Ok(self.values[id].clone_ref(py))
+    }
+
+    /// Adds a directed edge from `from_` to `to` with the given cost.
+    fn add_edge(&mut self, from_: usize, to: usize, cost: f64) -> PyResult<()> {
+        self.graph.add_edge(from_, to, cost).map_err(to_py_err)
+    }
+
+    /// Adds an edge between `a` and `b` that can be traversed in both
+    /// directions.
+    fn add_edge_undirected(&mut self, a: usize, b: usize, cost: f64) -> PyResult<()> {
+        self.graph
+            .add_edge_undirected(a, b, cost)
+            .map_err(to_py_err)
+    }
+
+    /// Returns the ids along the cheapest path from `from_` to `to` and its
+    /// cost.
+    fn shortest_path(&self, from_: usize, to: usize) -> PyResult<(Vec<usize>, f64)> {
+        let path = self.graph.get_shortest_path(from_, to).map_err(to_py_err)?;
+        Ok((path.iter().map(|vertex| vertex.id).collect(), path.cost()))
+    }
+
+    fn __len__(&self) -> usize {
+        self.values.len()
+    }
+}
+
+/// Raises `IndexError` for unknown vertices and `ValueError` otherwise.
+fn to_py_err(error: GraphError) -> PyErr {
+    match error {
+        GraphError::UnknownVertex(_) => PyIndexError::new_err(error.to_string()),
+        error => PyValueError::new_err(error.to_string()),
+    }
+}
+
+/// The `dijkstra_rust` Python module.
+#[pymodule]
+fn dijkstra_rust(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyGraph>()
+}
@@ -0,0 +1,203 @@
+use std::cell::Cell;
+use std::collections::HashSet;
+
+use crate::{Edge, Graph, GraphError, Modes, Path, Vertex, Weight};
+
+/// Restrictions that apply to a single query, for
+/// [`Graph::get_shortest_path_with`], so that e.g. "what if this vertex is
+/// down" scenarios need no copy of the graph.
+///
+/// Setters return the options again, so that they can be chained inline:
+/// `graph.get_shortest_path_with(a, b, QueryOptions::new().avoid_vertices(&[c]))`.
+#[derive(Debug, Clone)]
+pub struct QueryOptions {
+    avoided_vertices: HashSet<usize>,
+    /// Avoided edges by the direction they may not be traversed in.
+    avoided_edges: HashSet<(usize, usize)>,
+    ties_by_id: bool,
+    modes: Modes,
+}
+
+impl Default for QueryOptions {
+    fn default() -> Self {
+        QueryOptions {
+            avoided_vertices: HashSet::new(),
+            avoided_edges: HashSet::new(),
+            ties_by_id: false,
+            modes: Modes::ALL,
+        }
+    }
+}
+
+impl QueryOptions {
+    /// Creates options without any restriction.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forbids the path from passing through any of `ids`, including as its
+    /// endpoints.
+    pub fn avoid_vertices(&mut self, ids: &[usize]) -> &mut Self {
+        self.avoided_vertices.extend(ids);
+        self
+    }
+
+    /// Forbids the path from traversing any edge from the first to the second
+    /// vertex of each pair. An undirected edge stays usable in the other
+    /// direction unless that is avoided too.
+    pub fn avoid_edges(&mut self, edges: &[(usize, usize)]) -> &mut Self {
+        self.avoided_edges.extend(edges);
+        self
+    }
+
+    /// Restricts the path to edges that allow at least one of `modes`, e.g.
+    /// `Modes::WALK` for a pedestrian route. Replaces any earlier
+    /// restriction.
+    pub fn allow_modes(&mut self, modes: Modes) -> &mut Self {
+        self.modes = modes;
+        self
+    }
+
+    /// Breaks ties between equally cheap paths by vertex id: each vertex on the
+    /// path is entered from the lowest id a cheapest path can come from.
+    /// Without it, ties go to whichever vertex the search settles first,
+    /// which is just as reproducible but harder to predict, e.g. when writing
+    /// golden tests.
+    pub fn break_ties_by_id(&mut self) -> &mut Self {
+        self.ties_by_id = true;
+        self
+    }
+
+    /// Returns whether the options allow stepping from `from` along `edge`.
+    pub(crate) fn allows<W, E>(&self, from: usize, edge: &Edge<W, E>) -> bool {
+        edge.modes.intersects(self.modes)
+            && !self.avoided_vertices.contains(&edge.to)
+            && !self.avoided_edges.contains(&(from, edge.to))
+    }
+}
+
+impl<T, W, E> Graph<T, W, E>
+where
+    T: Clone,
+    W: Weight,
+    E: Clone,
+{
+    /// Like [`Graph::get_shortest_path`], but subject to the restrictions in
+    /// `options`.
+    ///
+    /// Fails if either vertex is unknown or `to` is not reachable from `from`
+    /// under the restrictions.
+    pub fn get_shortest_path_with(
+        &self,
+        from: usize,
+        to: usize,
+        options: &QueryOptions,
+    ) -> Result<Path<T, W, E>, GraphError> {
+        span!(DEBUG, "Graph::get_shortest_path_with", from, to);
+        self.get_vertex(from)?;
+        self.get_vertex(to)?;
+        if options.avoided_vertices.contains(&from) || options.avoided_vertices.contains(&to) {
+            return Err(GraphError::NoPathFound { from, to });
+        }
+
+        let filter = |vertex: &Vertex<T>, edge: &Edge<W, E>| options.allows(vertex.id, edge);
+        if options.ties_by_id {
+            return self.get_shortest_path_by_id(from, to, filter);
+        }
+        self.get_shortest_path_filtered(from, to, filter)
+    }
+
+    /// Like [`Graph::get_shortest_path_filtered`], but enters each vertex on
+    /// the path from the lowest id that keeps the path cheapest.
+    fn get_shortest_path_by_id<F>(
+        &self,
+        from: usize,
+        to: usize,
+        filter: F,
+    ) -> Result<Path<T, W, E>, GraphError>
+    where
+        F: Fn(&Vertex<T>, &Edge<W, E>) -> bool,
+    {
+        // Settles every vertex as close as `to`, ranking them in the order
+        // they are settled so that following predecessors cannot loop along
+        // zero-cost edges.
+        let bound = Cell::new(W::INFINITY);
+        let ranks: Vec<Cell<usize>> = (0..self.vertices.len())
+            .map(|_| Cell::new(usize::MAX))
+            .collect();
+        let settled = Cell::new(0);
+        let is_past_target = |vertex: &Vertex<T>, distance: W| {
+            if distance > bound.get() {
+                return true;
+            }
+            if vertex.id == to {
+                bound.set(distance);
+            }
+            ranks[vertex.id].set(settled.get());
+            settled.set(settled.get() + 1);
+            false
+        };
+        let (dist, _, _) =
+            self.search_until(&[(from, W::ZERO)], is_past_target, |_| W::ZERO, &filter);
+        if dist[to] == W::INFINITY {
+            return Err(GraphError::NoPathFound { from, to });
+        }
+
+        let mut ids = vec![to];
+        let mut at = to;
+        while at != from {
+            let cheapest_from = |pred: usize| {
+                ranks[pred].get() < ranks[at].get()
+                    && self.outgoing(pred).any(|edge| {
+                        edge.to == at
+                            && dist[pred]
+                                .saturating_add(edge.cost)
+                                .saturating_add(self.entry_cost(at))
+                                == dist[at]
+                            && filter(self.vertex(pred), edge)
+                    })
+            };
+            at = self
+                .incoming(at)
+                .map(|edge| edge.to)
+                .filter(|pred| cheapest_from(*pred))
+                .min()
+                .unwrap();
+            ids.push(at);
+        }
+        let path = ids
+            .iter()
+            .rev()
+            .map(|id| self.vertex(*id).clone())
+            .collect();
+        Ok(self.to_path_filtered(path, dist[to], filter))
+    }
+
+    /// Like [`Graph::get_shortest_path`], but only relaxes the edges accepted
+    /// by `filter`, which receives the vertex an edge is traversed from and
+    /// the edge itself, e.g. to restrict a query to edges with spare capacity.
+    ///
+    /// Fails if either vertex is unknown or `to` is not reachable from `from`
+    /// over the accepted edges.
+    pub fn get_shortest_path_filtered<F>(
+        &self,
+        from: usize,
+        to: usize,
+        filter: F,
+    ) -> Result<Path<T, W, E>, GraphError>
+    where
+        F: Fn(&Vertex<T>, &Edge<W, E>) -> bool,
+    {
+        span!(DEBUG, "Graph::get_shortest_path_filtered", from, to);
+        self.get_vertex(from)?;
+        self.get_vertex(to)?;
+
+        let (dist, prev) = self.search_filtered(from, Some(to), |_| W::ZERO, &filter);
+        if dist[to] == W::INFINITY {
+            return Err(GraphError::NoPathFound { from, to });
+        }
+
+        let path = self.reconstruct_path(&prev, from, to);
+        Ok(self.to_path_filtered(path, dist[to], filter))
+    }
+}
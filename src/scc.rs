@@ -0,0 +1,161 @@
+use crate::{Edge, Graph, Weight};
+
+/// The strongly connected components of a graph, i.e. the groups of vertices
+/// that can all reach each other, together with the condensation: the
+/// acyclic graph left when each component is merged into one vertex.
+///
+/// Built with [`Graph::strongly_connected_components`]. Components are
+/// numbered in topological order, so every edge between two components leads
+/// from the lower to the higher index.
+pub struct Condensation<W = f64> {
+    components: Vec<Vec<usize>>,
+    /// Component of each vertex id, `usize::MAX` for removed vertices.
+    component: Vec<usize>,
+    dag: Graph<usize, W>,
+}
+
+impl<W> Condensation<W>
+where
+    W: Weight,
+{
+    /// Returns the vertex ids of each component in ascending order, indexed
+    /// by component.
+    pub fn components(&self) -> &[Vec<usize>] {
+        &self.components
+    }
+
+    /// Returns the index of the component holding vertex `id`, or `None` if
+    /// there is no such vertex.
+    pub fn component_of(&self, id: usize) -> Option<usize> {
+        self.component
+            .get(id)
+            .copied()
+            .filter(|component| *component != usize::MAX)
+    }
+
+    /// Returns the condensation, with one vertex per component whose id and
+    /// value are the component's index, and one edge between two components
+    /// for the cheapest edge linking them.
+    pub fn dag(&self) -> &Graph<usize, W> {
+        &self.dag
+    }
+}
+
+impl<T, W> Graph<T, W>
+where
+    T: Clone,
+    W: Weight,
+{
+    /// Splits the vertices into strongly connected components with Tarjan's
+    /// algorithm, and builds the condensation between them. Edges of
+    /// infinite cost count as blocked, as they do for searches.
+    ///
+    /// A graph with more than one component has pairs of vertices where one
+    /// cannot reach the other.
+    pub fn strongly_connected_components(&self) -> Condensation<W> {
+        let slots = self.vertices.len();
+        let mut index = vec![usize::MAX; slots];
+        let mut low_link = vec![0; slots];
+        let mut on_stack = vec![false; slots];
+        let mut stack = Vec::new();
+        let mut component = vec![usize::MAX; slots];
+        let mut components: Vec<Vec<usize>> = Vec::new();
+        let mut visited = 0;
+
+        // Walks depth first without recursion, keeping the remaining edges
+        // of each vertex on the path being explored.
+        for root in self.vertices.iter().flatten() {
+            if index[root.id] != usize::MAX {
+                continue;
+            }
+            index[root.id] = visited;
+            low_link[root.id] = visited;
+            visited += 1;
+            stack.push(root.id);
+            on_stack[root.id] = true;
+            let mut path = vec![(root.id, self.outgoing(root.id))];
+
+            while let Some((current, edges)) = path.last_mut() {
+                let current = *current;
+                if let Some(edge) = edges.next() {
+                    let next = edge.to;
+                    if edge.cost == W::INFINITY {
+                        continue;
+                    }
+                    if index[next] == usize::MAX {
+                        index[next] = visited;
+                        low_link[next] = visited;
+                        visited += 1;
+                        stack.push(next);
+                        on_stack[next] = true;
+                        path.push((next, self.outgoing(next)));
+                    } else if on_stack[next] {
+                        low_link[current] = low_link[current].min(index[next]);
+                    }
+                    continue;
+                }
+
+                path.pop();
+                if let Some((parent, _)) = path.last() {
+                    low_link[*parent] = low_link[*parent].min(low_link[current]);
+                }
+                if low_link[current] == index[current] {
+                    let mut members = Vec::new();
+                    loop {
+                        let member = stack.pop().unwrap();
+                        on_stack[member] = false;
+                        component[member] = components.len();
+                        members.push(member);
+                        if member == current {
+                            break;
+                        }
+                    }
+                    members.sort_unstable();
+                    components.push(members);
+                }
+            }
+        }
+
+        // Tarjan's algorithm completes components in reverse topological
+        // order.
+        components.reverse();
+        let count = components.len();
+        for id in component.iter_mut().filter(|id| **id != usize::MAX) {
+            *id = count - 1 - *id;
+        }
+
+        let mut dag = Graph::new();
+        for index in 0..count {
+            dag.add_vertex(index);
+        }
+        let mut cheapest = vec![W::INFINITY; count];
+        let mut linked = Vec::new();
+        for (from, members) in components.iter().enumerate() {
+            for id in members {
+                for edge in self.outgoing(*id) {
+                    let to = component[edge.to];
+                    if to == from || edge.cost == W::INFINITY {
+                        continue;
+                    }
+                    if cheapest[to] == W::INFINITY {
+                        linked.push(to);
+                    }
+                    if edge.cost < cheapest[to] {
+                        cheapest[to] = edge.cost;
+                    }
+                }
+            }
+            linked.sort_unstable();
+            for to in linked.drain(..) {
+                dag.push_edge(from, Edge::new(to, cheapest[to]));
+                cheapest[to] = W::INFINITY;
+            }
+        }
+
+        Condensation {
+            components,
+            component,
+            dag,
+        }
+    }
+}
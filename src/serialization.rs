@@ -0,0 +1,124 @@
+use std::collections::HashSet;
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Graph, Modes, TravelTimeProfile};
+
+/// Serialized layout of a [`Graph`]: vertex values indexed by id, with `None`
+/// for removed vertices, and a flat edge list.
+#[derive(Serialize, Deserialize)]
+struct GraphRecord<T> {
+    vertices: Vec<Option<T>>,
+    edges: Vec<EdgeRecord>,
+    /// Entry cost of each vertex by id, empty if none was set.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    entry_costs: Vec<f64>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct EdgeRecord {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    id: Option<usize>,
+    from: usize,
+    to: usize,
+    cost: f64,
+    #[serde(default)]
+    undirected: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    profile: Option<TravelTimeProfile>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    criteria: Vec<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    capacity: Option<f64>,
+    #[serde(default, skip_serializing_if = "is_all")]
+    modes: Modes,
+}
+
+fn is_all(modes: &Modes) -> bool {
+    *modes == Modes::ALL
+}
+
+impl<T: Serialize> Serialize for Graph<T>
+where
+    T: Clone,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let record = GraphRecord {
+            vertices: self
+                .vertices
+                .iter()
+                .map(|vertex| vertex.as_ref().map(|vertex| vertex.value.clone()))
+                .collect(),
+            edges: self
+                .vertices
+                .iter()
+                .flatten()
+                .flat_map(|vertex| {
+                    self.adjacency[vertex.id].iter().map(|edge| EdgeRecord {
+                        id: Some(edge.id),
+                        from: vertex.id,
+                        to: edge.to,
+                        cost: edge.cost,
+                        undirected: edge.undirected,
+                        profile: edge.profile.clone(),
+                        criteria: edge.criteria.clone(),
+                        capacity: edge.capacity,
+                        modes: edge.modes,
+                    })
+                })
+                .collect(),
+            entry_costs: self.entry_costs.clone(),
+        };
+        record.serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Graph<T>
+where
+    T: Clone,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let record = GraphRecord::<T>::deserialize(deserializer)?;
+
+        let mut graph = Graph::new();
+        let mut ids = HashSet::new();
+        for value in record.vertices {
+            graph.push_slot(value);
+        }
+        for edge in record.edges {
+            let added = if edge.undirected {
+                graph.add_edge_undirected(edge.from, edge.to, edge.cost)
+            } else {
+                graph.add_edge(edge.from, edge.to, edge.cost)
+            };
+            added.map_err(D::Error::custom)?;
+            // Edges recorded without ids keep the ones just handed out.
+            let id = edge.id.unwrap_or(graph.next_edge_id - 1);
+            if !ids.insert(id) {
+                return Err(D::Error::custom(format!("duplicate edge id {}", id)));
+            }
+            graph.next_edge_id = graph.next_edge_id.max(id + 1);
+            let stored = graph.adjacency[edge.from].last_mut().unwrap();
+            stored.id = id;
+            stored.profile = edge.profile.clone();
+            stored.criteria = edge.criteria.clone();
+            stored.capacity = edge.capacity;
+            stored.modes = edge.modes;
+            let mirrored = graph.reverse_adjacency[edge.to].last_mut().unwrap();
+            mirrored.id = id;
+            mirrored.profile = edge.profile;
+            mirrored.criteria = edge.criteria;
+            mirrored.capacity = edge.capacity;
+            mirrored.modes = edge.modes;
+        }
+
+        for (id, cost) in record.entry_costs.into_iter().enumerate() {
+            if cost != 0.0 && graph.get_vertex(id).is_ok() {
+                graph.set_entry_cost(id, cost).map_err(D::Error::custom)?;
+            }
+        }
+
+        Ok(graph)
+    }
+}
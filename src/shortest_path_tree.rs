@@ -0,0 +1,154 @@
+use std::iter::FusedIterator;
+
+use crate::graph::Predecessors;
+use crate::{Graph, GraphError, Weight};
+
+/// Shortest paths from one source vertex to every vertex reachable from it.
+#[derive(Debug, Clone)]
+pub struct ShortestPathTree<W = f64> {
+    pub(crate) source: usize,
+    pub(crate) dist: Vec<W>,
+    pub(crate) prev: Predecessors,
+}
+
+impl<W: Weight> ShortestPathTree<W> {
+    /// Returns the id of the vertex the tree was grown from.
+    pub fn source(&self) -> usize {
+        self.source
+    }
+
+    /// Returns the cost of the shortest path to `id`, or `None` if it is
+    /// unreachable.
+    pub fn distance_to(&self, id: usize) -> Option<W> {
+        self.dist
+            .get(id)
+            .copied()
+            .filter(|distance| *distance != W::INFINITY)
+    }
+
+    /// Returns the vertex ids along the shortest path to `id`, or `None` if it
+    /// is unreachable.
+    pub fn path_to(&self, id: usize) -> Option<Vec<usize>> {
+        let mut path = Vec::new();
+        self.path_to_into(id, &mut path).then_some(path)
+    }
+
+    /// Returns an iterator over the vertex ids along the shortest path to
+    /// `id`, from `id` back to the source, or `None` if it is unreachable.
+    ///
+    /// Walks the tree as it goes, without allocating.
+    pub fn iter_path_to(&self, id: usize) -> Option<PathIds<'_, W>> {
+        self.distance_to(id)?;
+        Some(PathIds {
+            tree: self,
+            next: Some(id),
+        })
+    }
+
+    /// Replaces the contents of `path` with the vertex ids along the shortest
+    /// path to `id`, from the source to `id`, and returns whether it is
+    /// reachable.
+    ///
+    /// Reusing `path` across queries avoids allocating once it has grown to
+    /// the longest path.
+    pub fn path_to_into(&self, id: usize, path: &mut Vec<usize>) -> bool {
+        path.clear();
+        let Some(ids) = self.iter_path_to(id) else {
+            return false;
+        };
+        path.extend(ids);
+        path.reverse();
+        true
+    }
+}
+
+/// Iterator over the vertex ids along a path in a [`ShortestPathTree`], from
+/// the target back to the source.
+///
+/// Returned by [`ShortestPathTree::iter_path_to`].
+#[derive(Debug, Clone)]
+pub struct PathIds<'a, W = f64> {
+    tree: &'a ShortestPathTree<W>,
+    next: Option<usize>,
+}
+
+impl<W> Iterator for PathIds<'_, W> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let id = self.next?;
+        self.next = if id == self.tree.source {
+            None
+        } else {
+            self.tree.prev[id].map(|prev| prev as usize)
+        };
+        Some(id)
+    }
+}
+
+impl<W> FusedIterator for PathIds<'_, W> {}
+
+/// Shortest paths into one target vertex from every vertex that can reach
+/// it.
+#[derive(Debug, Clone)]
+pub struct ShortestPathsTo<W = f64> {
+    target: usize,
+    dist: Vec<W>,
+    /// Vertex each vertex continues to on its shortest path to the target.
+    next: Predecessors,
+}
+
+impl<W: Weight> ShortestPathsTo<W> {
+    /// Returns the id of the vertex the paths lead to.
+    pub fn target(&self) -> usize {
+        self.target
+    }
+
+    /// Returns the cost of the shortest path from `id`, or `None` if the
+    /// target is unreachable from it.
+    pub fn distance_from(&self, id: usize) -> Option<W> {
+        self.dist
+            .get(id)
+            .copied()
+            .filter(|distance| *distance != W::INFINITY)
+    }
+
+    /// Returns the vertex ids along the shortest path from `id` to the target,
+    /// or `None` if the target is unreachable from it.
+    pub fn path_from(&self, id: usize) -> Option<Vec<usize>> {
+        self.distance_from(id)?;
+
+        let mut path = vec![id];
+        let mut at = id;
+        while at != self.target {
+            at = self.next[at]? as usize;
+            path.push(at);
+        }
+        Some(path)
+    }
+}
+
+impl<T, W, E> Graph<T, W, E>
+where
+    T: Clone,
+    W: Weight,
+    E: Clone,
+{
+    /// Runs Dijkstra from `source` until every reachable vertex is settled.
+    pub fn shortest_paths_from(&self, source: usize) -> Result<ShortestPathTree<W>, GraphError> {
+        self.get_vertex(source)?;
+        let (dist, prev) = self.search(source, None, |_| W::ZERO);
+
+        Ok(ShortestPathTree { source, dist, prev })
+    }
+
+    /// Runs Dijkstra backwards from `target`, following edges against their
+    /// direction, to find the cheapest path from every vertex into it with a
+    /// single search.
+    pub fn shortest_paths_to(&self, target: usize) -> Result<ShortestPathsTo<W>, GraphError> {
+        self.get_vertex(target)?;
+        let (dist, next) = self.search_backward(target);
+
+        Ok(ShortestPathsTo { target, dist, next })
+    }
+}
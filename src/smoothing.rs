@@ -0,0 +1,45 @@
+use crate::theta_star::euclidean;
+use crate::{GraphError, Grid};
+
+impl Grid {
+    /// Straightens a path found on the grid, e.g. by
+    /// [`Grid::jump_point_search`] or on the graph of [`Graph::from_grid`]
+    /// via [`Path::ids`], by string pulling: every waypoint that the one
+    /// before it can see past is dropped, so the path cuts across open space
+    /// instead of following cell steps.
+    ///
+    /// Returns the ids of the cells where the smoothed path turns, starting
+    /// and ending where `path` does, and its Euclidean length between cell
+    /// centres, which is never longer than that of `path`.
+    ///
+    /// Fails if a cell of `path` is outside the grid.
+    ///
+    /// [`Graph::from_grid`]: crate::Graph::from_grid
+    /// [`Path::ids`]: crate::Path::ids
+    pub fn smooth_path(&self, path: &[usize]) -> Result<(Vec<usize>, f64), GraphError> {
+        if let Some(id) = path.iter().find(|id| **id >= self.width() * self.height()) {
+            return Err(GraphError::UnknownVertex(*id));
+        }
+        let Some((&last, steps)) = path.split_last() else {
+            return Ok((Vec::new(), 0.0));
+        };
+
+        let mut waypoints = vec![path[0]];
+        let mut length = 0.0;
+        for (index, &cell) in steps.iter().enumerate().skip(1) {
+            let anchor = self.position(*waypoints.last().unwrap());
+            if !self.line_of_sight(anchor, self.position(path[index + 1])) {
+                length += euclidean(anchor, self.position(cell));
+                waypoints.push(cell);
+            }
+        }
+        if path.len() > 1 {
+            length += euclidean(
+                self.position(*waypoints.last().unwrap()),
+                self.position(last),
+            );
+            waypoints.push(last);
+        }
+        Ok((waypoints, length))
+    }
+}
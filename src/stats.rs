@@ -0,0 +1,113 @@
+use std::collections::HashSet;
+use std::fmt::{self, Debug, Formatter};
+use std::time::{Duration, Instant};
+
+use crate::{Edge, Graph, GraphError, Path, SearchVisitor, Vertex, Weight};
+
+/// Counts of the work done by a search, for comparing heuristics and
+/// preprocessing.
+///
+/// Collected by [`Graph::get_shortest_path_with_stats`], or by passing a
+/// `SearchStats` as the visitor of [`Graph::get_shortest_path_visited`], in
+/// which case the wall time is left at zero.
+#[derive(Clone, Default)]
+pub struct SearchStats {
+    settled: usize,
+    relaxed: usize,
+    decreased: usize,
+    peak_frontier: usize,
+    elapsed: Duration,
+    /// Vertices that have entered the frontier, settled or not.
+    reached: HashSet<usize>,
+}
+
+impl SearchStats {
+    /// Creates empty statistics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of vertices settled, which is also the number of
+    /// pops from the frontier.
+    pub fn settled(&self) -> usize {
+        self.settled
+    }
+
+    /// Returns the number of edges that lowered the distance of their target.
+    pub fn relaxed(&self) -> usize {
+        self.relaxed
+    }
+
+    /// Returns the number of vertices pushed onto the frontier.
+    pub fn pushes(&self) -> usize {
+        self.reached.len()
+    }
+
+    /// Returns how often a vertex already on the frontier was moved forward
+    /// instead of being pushed again.
+    pub fn decreases(&self) -> usize {
+        self.decreased
+    }
+
+    /// Returns the most vertices that were on the frontier at once.
+    pub fn peak_frontier(&self) -> usize {
+        self.peak_frontier
+    }
+
+    /// Returns the wall time the search took.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+
+impl Debug for SearchStats {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("SearchStats")
+            .field("settled", &self.settled)
+            .field("relaxed", &self.relaxed)
+            .field("pushes", &self.pushes())
+            .field("decreases", &self.decreased)
+            .field("peak_frontier", &self.peak_frontier)
+            .field("elapsed", &self.elapsed)
+            .finish()
+    }
+}
+
+impl<T, W, E> SearchVisitor<T, W, E> for SearchStats {
+    fn on_settle(&mut self, vertex: &Vertex<T>, _: W) {
+        // Sources enter the frontier without a relaxed edge.
+        if self.reached.insert(vertex.id) {
+            self.peak_frontier = self.peak_frontier.max(self.reached.len() - self.settled);
+        }
+        self.settled += 1;
+    }
+
+    fn on_relax(&mut self, _: &Vertex<T>, edge: &Edge<W, E>, _: W) {
+        self.relaxed += 1;
+        if self.reached.insert(edge.to) {
+            self.peak_frontier = self.peak_frontier.max(self.reached.len() - self.settled);
+        } else {
+            self.decreased += 1;
+        }
+    }
+}
+
+impl<T, W> Graph<T, W>
+where
+    T: Clone,
+    W: Weight,
+{
+    /// Like [`Graph::get_shortest_path`], but also returns statistics about
+    /// the search, whether or not it found a path.
+    pub fn get_shortest_path_with_stats(
+        &self,
+        from: usize,
+        to: usize,
+    ) -> (Result<Path<T, W>, GraphError>, SearchStats) {
+        let mut stats = SearchStats::new();
+        let start = Instant::now();
+        let path = self.get_shortest_path_visited(from, to, &mut stats);
+        stats.elapsed = start.elapsed();
+        (path, stats)
+    }
+}
@@ -0,0 +1,141 @@
+use std::iter::FusedIterator;
+
+use crate::indexed_heap::IndexedHeap;
+use crate::{Graph, GraphError, Path, Weight};
+
+/// A Dijkstra search from one vertex to another that settles one vertex per
+/// call to [`Iterator::next`], so that its state can be inspected in
+/// between, e.g. by a visualizer or debugger.
+///
+/// Each step yields the id of the settled vertex and its distance. The
+/// search ends once the target is settled or nothing is left to settle.
+///
+/// Returned by [`Graph::dijkstra_steps`].
+pub struct DijkstraSteps<'a, T, W = f64> {
+    graph: &'a Graph<T, W>,
+    from: usize,
+    to: usize,
+    dist: Vec<W>,
+    /// Kept as plain ids for [`DijkstraSteps::predecessors`].
+    prev: Vec<Option<usize>>,
+    settled: Vec<bool>,
+    queue: IndexedHeap<W>,
+}
+
+impl<'a, T, W> DijkstraSteps<'a, T, W>
+where
+    T: Clone,
+    W: Weight,
+{
+    /// Returns the tentative distance of every vertex, indexed by id and
+    /// infinite for vertices not reached yet.
+    pub fn distances(&self) -> &[W] {
+        &self.dist
+    }
+
+    /// Returns the vertex each vertex was reached from, indexed by id.
+    pub fn predecessors(&self) -> &[Option<usize>] {
+        &self.prev
+    }
+
+    /// Returns whether `id` has been settled at its final distance.
+    pub fn is_settled(&self, id: usize) -> bool {
+        self.settled.get(id).copied().unwrap_or(false)
+    }
+
+    /// Returns the ids of the vertices reached but not yet settled with their
+    /// tentative distances, closest first.
+    pub fn frontier(&self) -> Vec<(usize, W)> {
+        let mut frontier: Vec<(usize, W)> = self.queue.iter().map(|(key, id)| (id, key)).collect();
+        frontier.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap().then(a.0.cmp(&b.0)));
+        frontier
+    }
+
+    /// Returns the path to the target once it has been settled.
+    pub fn path(&self) -> Option<Path<T, W>> {
+        if !self.settled[self.to] {
+            return None;
+        }
+        let mut path = vec![self.graph.vertex(self.to).clone()];
+        let mut at = self.to;
+        while at != self.from {
+            at = self.prev[at].unwrap();
+            path.push(self.graph.vertex(at).clone());
+        }
+        path.reverse();
+        Some(self.graph.to_path(path, self.dist[self.to]))
+    }
+}
+
+impl<T, W> Iterator for DijkstraSteps<'_, T, W>
+where
+    T: Clone,
+    W: Weight,
+{
+    type Item = (usize, W);
+
+    fn next(&mut self) -> Option<(usize, W)> {
+        if self.settled[self.to] {
+            return None;
+        }
+        let (_, current) = self.queue.pop()?;
+        self.settled[current] = true;
+        if current != self.to {
+            for edge in self.graph.outgoing(current) {
+                if self.settled[edge.to] {
+                    continue;
+                }
+                let new_dist = self.dist[current]
+                    .saturating_add(edge.cost)
+                    .saturating_add(self.graph.entry_cost(edge.to));
+                if new_dist < self.dist[edge.to] {
+                    self.prev[edge.to] = Some(current);
+                    self.dist[edge.to] = new_dist;
+                    self.queue.push_or_decrease(edge.to, new_dist);
+                }
+            }
+        }
+        Some((current, self.dist[current]))
+    }
+}
+
+impl<T, W> FusedIterator for DijkstraSteps<'_, T, W>
+where
+    T: Clone,
+    W: Weight,
+{
+}
+
+impl<T, W> Graph<T, W>
+where
+    T: Clone,
+    W: Weight,
+{
+    /// Starts a Dijkstra search from `from` to `to` that runs one settled
+    /// vertex at a time, as [`Graph::get_shortest_path`] would in one go.
+    ///
+    /// Fails if either vertex is unknown.
+    pub fn dijkstra_steps(
+        &self,
+        from: usize,
+        to: usize,
+    ) -> Result<DijkstraSteps<'_, T, W>, GraphError> {
+        self.get_vertex(from)?;
+        self.get_vertex(to)?;
+
+        let slots = self.vertices.len();
+        let mut dist = vec![W::INFINITY; slots];
+        dist[from] = W::ZERO;
+        let mut queue = IndexedHeap::new(slots);
+        queue.push_or_decrease(from, W::ZERO);
+        Ok(DijkstraSteps {
+            graph: self,
+            from,
+            to,
+            dist,
+            prev: vec![None; slots],
+            settled: vec![false; slots],
+            queue,
+        })
+    }
+}
@@ -0,0 +1,57 @@
+use crate::{Graph, Weight};
+
+/// Read access to the vertices and edges of a graph, whatever its
+/// representation, for algorithms that work on any of them.
+///
+/// Implemented by the adjacency lists of [`Graph`] and
+/// [`FrozenGraph`](crate::FrozenGraph), which suit large sparse graphs, and
+/// by the dense [`AdjacencyMatrix`](crate::AdjacencyMatrix), which suits
+/// small dense ones. Only the stored edge costs are exposed, without
+/// [entry costs](Graph::set_entry_cost).
+pub trait GraphStorage<W = f64> {
+    /// Returns one more than the highest vertex id, counting removed
+    /// vertices.
+    fn id_bound(&self) -> usize;
+
+    /// Whether a vertex with the given id exists.
+    fn contains_vertex(&self, id: usize) -> bool;
+
+    /// Returns the edges that can be traversed out of `id`, as the vertex
+    /// each leads to and its cost, including undirected edges stored at
+    /// their other end. Empty if the vertex does not exist.
+    fn edges_from(&self, id: usize) -> impl Iterator<Item = (usize, W)> + '_;
+
+    /// Returns the cost of the cheapest edge from `from` to `to`, or `None`
+    /// if there is none.
+    fn edge_cost(&self, from: usize, to: usize) -> Option<W>
+    where
+        W: Weight,
+    {
+        self.edges_from(from)
+            .filter(|(next, _)| *next == to)
+            .map(|(_, cost)| cost)
+            .reduce(|a, b| if b < a { b } else { a })
+    }
+}
+
+impl<T, W, E> GraphStorage<W> for Graph<T, W, E>
+where
+    T: Clone,
+    W: Weight,
+    E: Clone,
+{
+    fn id_bound(&self) -> usize {
+        self.vertices.len()
+    }
+
+    fn contains_vertex(&self, id: usize) -> bool {
+        self.vertices.get(id).is_some_and(Option::is_some)
+    }
+
+    fn edges_from(&self, id: usize) -> impl Iterator<Item = (usize, W)> + '_ {
+        let live = self.contains_vertex(id);
+        live.then(|| self.outgoing(id).map(|edge| (edge.to, edge.cost)))
+            .into_iter()
+            .flatten()
+    }
+}
@@ -0,0 +1,144 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{Edge, Graph, GraphError, Vertex, Weight};
+
+/// Builds a [`Graph`] from a long stream of edge records between vertex ids,
+/// such as the lines of a multi-gigabyte edge list.
+///
+/// Records are only checked and queued while they come in. The adjacency
+/// lists are built by [`StreamingBuilder::finish`], which counts the edges
+/// at each vertex first and allocates every list once at its final size,
+/// instead of growing hundreds of millions of lists edge by edge as
+/// [`Graph::add_edge`] does.
+#[derive(Debug, Clone)]
+pub struct StreamingBuilder<W = f64> {
+    /// Edges as `(from, to, cost, undirected)`, in the order they came in.
+    edges: Vec<(usize, usize, W, bool)>,
+    /// One more than the highest vertex id seen so far.
+    vertices: usize,
+}
+
+impl<W: Weight> Default for StreamingBuilder<W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<W: Weight> StreamingBuilder<W> {
+    /// Creates a builder without edges.
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Creates a builder with room for `edges` records, so that queueing
+    /// them does not keep reallocating.
+    pub fn with_capacity(edges: usize) -> Self {
+        StreamingBuilder {
+            edges: Vec::with_capacity(edges),
+            vertices: 0,
+        }
+    }
+
+    /// Queues a directed edge from `from` to `to`. Vertices are created by
+    /// [`StreamingBuilder::finish`] for every id up to the highest one seen.
+    ///
+    /// Fails if the cost is NaN.
+    pub fn push(&mut self, from: usize, to: usize, cost: W) -> Result<(), GraphError> {
+        self.queue(from, to, cost, false)
+    }
+
+    /// Like [`StreamingBuilder::push`], but for an edge that can be traversed
+    /// in both directions.
+    pub fn push_undirected(&mut self, a: usize, b: usize, cost: W) -> Result<(), GraphError> {
+        self.queue(a, b, cost, true)
+    }
+
+    /// Queues a directed edge for every `(from, to, cost)` record.
+    ///
+    /// Fails on the first record with a NaN cost, keeping the records
+    /// before it.
+    pub fn extend<I>(&mut self, records: I) -> Result<(), GraphError>
+    where
+        I: IntoIterator<Item = (usize, usize, W)>,
+    {
+        for (from, to, cost) in records {
+            self.push(from, to, cost)?;
+        }
+        Ok(())
+    }
+
+    /// Makes the graph have at least `count` vertices, e.g. to keep vertices
+    /// without edges at the end of the id range.
+    pub fn ensure_vertices(&mut self, count: usize) -> &mut Self {
+        self.vertices = self.vertices.max(count);
+        self
+    }
+
+    /// Returns the number of edges queued so far.
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Builds the graph, with a vertex for every id up to the highest one
+    /// seen, each holding its own id, and edge ids in the order the edges
+    /// were queued.
+    pub fn finish(self) -> Graph<usize, W> {
+        self.finish_with(|id| id)
+    }
+
+    /// Like [`StreamingBuilder::finish`], but gives the vertex with each id
+    /// the value returned by `value`.
+    pub fn finish_with<T, F>(self, mut value: F) -> Graph<T, W>
+    where
+        T: Clone,
+        F: FnMut(usize) -> T,
+    {
+        span!(
+            INFO,
+            "StreamingBuilder::finish",
+            vertices = self.vertices,
+            edges = self.edges.len()
+        );
+        let mut outgoing = vec![0; self.vertices];
+        let mut incoming = vec![0; self.vertices];
+        for &(from, to, _, _) in &self.edges {
+            outgoing[from] += 1;
+            incoming[to] += 1;
+        }
+
+        let mut graph = Graph::new();
+        graph.vertices = (0..self.vertices)
+            .map(|id| {
+                Some(Vertex {
+                    id,
+                    value: value(id),
+                })
+            })
+            .collect();
+        graph.adjacency = outgoing.into_iter().map(Vec::with_capacity).collect();
+        graph.reverse_adjacency = incoming.into_iter().map(Vec::with_capacity).collect();
+        for (from, to, cost, undirected) in self.edges {
+            let edge = if undirected {
+                Edge::new_undirected(to, cost)
+            } else {
+                Edge::new(to, cost)
+            };
+            graph.push_edge(from, edge);
+        }
+        graph
+    }
+
+    fn queue(
+        &mut self,
+        from: usize,
+        to: usize,
+        cost: W,
+        undirected: bool,
+    ) -> Result<(), GraphError> {
+        Graph::<usize, W>::check_cost(cost)?;
+        self.vertices = self.vertices.max(from.max(to) + 1);
+        self.edges.push((from, to, cost, undirected));
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{Edge, Graph, GraphError, Weight};
+
+impl<T, W> Graph<T, W>
+where
+    T: Clone,
+    W: Weight,
+{
+    /// Returns the subgraph induced by `ids`: a new graph with a copy of each
+    /// chosen vertex and of every edge between two of them, together with the
+    /// original id of each new vertex, indexed by its new id.
+    ///
+    /// New ids follow the order of `ids`, ignoring repeats, while edges keep
+    /// their ids. The new graph keeps the insertion policy and, if present,
+    /// the value index of this one.
+    ///
+    /// Fails if any vertex is unknown.
+    pub fn subgraph(&self, ids: &[usize]) -> Result<(Graph<T, W>, Vec<usize>), GraphError> {
+        let mut new_ids: Vec<Option<usize>> = vec![None; self.vertices.len()];
+        let mut original = Vec::with_capacity(ids.len());
+        for id in ids {
+            self.get_vertex(*id)?;
+            if new_ids[*id].is_none() {
+                new_ids[*id] = Some(original.len());
+                original.push(*id);
+            }
+        }
+
+        let mut graph = Graph::with_capacity(original.len(), 0);
+        graph.policy = self.policy;
+        graph.next_edge_id = self.next_edge_id;
+        graph.value_index = self.value_index.as_ref().map(|index| index.cleared());
+        for id in &original {
+            graph.add_vertex(self.vertex(*id).value.clone());
+        }
+        graph.copy_entry_costs(self, original.iter().copied().enumerate());
+        for (from, id) in original.iter().enumerate() {
+            for edge in &self.adjacency[*id] {
+                let Some(to) = new_ids[edge.to] else {
+                    continue;
+                };
+                graph.adjacency[from].push(Edge { to, ..edge.clone() });
+                graph.reverse_adjacency[to].push(Edge {
+                    to: from,
+                    ..edge.clone()
+                });
+            }
+        }
+        Ok((graph, original))
+    }
+}
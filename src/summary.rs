@@ -0,0 +1,134 @@
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+
+use crate::{Graph, GraphError, Weight};
+
+/// An overview of the size and shape of a graph, computed by
+/// [`Graph::stats`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphStats<W = f64> {
+    vertices: usize,
+    edges: usize,
+    arcs: usize,
+    min_cost: Option<W>,
+    max_cost: Option<W>,
+}
+
+impl<W: Weight> GraphStats<W> {
+    /// Returns the number of vertices.
+    pub fn vertices(&self) -> usize {
+        self.vertices
+    }
+
+    /// Returns the number of edges, counting undirected edges once.
+    pub fn edges(&self) -> usize {
+        self.edges
+    }
+
+    /// Returns the number of edges that can be traversed, counting
+    /// undirected edges once per direction.
+    pub fn arcs(&self) -> usize {
+        self.arcs
+    }
+
+    /// Returns the share of ordered pairs of distinct vertices joined by a
+    /// traversable edge, or 0 with fewer than two vertices. Parallel edges and
+    /// self-loops can push it above 1.
+    pub fn density(&self) -> f64 {
+        if self.vertices < 2 {
+            return 0.0;
+        }
+        self.arcs as f64 / (self.vertices * (self.vertices - 1)) as f64
+    }
+
+    /// Returns the mean number of edges that can be traversed out of a
+    /// vertex, or 0 without vertices.
+    pub fn average_degree(&self) -> f64 {
+        if self.vertices == 0 {
+            return 0.0;
+        }
+        self.arcs as f64 / self.vertices as f64
+    }
+
+    /// Returns the cost of the cheapest edge, or `None` without edges.
+    pub fn min_cost(&self) -> Option<W> {
+        self.min_cost
+    }
+
+    /// Returns the cost of the most expensive edge, or `None` without edges.
+    pub fn max_cost(&self) -> Option<W> {
+        self.max_cost
+    }
+}
+
+/// Lists one figure per line.
+impl<W: Weight + Display> Display for GraphStats<W> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(f, "vertices: {}", self.vertices)?;
+        writeln!(f, "edges: {}", self.edges)?;
+        writeln!(f, "density: {}", self.density())?;
+        writeln!(f, "average degree: {}", self.average_degree())?;
+        if let (Some(min), Some(max)) = (self.min_cost, self.max_cost) {
+            writeln!(f, "costs: {} to {}", min, max)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, W, E> Graph<T, W, E>
+where
+    T: Clone,
+    W: Weight,
+    E: Clone,
+{
+    /// Returns the number of vertices, not counting removed ones.
+    pub fn vertex_count(&self) -> usize {
+        self.vertices().count()
+    }
+
+    /// Returns the number of edges, counting undirected edges once.
+    pub fn edge_count(&self) -> usize {
+        self.adjacency.iter().map(Vec::len).sum()
+    }
+
+    /// Returns the number of edges that can be traversed out of `id`,
+    /// including undirected edges added from the other end.
+    ///
+    /// Fails if the vertex is unknown.
+    pub fn out_degree(&self, id: usize) -> Result<usize, GraphError> {
+        self.get_vertex(id)?;
+        Ok(self.outgoing(id).count())
+    }
+
+    /// Returns the number of edges that can be traversed into `id`,
+    /// including undirected edges added from it.
+    ///
+    /// Fails if the vertex is unknown.
+    pub fn in_degree(&self, id: usize) -> Result<usize, GraphError> {
+        self.get_vertex(id)?;
+        Ok(self.incoming(id).count())
+    }
+
+    /// Summarises the size, density and edge costs of the graph, e.g. to
+    /// sanity-check imported data.
+    pub fn stats(&self) -> GraphStats<W> {
+        let mut stats = GraphStats {
+            vertices: self.vertex_count(),
+            edges: 0,
+            arcs: 0,
+            min_cost: None,
+            max_cost: None,
+        };
+        for (_, edge) in self.edges() {
+            stats.edges += 1;
+            stats.arcs += if edge.undirected { 2 } else { 1 };
+            if stats.min_cost.is_none_or(|min| edge.cost < min) {
+                stats.min_cost = Some(edge.cost);
+            }
+            if stats.max_cost.is_none_or(|max| edge.cost > max) {
+                stats.max_cost = Some(edge.cost);
+            }
+        }
+        stats
+    }
+}
@@ -0,0 +1,111 @@
+use std::collections::BinaryHeap;
+
+use crate::min_non_nan::MinNonNan;
+use crate::{GraphError, Grid};
+
+pub(crate) fn euclidean(from: (isize, isize), to: (isize, isize)) -> f64 {
+    (((from.0 - to.0).pow(2) + (from.1 - to.1).pow(2)) as f64).sqrt()
+}
+
+impl Grid {
+    /// Finds a short any-angle path between two walkable cells with Theta*,
+    /// which, unlike an eight-way search, may connect any two cells that can
+    /// see each other and so avoids staircase-shaped paths.
+    ///
+    /// Returns the ids of the cells where the path turns, starting with
+    /// `from` and ending with `to`, and its Euclidean length between cell
+    /// centres.
+    pub fn theta_star(&self, from: usize, to: usize) -> Result<(Vec<usize>, f64), GraphError> {
+        self.check_endpoints(from, to)?;
+        let cells = self.width() * self.height();
+        let goal = self.position(to);
+
+        let mut dist = vec![f64::INFINITY; cells];
+        let mut parent: Vec<usize> = (0..cells).collect();
+        let mut closed = vec![false; cells];
+        let mut open = BinaryHeap::new();
+        dist[from] = 0.0;
+        open.push((MinNonNan(euclidean(self.position(from), goal)), from));
+
+        while let Some((_, current)) = open.pop() {
+            if closed[current] {
+                continue;
+            }
+            closed[current] = true;
+            if current == to {
+                let mut path = vec![to];
+                let mut at = to;
+                while at != from {
+                    at = parent[at];
+                    path.push(at);
+                }
+                path.reverse();
+                return Ok((path, dist[to]));
+            }
+
+            let position = self.position(current);
+            let grandparent = parent[current];
+            for dr in -1..=1 {
+                for dc in -1..=1 {
+                    if (dr, dc) == (0, 0) || !self.can_step(position, (dr, dc)) {
+                        continue;
+                    }
+                    let next = (position.0 + dr, position.1 + dc);
+                    let id = self.id(next.0 as usize, next.1 as usize);
+                    if closed[id] {
+                        continue;
+                    }
+
+                    let (via, new_dist) = if self.line_of_sight(self.position(grandparent), next) {
+                        let seen = self.position(grandparent);
+                        (grandparent, dist[grandparent] + euclidean(seen, next))
+                    } else {
+                        (current, dist[current] + euclidean(position, next))
+                    };
+                    if new_dist < dist[id] {
+                        dist[id] = new_dist;
+                        parent[id] = via;
+                        open.push((MinNonNan(new_dist + euclidean(next, goal)), id));
+                    }
+                }
+            }
+        }
+
+        Err(GraphError::NoPathFound { from, to })
+    }
+
+    /// Whether the segment between the centres of two cells only crosses
+    /// walkable cells. A segment through the corner of two cells needs both
+    /// of them walkable, as diagonal steps do.
+    pub(crate) fn line_of_sight(&self, from: (isize, isize), to: (isize, isize)) -> bool {
+        let (rows, cols) = ((to.0 - from.0).abs(), (to.1 - from.1).abs());
+        let (step_r, step_c) = ((to.0 - from.0).signum(), (to.1 - from.1).signum());
+        let (mut r, mut c) = from;
+        let (mut taken_r, mut taken_c) = (0, 0);
+
+        while taken_r < rows || taken_c < cols {
+            // Compares where the segment leaves the current cell vertically
+            // and horizontally, scaled to stay in integers.
+            let decision = (1 + 2 * taken_c) * rows - (1 + 2 * taken_r) * cols;
+            if decision == 0 {
+                if !self.open((r + step_r, c)) || !self.open((r, c + step_c)) {
+                    return false;
+                }
+                r += step_r;
+                c += step_c;
+                taken_r += 1;
+                taken_c += 1;
+            } else if decision < 0 {
+                c += step_c;
+                taken_c += 1;
+            } else {
+                r += step_r;
+                taken_r += 1;
+            }
+            if !self.open((r, c)) {
+                return false;
+            }
+        }
+        true
+    }
+}
@@ -0,0 +1,151 @@
+use alloc::vec::Vec;
+
+use crate::{Graph, GraphError, Path, Vertex};
+
+/// A piecewise-linear travel time as a function of departure time, held
+/// constant before the first and after the last point.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TravelTimeProfile {
+    /// `(departure, travel time)` points sorted by departure.
+    pub(crate) points: Vec<(f64, f64)>,
+}
+
+impl TravelTimeProfile {
+    /// Creates a profile through the given `(departure, travel time)` points.
+    ///
+    /// Fails with [`GraphError::InvalidCost`] if there are no points, a value
+    /// is NaN, a travel time is negative, two points share a departure, or a
+    /// later departure would arrive earlier, which shortest path searches
+    /// cannot handle.
+    pub fn new(points: &[(f64, f64)]) -> Result<Self, GraphError> {
+        if points.is_empty() {
+            return Err(GraphError::InvalidCost(f64::NAN));
+        }
+        let mut points = points.to_vec();
+        for (departure, travel_time) in &points {
+            if departure.is_nan() || travel_time.is_nan() || *travel_time < 0.0 {
+                return Err(GraphError::InvalidCost(*travel_time));
+            }
+        }
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        for pair in points.windows(2) {
+            let ((first, first_time), (second, second_time)) = (pair[0], pair[1]);
+            if second == first || second + second_time < first + first_time {
+                return Err(GraphError::InvalidCost(second_time));
+            }
+        }
+        Ok(TravelTimeProfile { points })
+    }
+
+    /// Returns the travel time when departing at `departure`.
+    pub fn travel_time(&self, departure: f64) -> f64 {
+        let after = self.points.partition_point(|(time, _)| *time <= departure);
+        if after == 0 {
+            return self.points[0].1;
+        }
+        if after == self.points.len() {
+            return self.points[after - 1].1;
+        }
+
+        let (start, start_time) = self.points[after - 1];
+        let (end, end_time) = self.points[after];
+        let fraction = (departure - start) / (end - start);
+        start_time + (end_time - start_time) * fraction
+    }
+}
+
+impl<T> Graph<T>
+where
+    T: Clone,
+{
+    /// Gives every edge that can be traversed from `from` to `to` a travel
+    /// time profile, including parallel edges and undirected edges between
+    /// the two. Static searches keep using the edges' costs.
+    ///
+    /// Fails if either vertex is unknown or there is no such edge.
+    pub fn set_edge_profile(
+        &mut self,
+        from: usize,
+        to: usize,
+        profile: TravelTimeProfile,
+    ) -> Result<(), GraphError> {
+        self.get_vertex(from)?;
+        self.get_vertex(to)?;
+
+        let mut updated = false;
+        for edge in &mut self.adjacency[from] {
+            if edge.to == to {
+                edge.profile = Some(profile.clone());
+                updated = true;
+            }
+        }
+        for edge in &mut self.adjacency[to] {
+            if edge.undirected && edge.to == from {
+                edge.profile = Some(profile.clone());
+                updated = true;
+            }
+        }
+        for edge in &mut self.reverse_adjacency[to] {
+            if edge.to == from {
+                edge.profile = Some(profile.clone());
+            }
+        }
+        for edge in &mut self.reverse_adjacency[from] {
+            if edge.undirected && edge.to == to {
+                edge.profile = Some(profile.clone());
+            }
+        }
+
+        if !updated {
+            return Err(GraphError::UnknownEdge { from, to });
+        }
+        Ok(())
+    }
+
+    /// Returns the fastest path from `from` to `to` when departing at
+    /// `departure`, costed by travel time. Edges with a profile take the
+    /// travel time for the moment they are entered; other edges take their
+    /// cost.
+    ///
+    /// Fails if either vertex is unknown, `departure` is NaN or `to` is not
+    /// reachable from `from`.
+    pub fn get_shortest_path_departing_at(
+        &self,
+        from: usize,
+        to: usize,
+        departure: f64,
+    ) -> Result<Path<T>, GraphError> {
+        span!(DEBUG, "Graph::get_shortest_path_departing_at", from, to);
+        if departure.is_nan() {
+            return Err(GraphError::InvalidCost(departure));
+        }
+        self.get_vertex(from)?;
+        self.get_vertex(to)?;
+
+        let is_end = |vertex: &Vertex<T>, _| vertex.id == to;
+        let (dist, prev, _) = self.search_costed(
+            &[(from, 0.0)],
+            is_end,
+            |_| 0.0,
+            |_, _| true,
+            |_, edge, elapsed| match &edge.profile {
+                Some(profile) => profile.travel_time(departure + elapsed),
+                None => edge.cost,
+            },
+        );
+        if dist[to] == f64::INFINITY {
+            return Err(GraphError::NoPathFound { from, to });
+        }
+
+        // Steps take the travel time for the moment they are entered, which
+        // the static edge costs do not reflect.
+        let path = self.reconstruct_path(&prev, from, to);
+        let costs = path
+            .windows(2)
+            .map(|step| dist[step[1].id] - dist[step[0].id])
+            .collect();
+        Ok(Path::new(path, costs, dist[to]))
+    }
+}
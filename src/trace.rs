@@ -0,0 +1,21 @@
+// Spans and events for the `tracing` feature, through macros that expand to
+// nothing without it, so that instrumented code needs no `cfg` of its own.
+// Field values are only evaluated when a subscriber is interested.
+
+/// Enters a span at the given level for the rest of the enclosing block,
+/// taking the same arguments as `tracing::span!` after the level.
+macro_rules! span {
+    ($level:ident, $($args:tt)*) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::span!(tracing::Level::$level, $($args)*).entered();
+    };
+}
+
+/// Emits an event at the given level, taking the same arguments as
+/// `tracing::event!` after the level.
+macro_rules! event {
+    ($level:ident, $($args:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::event!(tracing::Level::$level, $($args)*);
+    };
+}
@@ -0,0 +1,124 @@
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::min_non_nan::MinNonNan;
+use crate::{Graph, GraphError, Path};
+
+/// Penalties and restrictions on turning from one edge onto the next at the
+/// vertex between them, for [`Graph::get_shortest_path_with_turns`].
+///
+/// A turn is given by the three vertices it passes, e.g. no left turn
+/// from the edge `(a, b)` onto `(b, c)` is `forbid(a, b, c)`. Turns without a
+/// rule are free.
+#[derive(Debug, Clone, Default)]
+pub struct TurnCosts {
+    /// Extra cost of each turn, infinite if forbidden.
+    turns: HashMap<(usize, usize, usize), f64>,
+}
+
+impl TurnCosts {
+    /// Creates an empty set of turn rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forbids continuing from `from` through `via` to `to`.
+    pub fn forbid(&mut self, from: usize, via: usize, to: usize) {
+        self.turns.insert((from, via, to), f64::INFINITY);
+    }
+
+    /// Adds `penalty` to the cost of continuing from `from` through `via` to
+    /// `to`.
+    ///
+    /// Fails if the penalty is NaN or negative.
+    pub fn penalize(
+        &mut self,
+        from: usize,
+        via: usize,
+        to: usize,
+        penalty: f64,
+    ) -> Result<(), GraphError> {
+        if penalty.is_nan() || penalty < 0.0 {
+            return Err(GraphError::InvalidCost(penalty));
+        }
+        self.turns.insert((from, via, to), penalty);
+        Ok(())
+    }
+
+    /// Returns the extra cost of the turn, infinite if it is forbidden.
+    pub fn cost(&self, from: usize, via: usize, to: usize) -> f64 {
+        self.turns.get(&(from, via, to)).copied().unwrap_or(0.0)
+    }
+}
+
+/// Search state: a vertex together with the vertex it was entered from.
+type State = (Option<usize>, usize);
+
+impl<T> Graph<T>
+where
+    T: Clone,
+{
+    /// Like [`Graph::get_shortest_path`], but adds the penalty of every turn
+    /// taken and never takes a forbidden turn. The returned cost includes the
+    /// penalties.
+    ///
+    /// The search tracks the edge each vertex is entered by, so a vertex may
+    /// appear more than once on the path, e.g. to make up for a forbidden
+    /// left turn with three right turns.
+    pub fn get_shortest_path_with_turns(
+        &self,
+        from: usize,
+        to: usize,
+        turns: &TurnCosts,
+    ) -> Result<Path<T>, GraphError> {
+        span!(DEBUG, "Graph::get_shortest_path_with_turns", from, to);
+        self.get_vertex(from)?;
+        self.get_vertex(to)?;
+
+        let mut dist: HashMap<State, f64> = HashMap::from([((None, from), 0.0)]);
+        let mut prev: HashMap<State, State> = HashMap::new();
+        let mut queue = BinaryHeap::from([(MinNonNan(0.0), None, from)]);
+
+        while let Some((MinNonNan(cost), entered_from, current)) = queue.pop() {
+            let state = (entered_from, current);
+            if cost > dist[&state] {
+                continue;
+            }
+
+            if current == to {
+                let mut states = vec![state];
+                while let Some(before) = prev.get(states.last().unwrap()) {
+                    states.push(*before);
+                }
+                states.reverse();
+                // Each step costs its edge plus the turn onto it.
+                let costs = states
+                    .windows(2)
+                    .map(|step| dist[&step[1]] - dist[&step[0]])
+                    .collect();
+                let path = states
+                    .iter()
+                    .map(|(_, id)| self.vertex(*id).clone())
+                    .collect();
+                return Ok(Path::new(path, costs, cost));
+            }
+
+            for edge in self.outgoing(current) {
+                let turn =
+                    entered_from.map_or(0.0, |previous| turns.cost(previous, current, edge.to));
+                let new_dist = cost + edge.cost + turn;
+                if new_dist == f64::INFINITY {
+                    continue;
+                }
+
+                let next = (Some(current), edge.to);
+                if dist.get(&next).is_none_or(|dist| new_dist < *dist) {
+                    dist.insert(next, new_dist);
+                    prev.insert(next, state);
+                    queue.push((MinNonNan(new_dist), next.0, next.1));
+                }
+            }
+        }
+
+        Err(GraphError::NoPathFound { from, to })
+    }
+}
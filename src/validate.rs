@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+
+use crate::{Graph, GraphError, Weight};
+
+/// A structural problem found by [`Graph::validate`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationIssue {
+    /// An edge leads from or to an id with no vertex.
+    DanglingEdge { from: usize, to: usize },
+    /// An edge cost is NaN, negative or infinite, which most searches reject
+    /// or treat as a missing edge.
+    InvalidCost { from: usize, to: usize, cost: f64 },
+    /// An edge leads from a vertex back to itself.
+    SelfLoop { id: usize },
+    /// More than one edge leads from `from` to `to`.
+    ParallelEdges {
+        from: usize,
+        to: usize,
+        count: usize,
+    },
+    /// The vertices in `ids` cannot be reached from the root.
+    Unreachable { root: usize, ids: Vec<usize> },
+}
+
+impl Display for ValidationIssue {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ValidationIssue::DanglingEdge { from, to } => {
+                write!(f, "The edge from {} to {} has a missing endpoint", from, to)
+            }
+            ValidationIssue::InvalidCost { from, to, cost } => {
+                write!(f, "The edge from {} to {} costs {}", from, to, cost)
+            }
+            ValidationIssue::SelfLoop { id } => write!(f, "Vertex {} has an edge to itself", id),
+            ValidationIssue::ParallelEdges { from, to, count } => {
+                write!(f, "There are {} edges from {} to {}", count, from, to)
+            }
+            ValidationIssue::Unreachable { root, ids } => {
+                write!(f, "{} vertices cannot be reached from {}", ids.len(), root)
+            }
+        }
+    }
+}
+
+/// The outcome of [`Graph::validate`]: every problem found, grouped by kind
+/// and ordered by vertex id within each kind.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidationReport {
+    issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Returns whether no problem was found.
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn issues(&self) -> &[ValidationIssue] {
+        &self.issues
+    }
+}
+
+/// Lists one problem per line.
+impl Display for ValidationReport {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for issue in &self.issues {
+            writeln!(f, "{}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, W> Graph<T, W>
+where
+    T: Clone,
+    W: Weight,
+{
+    /// Checks the graph for problems that would make queries fail or give
+    /// surprising answers, and reports all of them instead of stopping at the
+    /// first. With a `root`, also reports the vertices it cannot reach.
+    ///
+    /// Fails if `root` is an unknown vertex.
+    pub fn validate(&self, root: Option<usize>) -> Result<ValidationReport, GraphError> {
+        let mut dangling = Vec::new();
+        let mut costs = Vec::new();
+        let mut loops = Vec::new();
+        let mut parallel = Vec::new();
+        for (from, edges) in self.adjacency.iter().enumerate() {
+            let mut counts: HashMap<usize, usize> = HashMap::new();
+            for edge in edges {
+                if self.get_vertex(from).is_err() || self.get_vertex(edge.to).is_err() {
+                    dangling.push(ValidationIssue::DanglingEdge { from, to: edge.to });
+                    continue;
+                }
+                #[allow(clippy::eq_op)]
+                let invalid =
+                    edge.cost != edge.cost || edge.cost < W::ZERO || edge.cost == W::INFINITY;
+                if invalid {
+                    costs.push(ValidationIssue::InvalidCost {
+                        from,
+                        to: edge.to,
+                        cost: edge.cost.to_f64(),
+                    });
+                }
+                if edge.to == from {
+                    loops.push(ValidationIssue::SelfLoop { id: from });
+                }
+                *counts.entry(edge.to).or_default() += 1;
+            }
+            let mut repeated: Vec<(usize, usize)> =
+                counts.into_iter().filter(|(_, count)| *count > 1).collect();
+            repeated.sort_unstable();
+            parallel.extend(
+                repeated
+                    .into_iter()
+                    .map(|(to, count)| ValidationIssue::ParallelEdges { from, to, count }),
+            );
+        }
+        loops.dedup();
+
+        let mut issues = dangling;
+        issues.extend(costs);
+        issues.extend(loops);
+        issues.extend(parallel);
+        if let Some(root) = root {
+            let reached = self.reachable(root)?;
+            let ids: Vec<usize> = self
+                .vertices
+                .iter()
+                .flatten()
+                .map(|vertex| vertex.id)
+                .filter(|id| !reached[*id])
+                .collect();
+            if !ids.is_empty() {
+                issues.push(ValidationIssue::Unreachable { root, ids });
+            }
+        }
+        Ok(ValidationReport { issues })
+    }
+}
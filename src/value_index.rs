@@ -0,0 +1,113 @@
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap as HashMap;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use crate::Graph;
+
+/// Ids of the vertices by a hash of their value, kept up to date as vertices
+/// are added and removed once [`Graph::index_values`] has been called.
+#[derive(Clone)]
+pub(crate) struct ValueIndex<T> {
+    /// Hashes a value, fixed when the index is built so that maintaining it
+    /// needs no `Hash` bound.
+    hash: fn(&T) -> u64,
+    ids: HashMap<u64, Vec<usize>>,
+}
+
+impl<T> ValueIndex<T> {
+    /// Returns an index with the same hash but no ids, for a new graph built
+    /// from this one.
+    pub(crate) fn cleared(&self) -> Self {
+        ValueIndex {
+            hash: self.hash,
+            ids: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn insert(&mut self, value: &T, id: usize) {
+        self.ids.entry((self.hash)(value)).or_default().push(id);
+    }
+
+    pub(crate) fn remove(&mut self, value: &T, id: usize) {
+        let hash = (self.hash)(value);
+        if let Some(ids) = self.ids.get_mut(&hash) {
+            ids.retain(|other| *other != id);
+            if ids.is_empty() {
+                self.ids.remove(&hash);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+fn hash_value<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl<T, W, E> Graph<T, W, E>
+where
+    T: Clone,
+    E: Clone,
+{
+    /// Builds an index from vertex values to ids, after which
+    /// [`Graph::find_vertex`] and [`Graph::find_all`] take constant instead of
+    /// linear time. The index is kept up to date as vertices are added and
+    /// removed.
+    ///
+    /// Requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn index_values(&mut self)
+    where
+        T: Hash,
+    {
+        let mut index = ValueIndex {
+            hash: hash_value::<T>,
+            ids: HashMap::new(),
+        };
+        for vertex in self.vertices.iter().flatten() {
+            index.insert(&vertex.value, vertex.id);
+        }
+        self.value_index = Some(index);
+    }
+
+    /// Returns the id of the vertex holding `value`, the smallest one if
+    /// several do.
+    pub fn find_vertex(&self, value: &T) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        self.find_all(value).first().copied()
+    }
+
+    /// Returns the ids of every vertex holding `value`, in ascending order.
+    pub fn find_all(&self, value: &T) -> Vec<usize>
+    where
+        T: PartialEq,
+    {
+        let holds = |id: &usize| {
+            self.vertices[*id]
+                .as_ref()
+                .is_some_and(|vertex| vertex.value == *value)
+        };
+        match &self.value_index {
+            Some(index) => {
+                let candidates = index.ids.get(&(index.hash)(value));
+                let mut ids: Vec<usize> = candidates
+                    .into_iter()
+                    .flatten()
+                    .copied()
+                    .filter(holds)
+                    .collect();
+                ids.sort_unstable();
+                ids
+            }
+            None => (0..self.vertices.len()).filter(holds).collect(),
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+
+/// A vertex of a [`Graph`](crate::Graph), identified by the id returned from
+/// [`Graph::add_vertex`](crate::Graph::add_vertex).
+///
+/// Equality, ordering and hashing only consider the id.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Vertex<T> {
+    pub id: usize,
+    pub value: T,
+}
+
+impl<T> Ord for Vertex<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
+impl<T> PartialOrd for Vertex<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> PartialEq for Vertex<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<T> Eq for Vertex<T> {}
+
+impl<T> Hash for Vertex<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
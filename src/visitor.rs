@@ -0,0 +1,62 @@
+use crate::{Edge, Graph, GraphError, Path, Vertex, Weight};
+
+/// Observer of the progress of a search, for
+/// [`Graph::get_shortest_path_visited`], e.g. to animate the frontier.
+///
+/// Every callback does nothing by default, so an implementation only needs
+/// the ones it cares about.
+#[allow(unused_variables)]
+pub trait SearchVisitor<T, W = f64, E = ()> {
+    /// Called when `vertex` leaves the frontier at its final distance `dist`.
+    fn on_settle(&mut self, vertex: &Vertex<T>, dist: W) {}
+
+    /// Called when `edge` out of `from` lowers the tentative distance of its
+    /// target to `new_dist`, adding it to the frontier or moving it forward.
+    fn on_relax(&mut self, from: &Vertex<T>, edge: &Edge<W, E>, new_dist: W) {}
+
+    /// Called when `edge` out of `from` leaves the distance of its target
+    /// unchanged, because the target is settled or already as close.
+    fn on_skip(&mut self, from: &Vertex<T>, edge: &Edge<W, E>) {}
+}
+
+/// The visitor that ignores everything, used by searches nobody observes.
+impl<T, W, E> SearchVisitor<T, W, E> for () {}
+
+impl<T, W, E> Graph<T, W, E>
+where
+    T: Clone,
+    W: Weight,
+    E: Clone,
+{
+    /// Like [`Graph::get_shortest_path`], but reports the progress of the
+    /// search to `visitor`.
+    ///
+    /// Fails if either vertex is unknown or `to` is not reachable from `from`.
+    pub fn get_shortest_path_visited<V>(
+        &self,
+        from: usize,
+        to: usize,
+        visitor: &mut V,
+    ) -> Result<Path<T, W, E>, GraphError>
+    where
+        V: SearchVisitor<T, W, E>,
+    {
+        span!(DEBUG, "Graph::get_shortest_path_visited", from, to);
+        self.get_vertex(from)?;
+        self.get_vertex(to)?;
+
+        let (dist, prev, _) = self.search_visited(
+            &[(from, W::ZERO)],
+            |vertex, _| vertex.id == to,
+            |_| W::ZERO,
+            |_, _| true,
+            |_, edge, _| edge.cost,
+            visitor,
+        );
+        if dist[to] == W::INFINITY {
+            return Err(GraphError::NoPathFound { from, to });
+        }
+
+        Ok(self.to_path(self.reconstruct_path(&prev, from, to), dist[to]))
+    }
+}
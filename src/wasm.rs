@@ -0,0 +1,85 @@
+use wasm_bindgen::prelude::*;
+
+use crate::Graph;
+
+/// A graph for JavaScript, exported as `Graph`: vertices are plain ids and
+/// edges cost a number.
+///
+/// The crate builds as a Rust library only, so that it also links without
+/// `std`. Build the module with `cargo rustc --lib --release --features wasm
+/// --target wasm32-unknown-unknown --crate-type cdylib` and pass the output
+/// to `wasm-bindgen`.
+#[wasm_bindgen(js_name = Graph)]
+pub struct WasmGraph {
+    graph: Graph<()>,
+}
+
+/// A path found by [`WasmGraph::shortest_path`], exported as `Path`.
+#[wasm_bindgen(js_name = Path)]
+pub struct WasmPath {
+    vertices: Vec<usize>,
+    cost: f64,
+}
+
+#[wasm_bindgen(js_class = Path)]
+impl WasmPath {
+    /// Returns the ids of the vertices along the path, from start to end.
+    #[wasm_bindgen(getter)]
+    pub fn vertices(&self) -> Vec<usize> {
+        self.vertices.clone()
+    }
+
+    /// Returns the sum of the edge costs along the path.
+    #[wasm_bindgen(getter)]
+    pub fn cost(&self) -> f64 {
+        self.cost
+    }
+}
+
+impl Default for WasmGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen(js_class = Graph)]
+impl WasmGraph {
+    /// Creates an empty graph.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        WasmGraph {
+            graph: Graph::new(),
+        }
+    }
+
+    /// Adds a vertex and returns its id.
+    #[wasm_bindgen(js_name = addVertex)]
+    pub fn add_vertex(&mut self) -> usize {
+        self.graph.add_vertex(())
+    }
+
+    /// Adds a directed edge, throwing if either vertex is unknown or the cost
+    /// is NaN.
+    #[wasm_bindgen(js_name = addEdge)]
+    pub fn add_edge(&mut self, from: usize, to: usize, cost: f64) -> Result<(), JsError> {
+        Ok(self.graph.add_edge(from, to, cost)?)
+    }
+
+    /// Adds an edge that can be traversed in both directions, throwing if
+    /// either vertex is unknown or the cost is NaN.
+    #[wasm_bindgen(js_name = addEdgeUndirected)]
+    pub fn add_edge_undirected(&mut self, a: usize, b: usize, cost: f64) -> Result<(), JsError> {
+        Ok(self.graph.add_edge_undirected(a, b, cost)?)
+    }
+
+    /// Returns the cheapest path from `from` to `to`, throwing if either
+    /// vertex is unknown or `to` is not reachable.
+    #[wasm_bindgen(js_name = shortestPath)]
+    pub fn shortest_path(&self, from: usize, to: usize) -> Result<WasmPath, JsError> {
+        let path = self.graph.get_shortest_path(from, to)?;
+        Ok(WasmPath {
+            vertices: path.iter().map(|vertex| vertex.id).collect(),
+            cost: path.cost(),
+        })
+    }
+}
@@ -0,0 +1,58 @@
+/// Edge cost types that shortest path searches can add up and compare.
+///
+/// Implemented for the primitive floats and integers. Searches order costs
+/// with `PartialOrd`, so a cost that does not compare equal to itself, like
+/// NaN, is rejected when an edge is added.
+pub trait Weight: Copy + PartialOrd {
+    /// The cost of an empty path.
+    const ZERO: Self;
+    /// The cost of a single unit step.
+    const ONE: Self;
+    /// The cost of an unreachable vertex, larger than any path's cost.
+    const INFINITY: Self;
+
+    /// Adds two costs, staying at [`Weight::INFINITY`] instead of overflowing.
+    fn saturating_add(self, other: Self) -> Self;
+
+    /// Converts the cost for reporting, e.g. in [`GraphError::InvalidCost`](crate::GraphError::InvalidCost).
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! float_weight {
+    ($($float:ty),*) => {$(
+        impl Weight for $float {
+            const ZERO: Self = 0.0;
+            const ONE: Self = 1.0;
+            const INFINITY: Self = <$float>::INFINITY;
+
+            fn saturating_add(self, other: Self) -> Self {
+                self + other
+            }
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        }
+    )*};
+}
+
+macro_rules! integer_weight {
+    ($($integer:ty),*) => {$(
+        impl Weight for $integer {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            const INFINITY: Self = <$integer>::MAX;
+
+            fn saturating_add(self, other: Self) -> Self {
+                <$integer>::saturating_add(self, other)
+            }
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        }
+    )*};
+}
+
+float_weight!(f32, f64);
+integer_weight!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
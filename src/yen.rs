@@ -0,0 +1,90 @@
+use crate::{Edge, Graph, GraphError, Path, Vertex, Weight};
+
+impl<T, W, E> Graph<T, W, E>
+where
+    T: Clone,
+    W: Weight,
+    E: Clone,
+{
+    /// Returns up to `k` loopless paths from `from` to `to` in increasing
+    /// order of cost, using Yen's algorithm. Paths through the same vertices
+    /// by different parallel edges count as different paths.
+    ///
+    /// Fails if either vertex is unknown or `to` is not reachable from `from`.
+    pub fn get_k_shortest_paths(
+        &self,
+        from: usize,
+        to: usize,
+        k: usize,
+    ) -> Result<Vec<Path<T, W, E>>, GraphError> {
+        let first = self.get_shortest_path(from, to)?;
+
+        let mut paths = vec![first];
+        let mut candidates: Vec<Path<T, W, E>> = Vec::new();
+
+        while paths.len() < k {
+            let last = &paths[paths.len() - 1];
+
+            let mut root_cost = W::ZERO;
+            for i in 0..last.len() - 1 {
+                let spur = last.vertices()[i].id;
+                let root = &last.vertices()[..=i];
+                let root_edges = &last.edge_ids()[..i];
+
+                // Parallel edges have their own ids, so each is banned alone.
+                let banned_edges: Vec<usize> = paths
+                    .iter()
+                    .filter(|path| {
+                        path.len() > i + 1
+                            && path.vertices()[..=i] == *root
+                            && path.edge_ids()[..i] == *root_edges
+                    })
+                    .map(|path| path.edge_ids()[i])
+                    .collect();
+                let filter = |from: &Vertex<T>, edge: &Edge<W, E>| {
+                    !root[..i].iter().any(|vertex| vertex.id == edge.to)
+                        && (from.id != spur || !banned_edges.contains(&edge.id))
+                };
+
+                let (dist, prev) = self.search_filtered(spur, Some(to), |_| W::ZERO, filter);
+
+                if dist[to] != W::INFINITY {
+                    let spur_path = self.to_path_filtered(
+                        self.reconstruct_path(&prev, spur, to),
+                        dist[to],
+                        filter,
+                    );
+                    let path = Path::with_edges(
+                        [&root[..i], spur_path.vertices()].concat(),
+                        [&last.costs()[..i], spur_path.costs()].concat(),
+                        [root_edges, spur_path.edge_ids()].concat(),
+                        [&last.data()[..i], spur_path.data()].concat(),
+                        root_cost.saturating_add(dist[to]),
+                    );
+
+                    let known = |other: &Path<T, W, E>| {
+                        other.vertices() == path.vertices() && other.edge_ids() == path.edge_ids()
+                    };
+                    if !candidates.iter().any(known) && !paths.iter().any(known) {
+                        candidates.push(path);
+                    }
+                }
+
+                root_cost = root_cost.saturating_add(last.costs()[i]);
+            }
+
+            let Some(best) = candidates
+                .iter()
+                .enumerate()
+                .min_by(|(_, a), (_, b)| a.cost().partial_cmp(&b.cost()).unwrap())
+                .map(|(index, _)| index)
+            else {
+                break;
+            };
+            paths.push(candidates.swap_remove(best));
+        }
+
+        paths.truncate(k);
+        Ok(paths)
+    }
+}
@@ -0,0 +1,76 @@
+use std::collections::VecDeque;
+
+use crate::graph::{CompactId, Predecessors};
+use crate::{Edge, Graph, GraphError, Path, Weight};
+
+impl<T, W> Graph<T, W>
+where
+    T: Clone,
+    W: Weight,
+{
+    /// Like [`Graph::get_shortest_path`] for graphs whose edges all cost
+    /// zero or one, together with the [entry cost](Graph::set_entry_cost) of
+    /// the vertex they lead to, in time linear in the size of the graph
+    /// (0-1 BFS).
+    /// Edges of cost [`Weight::INFINITY`] are never traversed.
+    ///
+    /// Fails if either vertex is unknown, an edge has any other cost, or
+    /// `to` is not reachable from `from`.
+    pub fn get_shortest_path_zero_one(
+        &self,
+        from: usize,
+        to: usize,
+    ) -> Result<Path<T, W>, GraphError> {
+        span!(DEBUG, "Graph::get_shortest_path_zero_one", from, to);
+        self.get_vertex(from)?;
+        self.get_vertex(to)?;
+        let unit = |cost: W| cost == W::ZERO || cost == W::ONE || cost == W::INFINITY;
+        let step_cost = |edge: &Edge<W>| edge.cost.saturating_add(self.entry_cost(edge.to));
+        for vertex in self.vertices.iter().flatten() {
+            for edge in self.outgoing(vertex.id) {
+                let cost = step_cost(edge);
+                if !unit(cost) {
+                    return Err(GraphError::InvalidCost(cost.to_f64()));
+                }
+            }
+        }
+
+        let mut dist = vec![W::INFINITY; self.vertices.len()];
+        let mut prev: Predecessors = vec![None; self.vertices.len()];
+        let mut visited = vec![false; self.vertices.len()];
+        dist[from] = W::ZERO;
+        let mut queue = VecDeque::from([from]);
+
+        while let Some(current) = queue.pop_front() {
+            if visited[current] {
+                continue;
+            }
+            visited[current] = true;
+            if current == to {
+                break;
+            }
+
+            for edge in self.outgoing(current) {
+                if edge.cost == W::INFINITY {
+                    continue;
+                }
+                let cost = step_cost(edge);
+                let new_dist = dist[current].saturating_add(cost);
+                if new_dist < dist[edge.to] {
+                    dist[edge.to] = new_dist;
+                    prev[edge.to] = Some(current as CompactId);
+                    if cost == W::ZERO {
+                        queue.push_front(edge.to);
+                    } else {
+                        queue.push_back(edge.to);
+                    }
+                }
+            }
+        }
+
+        if dist[to] == W::INFINITY {
+            return Err(GraphError::NoPathFound { from, to });
+        }
+        Ok(self.to_path(self.reconstruct_path(&prev, from, to), dist[to]))
+    }
+}
@@ -0,0 +1,224 @@
+//! Every search that honours entry costs must agree with
+//! `Graph::get_shortest_path` on them, and the steps of the paths it returns
+//! must add up to the cost it reports.
+
+#![cfg(feature = "std")]
+
+use dijkstra_rust::{DStarLite, DynamicShortestPaths, Graph, Path, QueryOptions};
+
+const VERTICES: usize = 24;
+
+/// A small pseudo-random generator, so that the graphs are the same on every
+/// run.
+struct Lcg(u64);
+
+impl Lcg {
+    fn below(&mut self, bound: u64) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.0 >> 33) % bound
+    }
+}
+
+/// Builds the same random graph with `f64` and `u32` costs: directed and
+/// undirected edges of integer cost, and an entry cost at most vertices.
+/// `unit` limits edges to cost zero and entry costs to zero or one.
+fn graphs(seed: u64, unit: bool) -> (Graph<usize>, Graph<usize, u32>) {
+    let mut rng = Lcg(seed);
+    let mut float = Graph::new();
+    let mut integer = Graph::new();
+    for id in 0..VERTICES {
+        float.add_vertex(id);
+        integer.add_vertex(id);
+    }
+    for _ in 0..VERTICES * 3 {
+        let from = rng.below(VERTICES as u64) as usize;
+        let to = rng.below(VERTICES as u64) as usize;
+        let cost = if unit { 0 } else { rng.below(10) as u32 };
+        if rng.below(3) == 0 {
+            float.add_edge_undirected(from, to, cost as f64).unwrap();
+            integer.add_edge_undirected(from, to, cost).unwrap();
+        } else {
+            float.add_edge(from, to, cost as f64).unwrap();
+            integer.add_edge(from, to, cost).unwrap();
+        }
+    }
+    for id in 0..VERTICES {
+        let cost = rng.below(if unit { 2 } else { 6 }) as u32;
+        float.set_entry_cost(id, cost as f64).unwrap();
+        integer.set_entry_cost(id, cost).unwrap();
+    }
+    (float, integer)
+}
+
+/// The cost of the cheapest path, or `None` if there is none.
+fn expected(graph: &Graph<usize>, from: usize, to: usize) -> Option<f64> {
+    graph
+        .get_shortest_path(from, to)
+        .ok()
+        .map(|path| path.cost())
+}
+
+/// Checks that a path's steps add up to its cost, and returns the cost.
+fn checked<W: Copy + Into<f64>>(costs: &[W], cost: W) -> f64 {
+    let sum: f64 = costs.iter().map(|cost| (*cost).into()).sum();
+    assert_eq!(sum, cost.into(), "steps do not add up to the path cost");
+    cost.into()
+}
+
+#[test]
+fn searches_agree_on_entry_costs() {
+    for seed in 0..20 {
+        let (graph, integer) = graphs(seed, false);
+        let floyd = graph.all_pairs_shortest_paths().unwrap();
+        let johnson = graph.all_pairs_shortest_paths_johnson().unwrap();
+        let flags = graph.build_arc_flags(4).unwrap();
+
+        for from in 0..VERTICES {
+            let tree = graph.shortest_paths_from(from).unwrap();
+            for to in 0..VERTICES {
+                let want = expected(&graph, from, to);
+                let found = |path: Result<Path<usize>, _>| {
+                    path.ok().map(|path| checked(path.costs(), path.cost()))
+                };
+                let found_integer = |path: Result<Path<usize, u32>, _>| {
+                    path.ok().map(|path| checked(path.costs(), path.cost()))
+                };
+
+                assert_eq!(want, found(graph.get_shortest_path_bellman_ford(from, to)));
+                assert_eq!(
+                    want,
+                    found(graph.get_shortest_path_astar(from, to, |_: &usize, _: &usize| 0.0))
+                );
+                assert_eq!(want, found(graph.get_shortest_path_bidirectional(from, to)));
+                assert_eq!(
+                    want,
+                    found(graph.get_shortest_path_arc_flags(from, to, &flags))
+                );
+                assert_eq!(
+                    want,
+                    found(graph.get_shortest_path_with(
+                        from,
+                        to,
+                        QueryOptions::new().break_ties_by_id()
+                    ))
+                );
+                assert_eq!(
+                    want,
+                    found(graph.get_shortest_path_max_hops(from, to, VERTICES))
+                );
+                assert_eq!(
+                    want,
+                    found_integer(integer.get_shortest_path_dial(from, to))
+                );
+                assert_eq!(
+                    want,
+                    integer
+                        .dijkstra_steps(from, to)
+                        .ok()
+                        .and_then(|mut steps| {
+                            steps.by_ref().for_each(drop);
+                            steps.path()
+                        })
+                        .map(|path| checked(path.costs(), path.cost()))
+                );
+
+                let want = want.unwrap_or(f64::INFINITY);
+                assert_eq!(want, floyd.distance(from, to));
+                assert_eq!(want, johnson.distance(from, to));
+                assert_eq!(want, tree.distance_to(to).unwrap_or(f64::INFINITY));
+
+                if from != to && want != f64::INFINITY {
+                    for path in graph.get_all_shortest_paths(from, to, 8).unwrap() {
+                        assert_eq!(want, checked(path.costs(), path.cost()));
+                    }
+                    assert!(graph.count_shortest_paths(from, to).unwrap() >= 1);
+                }
+                if let Ok((first, second)) = graph.get_disjoint_paths(from, to) {
+                    // Two disjoint paths can't beat the cheapest one.
+                    assert!(checked(first.costs(), first.cost()) >= want);
+                    assert!(checked(second.costs(), second.cost()) >= want);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn zero_one_bfs_agrees_on_entry_costs() {
+    for seed in 0..20 {
+        let (graph, integer) = graphs(seed, true);
+        for from in 0..VERTICES {
+            for to in 0..VERTICES {
+                let found = integer
+                    .get_shortest_path_zero_one(from, to)
+                    .ok()
+                    .map(|path| checked(path.costs(), path.cost()));
+                assert_eq!(expected(&graph, from, to), found);
+            }
+        }
+    }
+}
+
+#[test]
+fn dynamic_shortest_paths_repair_with_entry_costs() {
+    for seed in 0..20 {
+        let (graph, _) = graphs(seed, false);
+        let mut dynamic = DynamicShortestPaths::new(graph, 0).unwrap();
+        let mut rng = Lcg(seed);
+        for _ in 0..10 {
+            let from = rng.below(VERTICES as u64) as usize;
+            let to = rng.below(VERTICES as u64) as usize;
+            let cost = rng.below(10) as f64;
+            if rng.below(2) == 0 {
+                dynamic.add_edge(from, to, cost).unwrap();
+            } else if dynamic.update_edge_cost(from, to, cost).is_err() {
+                continue;
+            }
+            for to in 0..VERTICES {
+                assert_eq!(
+                    expected(dynamic.graph(), 0, to),
+                    dynamic.tree().distance_to(to)
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn d_star_lite_replans_with_entry_costs() {
+    for seed in 0..20 {
+        let mut rng = Lcg(seed);
+        let mut graph = Graph::new();
+        for id in 0..VERTICES {
+            graph.add_vertex(id);
+        }
+        for _ in 0..VERTICES * 3 {
+            let from = rng.below(VERTICES as u64) as usize;
+            let to = rng.below(VERTICES as u64) as usize;
+            graph
+                .add_edge(from, to, rng.below(10) as f64 + 1.0)
+                .unwrap();
+        }
+        for id in 0..VERTICES {
+            graph.set_entry_cost(id, rng.below(6) as f64).unwrap();
+        }
+
+        let goal = VERTICES - 1;
+        let mut planner = DStarLite::new(graph, 0, goal).unwrap();
+        for _ in 0..10 {
+            let want = expected(planner.graph(), planner.start(), goal);
+            let found = planner
+                .path()
+                .ok()
+                .map(|path| checked(path.costs(), path.cost()));
+            assert_eq!(want, found);
+
+            let from = rng.below(VERTICES as u64) as usize;
+            let to = rng.below(VERTICES as u64) as usize;
+            let _ = planner.update_edge_cost(from, to, rng.below(10) as f64 + 1.0);
+        }
+    }
+}